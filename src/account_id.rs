@@ -0,0 +1,47 @@
+use std::ops::Deref;
+
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::env;
+
+/// An address that can take part in a transfer or an approval: neither the zero address nor this
+/// contract itself. Entrypoints take it instead of a raw `Address` for counterparties, so such
+/// arguments are rejected while the call input is decoded.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "Address", into = "Address")]
+pub struct AccountId(Address);
+
+impl AccountId {
+    fn is_zero(address: &Address) -> bool {
+        address.as_bytes().iter().all(|byte| *byte == 0)
+    }
+}
+
+impl TryFrom<Address> for AccountId {
+    type Error = String;
+
+    fn try_from(address: Address) -> Result<Self, Self::Error> {
+        if Self::is_zero(&address) {
+            return Err("The zero address is not a valid account".to_string());
+        }
+        if address == env::contract_instance_address() {
+            return Err("The contract's own address is not a valid account".to_string());
+        }
+        Ok(Self(address))
+    }
+}
+
+impl From<AccountId> for Address {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0
+    }
+}
+
+impl Deref for AccountId {
+    type Target = Address;
+
+    fn deref(&self) -> &Address {
+        &self.0
+    }
+}
@@ -0,0 +1,143 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy)]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy)]
+pub enum AlertKind {
+    Transfer,
+    Balance,
+    Allowance,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct AlertThreshold {
+    amount: U128,
+    severity: AlertSeverity,
+}
+
+/// Amounts that emit an `Alert` event when crossed, for monitoring that only watches events.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct AlertThresholds {
+    large_transfer: Option<AlertThreshold>,
+    large_balance: Option<AlertThreshold>,
+    large_allowance: Option<AlertThreshold>,
+}
+
+impl AlertThresholds {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        for threshold in [
+            &mut self.large_transfer,
+            &mut self.large_balance,
+            &mut self.large_allowance,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            threshold.amount = rescale(threshold.amount.0).into();
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_alert_thresholds(thresholds: AlertThresholds) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        *contract.alert_thresholds = thresholds;
+
+        contract.save();
+    }
+
+    pub fn alert_thresholds() -> AlertThresholds {
+        let contract = Self::load();
+        contract.alert_thresholds.into_inner()
+    }
+
+    pub(crate) fn check_transfer_alert(
+        &self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) {
+        if let Some(threshold) = &self.alert_thresholds.large_transfer {
+            if amount > threshold.amount.0 {
+                Self::alert(
+                    AlertKind::Transfer,
+                    threshold,
+                    sender_id,
+                    Some(recipient_id),
+                    amount,
+                );
+            }
+        }
+    }
+
+    /// Alerts when a balance moves from at or below the threshold to above it.
+    pub(crate) fn check_balance_alert(
+        &self,
+        account_id: &Address,
+        old_balance: u128,
+        new_balance: u128,
+    ) {
+        if let Some(threshold) = &self.alert_thresholds.large_balance {
+            if old_balance <= threshold.amount.0 && new_balance > threshold.amount.0 {
+                Self::alert(AlertKind::Balance, threshold, account_id, None, new_balance);
+            }
+        }
+    }
+
+    pub(crate) fn check_allowance_alert(
+        &self,
+        owner_id: &Address,
+        spender_id: &Address,
+        old_allowance: u128,
+        new_allowance: u128,
+    ) {
+        if let Some(threshold) = &self.alert_thresholds.large_allowance {
+            if old_allowance <= threshold.amount.0 && new_allowance > threshold.amount.0 {
+                Self::alert(
+                    AlertKind::Allowance,
+                    threshold,
+                    owner_id,
+                    Some(spender_id),
+                    new_allowance,
+                );
+            }
+        }
+    }
+
+    fn alert(
+        kind: AlertKind,
+        threshold: &AlertThreshold,
+        account_id: &Address,
+        related_id: Option<&Address>,
+        amount: u128,
+    ) {
+        FtEvent::Alert {
+            kind,
+            severity: threshold.severity,
+            account_id: *account_id,
+            related_id: related_id.copied(),
+            amount,
+            threshold: threshold.amount.0,
+        }
+        .emit();
+    }
+}
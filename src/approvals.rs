@@ -0,0 +1,274 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// An account's own list of spenders it allows approvals to, once enabled.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct TrustedSpenders {
+    enabled: bool,
+    spenders: BTreeSet<Address>,
+}
+
+/// Owner-curated spenders. When enabled, approvals to anyone else are rejected.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct SpenderRegistry {
+    enabled: bool,
+    spenders: BTreeSet<Address>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ApprovalView {
+    account_id: Address,
+    amount: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_spender_allowlist_enabled(enabled: bool) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+
+        let mut trusted = contract
+            .trusted_spenders
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default();
+        trusted.enabled = enabled;
+        contract.trusted_spenders.insert(account_id, trusted);
+
+        contract.save();
+    }
+
+    pub fn add_trusted_spender(spender_id: AccountId) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+
+        let mut trusted = contract
+            .trusted_spenders
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            trusted.spenders.insert(*spender_id),
+            "This address is already a trusted spender"
+        );
+        contract.trusted_spenders.insert(account_id, trusted);
+
+        contract.save();
+    }
+
+    pub fn remove_trusted_spender(spender_id: Address) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+
+        let mut trusted = contract
+            .trusted_spenders
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            trusted.spenders.remove(&spender_id),
+            "This address is not a trusted spender"
+        );
+        contract.trusted_spenders.insert(account_id, trusted);
+
+        contract.save();
+    }
+
+    pub fn trusted_spenders_of(account_id: Address) -> TrustedSpenders {
+        let contract = Self::load();
+        contract
+            .trusted_spenders
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set_spender_registry_enabled(enabled: bool) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.spender_registry.enabled = enabled;
+
+        contract.save();
+    }
+
+    pub fn add_registered_spender(spender_id: AccountId) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.spender_registry.spenders.insert(*spender_id),
+            "This address is already a registered spender"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_registered_spender(spender_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.spender_registry.spenders.remove(&spender_id),
+            "This address is not a registered spender"
+        );
+
+        contract.save();
+    }
+
+    pub fn spender_registry() -> SpenderRegistry {
+        let contract = Self::load();
+        contract.spender_registry.into_inner()
+    }
+
+    /// Spenders `owner_id` has approved, in address order. Pass the last `account_id` of a page
+    /// as `from` to get the next one.
+    pub fn ft_approvals_granted(
+        owner_id: Address,
+        from: Option<Address>,
+        limit: u32,
+    ) -> Vec<ApprovalView> {
+        assert_ne!(limit, 0, "Limit should be greater than 0");
+        let contract = Self::load();
+        let Some(allowance) = contract.allowances.get(&owner_id) else {
+            return Vec::new();
+        };
+
+        allowance
+            .spenders
+            .iter()
+            .filter(|(spender_id, _)| from.map_or(true, |from| **spender_id > from))
+            .filter(|(_, amount)| **amount != 0)
+            .take(limit as usize)
+            .map(|(spender_id, amount)| ApprovalView {
+                account_id: *spender_id,
+                amount: (*amount).into(),
+            })
+            .collect()
+    }
+
+    /// Owners that have approved `spender_id`, starting at position `cursor` of the spender's
+    /// list. Revoking an approval moves the last owner into its place, so the order can change
+    /// between pages.
+    pub fn ft_approvals_received(
+        spender_id: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> Vec<ApprovalView> {
+        assert_ne!(limit, 0, "Limit should be greater than 0");
+        let contract = Self::load();
+        let count = contract.approvals_received_count(&spender_id);
+        let end = cursor.saturating_add(limit).min(count);
+
+        (cursor.min(end)..end)
+            .filter_map(|position| {
+                let owner_id = *contract.approvals_received.get(&(spender_id, position))?;
+                Some(ApprovalView {
+                    account_id: owner_id,
+                    amount: contract.allowance_of(&owner_id, &spender_id).into(),
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn allowance_of(&self, owner_id: &Address, spender_id: &Address) -> u128 {
+        self.allowances
+            .get(owner_id)
+            .map_or(0, |allowance| allowance.get(spender_id))
+    }
+
+    /// Keeps the spender -> owners index in line with the allowance just written.
+    pub(crate) fn index_approval(
+        &mut self,
+        owner_id: &Address,
+        spender_id: &Address,
+        amount: u128,
+    ) {
+        let position = self
+            .approval_positions
+            .get(&(*spender_id, *owner_id))
+            .copied();
+        match (position, amount) {
+            (None, 0) | (Some(_), 1..) => {}
+            (None, _) => {
+                let count = self.approvals_received_count(spender_id);
+                self.approvals_received
+                    .insert((*spender_id, count), *owner_id);
+                self.approval_positions
+                    .insert((*spender_id, *owner_id), count);
+                self.approvals_received_count.insert(*spender_id, count + 1);
+            }
+            (Some(position), 0) => {
+                self.approval_positions.remove((*spender_id, *owner_id));
+                let last = self.approvals_received_count(spender_id) - 1;
+                let last_owner_id = self
+                    .approvals_received
+                    .remove((*spender_id, last))
+                    .expect("Approval index is corrupted");
+                if position != last {
+                    self.approvals_received
+                        .insert((*spender_id, position), last_owner_id);
+                    self.approval_positions
+                        .insert((*spender_id, last_owner_id), position);
+                }
+                if last == 0 {
+                    self.approvals_received_count.remove(*spender_id);
+                } else {
+                    self.approvals_received_count.insert(*spender_id, last);
+                }
+            }
+        }
+    }
+
+    /// Removes `spender_id` from the index and returns the owners that had approved it.
+    pub(crate) fn take_approvals_received(&mut self, spender_id: &Address) -> Vec<Address> {
+        let count = self.approvals_received_count(spender_id);
+        self.approvals_received_count.remove(*spender_id);
+        (0..count)
+            .filter_map(|position| self.approvals_received.remove((*spender_id, position)))
+            .inspect(|owner_id| {
+                self.approval_positions.remove((*spender_id, *owner_id));
+            })
+            .collect()
+    }
+
+    fn approvals_received_count(&self, spender_id: &Address) -> u32 {
+        self.approvals_received_count
+            .get(spender_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn assert_approval_allowed(&self, owner_id: &Address, spender_id: &Address) {
+        assert!(
+            !self.spender_registry.enabled || self.spender_registry.spenders.contains(spender_id),
+            "'{}' is not a registered spender",
+            spender_id
+        );
+        if let Some(trusted) = self.trusted_spenders.get(owner_id) {
+            assert!(
+                !trusted.enabled || trusted.spenders.contains(spender_id),
+                "'{}' is not on the owner's trusted spender list",
+                spender_id
+            );
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::contract_owner_address;
+use crate::roles::Role;
+use crate::L1xFtErc20;
+
+#[derive(Serialize, Deserialize)]
+pub enum AuditSection {
+    Balances,
+    Allowances,
+    Roles,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum AuditEntry {
+    Balance {
+        account_id: Address,
+        amount: U128,
+    },
+    Allowance {
+        owner_id: Address,
+        spender_id: Address,
+        amount: U128,
+    },
+    Role {
+        role: String,
+        account_id: Address,
+    },
+}
+
+/// A page of the export. `sequence` changes with every write, so a changed value between pages
+/// means the state moved while it was being read.
+#[derive(Serialize, Deserialize)]
+pub struct AuditSnapshotPage {
+    sequence: U64,
+    entries: Vec<AuditEntry>,
+    next_cursor: Option<U64>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Pages through a section in a fixed order. Balances and allowances follow the order
+    /// in which accounts first appeared; `cursor` is a position in that order.
+    pub fn export_audit_snapshot(
+        section: AuditSection,
+        cursor: U64,
+        limit: u32,
+    ) -> AuditSnapshotPage {
+        assert_ne!(limit, 0, "Limit should be greater than 0");
+        let contract = Self::load();
+
+        let (entries, next_cursor) = match section {
+            AuditSection::Balances => contract.audit_accounts_page(cursor.0, limit, |account_id| {
+                let amount = contract.balance_of(account_id).unwrap_or_default();
+                (amount != 0)
+                    .then(|| AuditEntry::Balance {
+                        account_id: *account_id,
+                        amount: amount.into(),
+                    })
+                    .into_iter()
+                    .collect()
+            }),
+            AuditSection::Allowances => contract.audit_accounts_page(cursor.0, limit, |owner_id| {
+                contract
+                    .allowances
+                    .get(owner_id)
+                    .map(|allowance| {
+                        allowance
+                            .spenders
+                            .iter()
+                            .filter(|(_, amount)| **amount != 0)
+                            .map(|(spender_id, amount)| AuditEntry::Allowance {
+                                owner_id: *owner_id,
+                                spender_id: *spender_id,
+                                amount: (*amount).into(),
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }),
+            AuditSection::Roles => {
+                let roles = contract.audit_roles();
+                let start = (cursor.0 as usize).min(roles.len());
+                let end = start.saturating_add(limit as usize).min(roles.len());
+                let next_cursor = (end < roles.len()).then_some(end as u64);
+                let entries = roles
+                    .into_iter()
+                    .skip(start)
+                    .take(end - start)
+                    .map(|(role, account_id)| AuditEntry::Role {
+                        role: role.to_string(),
+                        account_id,
+                    })
+                    .collect();
+                (entries, next_cursor)
+            }
+        };
+
+        AuditSnapshotPage {
+            sequence: contract.state_sequence.into(),
+            entries,
+            next_cursor: next_cursor.map(Into::into),
+        }
+    }
+
+    pub fn audit_sequence() -> U64 {
+        let contract = Self::load();
+        contract.state_sequence.into()
+    }
+
+    /// Adds an account to the audit index the first time it holds a balance or grants an allowance.
+    pub(crate) fn index_account(&mut self, account_id: &Address) {
+        if self.indexed_accounts.contains_key(account_id) {
+            return;
+        }
+        self.indexed_accounts
+            .insert(*account_id, self.accounts.len());
+        self.accounts.push(*account_id);
+    }
+
+    fn audit_accounts_page(
+        &self,
+        cursor: u64,
+        limit: u32,
+        entries_of: impl Fn(&Address) -> Vec<AuditEntry>,
+    ) -> (Vec<AuditEntry>, Option<u64>) {
+        let len = u64::from(self.accounts.len());
+        let start = cursor.min(len);
+        let end = start.saturating_add(limit.into()).min(len);
+        let entries = (start..end)
+            .filter_map(|index| self.accounts.get(index as u32))
+            .flat_map(entries_of)
+            .collect();
+        (entries, (end < len).then_some(end))
+    }
+
+    fn audit_roles(&self) -> Vec<(&'static str, Address)> {
+        let mut roles = vec![("OWNER", contract_owner_address())];
+        for role in Role::ALL {
+            if let Some(members) = self.roles.get(&role) {
+                roles.extend(members.iter().map(|account_id| (role.name(), *account_id)));
+            }
+        }
+        roles
+    }
+}
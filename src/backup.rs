@@ -0,0 +1,161 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct BackupRecord {
+    backup_id: Address,
+    flagged_at: Option<U128>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn register_backup(backup_id: AccountId) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+        assert_ne!(account_id, *backup_id, "An account can't be its own backup");
+        contract.assert_not_compromised(&account_id);
+
+        contract.backups.insert(
+            account_id,
+            BackupRecord {
+                backup_id: *backup_id,
+                flagged_at: None,
+            },
+        );
+
+        contract.save();
+    }
+
+    pub fn set_evacuation_delay(delay: U64) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.evacuation_delay = delay.0;
+
+        contract.save();
+    }
+
+    /// Blocks outgoing transfers of `account_id` and starts the evacuation delay.
+    ///
+    /// Callable by the contract owner or the account's backup.
+    pub fn flag_compromised(account_id: Address) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let mut record = contract
+            .backups
+            .get(&account_id)
+            .cloned()
+            .expect("The account has no backup address");
+        assert!(
+            caller_id == contract_owner_address() || caller_id == record.backup_id,
+            "Only the owner or the backup address can flag an account"
+        );
+        assert!(
+            record.flagged_at.is_none(),
+            "The account is already flagged"
+        );
+
+        record.flagged_at = Some(env::block_timestamp().into());
+        contract.backups.insert(account_id, record);
+        FtEvent::AccountFlagged { account_id }.emit();
+
+        contract.save();
+    }
+
+    /// Callable by the contract owner or the account's backup. The flagged key itself can't, as
+    /// whoever stole it could clear the flag and drain the account.
+    pub fn clear_compromised_flag(account_id: Address) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let mut record = contract
+            .backups
+            .get(&account_id)
+            .cloned()
+            .expect("The account has no backup address");
+        assert!(
+            caller_id == contract_owner_address() || caller_id == record.backup_id,
+            "Only the owner or the backup address can clear the flag"
+        );
+        assert!(record.flagged_at.is_some(), "The account is not flagged");
+        record.flagged_at = None;
+        contract.backups.insert(account_id, record);
+
+        contract.save();
+    }
+
+    /// Moves the balance of a flagged account to its backup once the delay has passed. Frozen
+    /// tokens move too and stay frozen on the backup.
+    pub fn evacuate(account_id: Address) -> U128 {
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+
+        let record = contract
+            .backups
+            .get(&account_id)
+            .cloned()
+            .expect("The account has no backup address");
+        assert_eq!(
+            caller_address(),
+            record.backup_id,
+            "Only the backup address can evacuate"
+        );
+        let flagged_at = record.flagged_at.expect("The account is not flagged");
+        assert!(
+            env::block_timestamp() >= flagged_at.0 + u128::from(contract.evacuation_delay),
+            "The evacuation delay has not passed yet"
+        );
+
+        // Held and prepared amounts stay behind, they are still owed to someone else.
+        let balance = contract.balance_of(&account_id).unwrap_or_default();
+        let amount = balance
+            .saturating_sub(contract.held_amount(&account_id))
+            .saturating_sub(contract.prepared_amount(&account_id));
+        let frozen = contract.frozen_amount(&account_id).min(amount);
+        if frozen > 0 {
+            contract.move_freeze(&account_id, &record.backup_id, frozen);
+        }
+        contract.set_balance(&account_id, balance - amount);
+        let backup_balance = contract.balance_of(&record.backup_id).unwrap_or_default();
+        contract.set_balance(
+            &record.backup_id,
+            backup_balance
+                .checked_add(amount)
+                .expect("Balance overflowed"),
+        );
+        FtEvent::AccountEvacuated {
+            account_id,
+            backup_id: record.backup_id,
+            amount,
+        }
+        .emit();
+
+        contract.save();
+        amount.into()
+    }
+
+    pub fn backup_of(account_id: Address) -> Option<BackupRecord> {
+        let contract = Self::load();
+        contract.backups.get(&account_id).cloned()
+    }
+
+    pub(crate) fn assert_not_compromised(&self, account_id: &Address) {
+        let flagged = self
+            .backups
+            .get(account_id)
+            .map_or(false, |record| record.flagged_at.is_some());
+        assert!(!flagged, "'{}' is flagged as compromised", account_id);
+    }
+}
@@ -0,0 +1,219 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+/// Leaves are addressed by the account's position in the audit index, so a u32 index needs 32 levels.
+pub(crate) const TREE_DEPTH: usize = 32;
+
+pub(crate) type NodeHash = [u8; 32];
+
+fn leaf_hash(account_id: &Address, balance: u128) -> NodeHash {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(account_id.as_bytes());
+    hasher.update(balance.to_le_bytes());
+    hasher.finalize().into()
+}
+
+pub(crate) fn node_hash(left: &NodeHash, right: &NodeHash) -> NodeHash {
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Hash of an empty subtree at each level, the empty leaf being all zeroes.
+pub(crate) fn empty_hashes() -> [NodeHash; TREE_DEPTH + 1] {
+    let mut hashes = [[0u8; 32]; TREE_DEPTH + 1];
+    for level in 0..TREE_DEPTH {
+        hashes[level + 1] = node_hash(&hashes[level], &hashes[level]);
+    }
+    hashes
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct BalancesRoot {
+    root: NodeHash,
+    sequence: u64,
+    leaves: u32,
+    committed_at: u128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BalancesRootView {
+    root: String,
+    sequence: U64,
+    leaves: u32,
+    committed_at: U128,
+    pending_leaves: u32,
+}
+
+/// Everything needed to check `balance` of `account_id` against `root` off chain. Siblings are
+/// listed from the leaf level up; bit `n` of `index` tells whether the node at level `n` is a right
+/// child.
+#[derive(Serialize, Deserialize)]
+pub struct MerkleProof {
+    account_id: Address,
+    index: u32,
+    balance: U128,
+    root: String,
+    sequence: U64,
+    siblings: Vec<String>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Rehashes up to `max_leaves` changed balances. Once none are left the new root is published
+    /// and `true` is returned; otherwise call again to continue.
+    ///
+    /// A reflection fee changes the balance of every included holder, so it queues every leaf
+    /// again. A commit only completes once a full pass runs without a reflection fee in between.
+    pub fn commit_balances_root(max_leaves: u32) -> bool {
+        assert_ne!(max_leaves, 0, "Max leaves should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let empty = empty_hashes();
+        let mut budget = max_leaves;
+        if let Some(cursor) = contract.merkle_rehash_cursor {
+            let end = cursor.saturating_add(budget).min(contract.accounts.len());
+            for index in cursor..end {
+                contract.update_balance_leaf(index, &empty);
+            }
+            budget -= end - cursor;
+            contract.merkle_rehash_cursor = (end < contract.accounts.len()).then_some(end);
+        }
+        for _ in 0..budget {
+            let Some(index) = contract.merkle_dirty.pop() else {
+                break;
+            };
+            contract.merkle_dirty_flags.remove(index);
+            contract.update_balance_leaf(index, &empty);
+        }
+
+        let complete = contract.merkle_rehash_cursor.is_none() && contract.merkle_dirty.is_empty();
+        if complete {
+            let root = contract.merkle_node(TREE_DEPTH as u8, 0, &empty);
+            let leaves = contract.accounts.len();
+            contract.balances_root = Some(BalancesRoot {
+                root,
+                sequence: contract.state_sequence,
+                leaves,
+                committed_at: env::block_timestamp(),
+            });
+            FtEvent::BalancesRootCommitted { root, leaves }.emit();
+        }
+
+        contract.save();
+        complete
+    }
+
+    pub fn balances_root() -> Option<BalancesRootView> {
+        let contract = Self::load();
+        let pending_leaves = contract.merkle_dirty.len()
+            + contract
+                .merkle_rehash_cursor
+                .map_or(0, |cursor| contract.accounts.len() - cursor);
+        contract.balances_root.map(|committed| BalancesRootView {
+            root: hex::encode(committed.root),
+            sequence: committed.sequence.into(),
+            leaves: committed.leaves,
+            committed_at: committed.committed_at.into(),
+            pending_leaves,
+        })
+    }
+
+    /// Proves the account's balance as of the committed root, which may differ from its current one.
+    pub fn ft_balance_proof(account_id: Address) -> MerkleProof {
+        let contract = Self::load();
+        let committed = contract
+            .balances_root
+            .clone()
+            .expect("No balances root has been committed");
+        let empty = empty_hashes();
+        assert!(
+            contract.merkle_node(TREE_DEPTH as u8, 0, &empty) == committed.root,
+            "A balances root commit is in progress"
+        );
+        let index = *contract
+            .indexed_accounts
+            .get(&account_id)
+            .filter(|index| **index < committed.leaves)
+            .expect("The account is not in the committed root");
+
+        let siblings = (0..TREE_DEPTH as u8)
+            .map(|level| hex::encode(contract.merkle_node(level, (index >> level) ^ 1, &empty)))
+            .collect();
+        MerkleProof {
+            account_id,
+            index,
+            balance: contract
+                .merkle_leaf_balances
+                .get(&index)
+                .copied()
+                .unwrap_or_default()
+                .into(),
+            root: hex::encode(committed.root),
+            sequence: committed.sequence.into(),
+            siblings,
+        }
+    }
+
+    pub(crate) fn balances_root_hash(&self) -> Option<NodeHash> {
+        self.balances_root.as_ref().map(|committed| committed.root)
+    }
+
+    /// Queues the account's leaf for the next `commit_balances_root`.
+    pub(crate) fn mark_balance_leaf_dirty(&mut self, account_id: &Address) {
+        let index = *self
+            .indexed_accounts
+            .get(account_id)
+            .expect("The account is not indexed");
+        if self.merkle_dirty_flags.insert(index, true).is_none() {
+            self.merkle_dirty.push(index);
+        }
+    }
+
+    /// Queues every leaf for the next `commit_balances_root`, from the first one.
+    #[cfg(feature = "reflection")]
+    pub(crate) fn mark_all_balance_leaves_dirty(&mut self) {
+        self.merkle_rehash_cursor = Some(0);
+    }
+
+    pub(crate) fn merkle_node(&self, level: u8, position: u32, empty: &[NodeHash]) -> NodeHash {
+        self.merkle_nodes
+            .get(&(level, position))
+            .copied()
+            .unwrap_or(empty[level as usize])
+    }
+
+    fn update_balance_leaf(&mut self, index: u32, empty: &[NodeHash]) {
+        let account_id = *self.accounts.get(index).expect("Account not found");
+        let balance = self.balance_of(&account_id).unwrap_or_default();
+
+        let mut hash = leaf_hash(&account_id, balance);
+        self.merkle_nodes.insert((0, index), hash);
+        self.merkle_leaf_balances.insert(index, balance);
+        for level in 0..TREE_DEPTH as u8 {
+            let position = index >> level;
+            let sibling = self.merkle_node(level, position ^ 1, empty);
+            hash = if position & 1 == 0 {
+                node_hash(&hash, &sibling)
+            } else {
+                node_hash(&sibling, &hash)
+            };
+            self.merkle_nodes.insert((level + 1, position >> 1), hash);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::decimals;
+use crate::env::{self, caller_address};
+use crate::L1xFtErc20;
+
+/// A cap on how much a spender can move out of an account per period, on top of its allowance.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct SpendingBudget {
+    amount: u128,
+    period: u64,
+    period_start: u128,
+    spent: u128,
+    /// The token decimals the amounts are in. Budgets aren't enumerable, so a decimals migration
+    /// leaves them be and they are rescaled when read.
+    decimals: u8,
+}
+
+impl SpendingBudget {
+    fn rescale_to(&mut self, decimals: u8) {
+        self.amount = decimals::rescale(self.decimals, decimals, self.amount);
+        self.spent = decimals::rescale(self.decimals, decimals, self.spent);
+        self.decimals = decimals;
+    }
+
+    /// Moves the window forward to the period containing `now`, clearing what was spent.
+    fn roll(&mut self, now: u128) {
+        let elapsed_periods = now.saturating_sub(self.period_start) / u128::from(self.period);
+        if elapsed_periods > 0 {
+            self.period_start += elapsed_periods * u128::from(self.period);
+            self.spent = 0;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SpendingBudgetView {
+    amount: U128,
+    period: U64,
+    remaining: U128,
+    resets_at: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Caps what `spender_id` can move out of the caller's account with `ft_transfer_from` to
+    /// `amount` per `period`. The first period starts now.
+    pub fn set_spending_budget(spender_id: AccountId, amount: U128, period: U64) {
+        assert_ne!(period.0, 0, "Period should be greater than 0");
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+        assert_ne!(
+            owner_id, *spender_id,
+            "Owner and spender cannot be the same"
+        );
+
+        contract.spending_budgets.insert(
+            (owner_id, *spender_id),
+            SpendingBudget {
+                amount: amount.0,
+                period: period.0,
+                period_start: env::block_timestamp(),
+                spent: 0,
+                decimals: contract.metadata.decimals,
+            },
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_spending_budget(spender_id: Address) {
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+        assert!(
+            contract
+                .spending_budgets
+                .remove((owner_id, spender_id))
+                .is_some(),
+            "This spender has no budget"
+        );
+
+        contract.save();
+    }
+
+    pub fn spending_budget(owner_id: Address, spender_id: Address) -> Option<SpendingBudgetView> {
+        let contract = Self::load();
+        let mut budget = contract
+            .spending_budgets
+            .get(&(owner_id, spender_id))?
+            .clone();
+        budget.rescale_to(contract.metadata.decimals);
+        budget.roll(env::block_timestamp());
+        Some(SpendingBudgetView {
+            amount: budget.amount.into(),
+            period: budget.period.into(),
+            remaining: budget.amount.saturating_sub(budget.spent).into(),
+            resets_at: (budget.period_start + u128::from(budget.period)).into(),
+        })
+    }
+
+    /// Charges `amount` to the spender's budget, if the owner has set one.
+    pub(crate) fn spend_budget(&mut self, owner_id: &Address, spender_id: &Address, amount: u128) {
+        let decimals = self.metadata.decimals;
+        let Some(budget) = self.spending_budgets.get_mut(&(*owner_id, *spender_id)) else {
+            return;
+        };
+        budget.rescale_to(decimals);
+        budget.roll(env::block_timestamp());
+        let spent = budget.spent.checked_add(amount).expect("amount overflowed");
+        assert!(
+            spent <= budget.amount,
+            "The spender's budget for this period is exceeded"
+        );
+        budget.spent = spent;
+    }
+}
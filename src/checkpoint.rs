@@ -0,0 +1,136 @@
+use borsh::BorshSerialize;
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::events;
+use crate::L1xFtErc20;
+
+#[derive(Serialize, Deserialize)]
+pub struct Checkpoint {
+    event_nonce: U64,
+    total_supply: U128,
+    holder_count: U64,
+    balances_root: Option<String>,
+    state_hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BalanceChange {
+    event_nonce: U64,
+    account_id: Address,
+    balance: U128,
+}
+
+/// Balance changes in the order they happened. An account can show up more than once; its
+/// `balance` is always the current one, so the last occurrence wins.
+#[derive(Serialize, Deserialize)]
+pub struct BalanceDiffPage {
+    event_nonce: U64,
+    changes: Vec<BalanceChange>,
+    next_cursor: Option<U64>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Summary an indexer can start from. `state_hash` covers the other fields, so two nodes
+    /// agreeing on it agree on all of them.
+    pub fn export_checkpoint() -> Checkpoint {
+        let contract = Self::load();
+        let balances_root = contract.balances_root_hash();
+
+        let mut hasher = Sha256::new();
+        hasher.update(
+            (
+                contract.event_nonce,
+                *contract.total_supply,
+                contract.holder_count,
+                balances_root.unwrap_or_default(),
+            )
+                .try_to_vec()
+                .unwrap(),
+        );
+        let state_hash: [u8; 32] = hasher.finalize().into();
+
+        Checkpoint {
+            event_nonce: contract.event_nonce.into(),
+            total_supply: (*contract.total_supply).into(),
+            holder_count: contract.holder_count.into(),
+            balances_root: balances_root.map(hex::encode),
+            state_hash: hex::encode(state_hash),
+        }
+    }
+
+    /// Balance changes made after `since_nonce`. Start with a zero `cursor` and pass back
+    /// `next_cursor` until it is `None`.
+    pub fn export_balance_diff(since_nonce: U64, cursor: U64, limit: u32) -> BalanceDiffPage {
+        assert_ne!(limit, 0, "Limit should be greater than 0");
+        let contract = Self::load();
+
+        let len = contract.balance_changes.len();
+        let first = contract.first_balance_change_after(since_nonce.0);
+        let start = first
+            .max(u32::try_from(cursor.0).unwrap_or(u32::MAX))
+            .min(len);
+        let end = start.saturating_add(limit).min(len);
+
+        let changes = (start..end)
+            .map(|position| {
+                let (event_nonce, account_id) = *contract
+                    .balance_changes
+                    .get(position)
+                    .expect("Balance change not found");
+                BalanceChange {
+                    event_nonce: event_nonce.into(),
+                    account_id,
+                    balance: contract.balance_of(&account_id).unwrap_or_default().into(),
+                }
+            })
+            .collect();
+
+        BalanceDiffPage {
+            event_nonce: contract.event_nonce.into(),
+            changes,
+            next_cursor: (end < len).then(|| u64::from(end).into()),
+        }
+    }
+
+    pub(crate) fn record_balance_change(
+        &mut self,
+        account_id: &Address,
+        old_balance: u128,
+        new_balance: u128,
+    ) {
+        if old_balance == 0 && new_balance != 0 {
+            self.holder_count += 1;
+        } else if old_balance != 0 && new_balance == 0 {
+            self.holder_count -= 1;
+        }
+
+        let change = (self.event_nonce + events::pending_events(), *account_id);
+        let len = self.balance_changes.len();
+        let repeated = len != 0 && self.balance_changes.get(len - 1) == Some(&change);
+        if !repeated {
+            self.balance_changes.push(change);
+        }
+    }
+
+    /// Position of the first recorded change with a nonce above `nonce`.
+    fn first_balance_change_after(&self, nonce: u64) -> u32 {
+        let (mut low, mut high) = (0, self.balance_changes.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let (change_nonce, _) = self
+                .balance_changes
+                .get(middle)
+                .expect("Balance change not found");
+            if *change_nonce <= nonce {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
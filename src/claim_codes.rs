@@ -0,0 +1,191 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address};
+use crate::events::FtEvent;
+use crate::{L1xFtErc20, TransferKind};
+
+/// How long a commitment can be revealed. One day, in seconds.
+const CLAIM_COMMITMENT_TTL: u128 = 86_400;
+
+type ClaimHash = [u8; 32];
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct ClaimCode {
+    creator_id: Address,
+    amount: U128,
+    expires_at: U128,
+}
+
+/// What `commit_claim` takes: the SHA-256 of the claim code followed by the recipient address.
+fn claim_commitment(preimage: &str, recipient_id: &Address) -> ClaimHash {
+    let mut hasher = Sha256::new();
+    hasher.update(preimage.as_bytes());
+    hasher.update(recipient_id.as_bytes());
+    hasher.finalize().into()
+}
+
+fn parse_claim_hash(hash: &str) -> ClaimHash {
+    hex::decode(hash.strip_prefix("0x").unwrap_or(hash))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .expect("The claim hash should be 32 hex-encoded bytes")
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Escrows `amount` from the caller, redeemable by whoever knows the preimage of `hash`.
+    ///
+    /// `hash` is the hex-encoded SHA-256 of the claim code.
+    pub fn create_claim_code(hash: String, amount: U128, expires_at: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let creator_id = caller_address();
+        let hash = parse_claim_hash(&hash);
+        assert!(
+            expires_at.0 > env::block_timestamp(),
+            "The expiry should be in the future"
+        );
+        assert!(
+            !contract.claim_codes.contains_key(&hash),
+            "The claim code already exists"
+        );
+
+        let creator_balance = contract.balance_of(&creator_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&creator_id) >= amount.0,
+            "Not enough balance to fund"
+        );
+        contract.set_balance(&creator_id, creator_balance - amount.0);
+        contract.claim_codes.insert(
+            hash,
+            ClaimCode {
+                creator_id,
+                amount,
+                expires_at,
+            },
+        );
+        FtEvent::ClaimCodeCreated {
+            creator_id,
+            hash,
+            amount: amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// First step of a redemption: commits to a claim code and its recipient without revealing
+    /// the code. `commitment` is the hex-encoded SHA-256 of the claim code followed by the
+    /// 20 bytes of the recipient address.
+    ///
+    /// The code can only be revealed with `redeem_claim` in a later block, so someone who sees it
+    /// in a pending redemption can't commit to it with their own address in time.
+    pub fn commit_claim(commitment: String) {
+        let mut contract = Self::load();
+        let commitment = parse_claim_hash(&commitment);
+        let now = env::block_timestamp();
+        let live = contract
+            .claim_commitments
+            .get(&commitment)
+            .map_or(false, |committed_at| {
+                now < committed_at + CLAIM_COMMITMENT_TTL
+            });
+        if !live {
+            contract.claim_commitments.insert(commitment, now);
+        }
+
+        contract.save();
+    }
+
+    /// Pays the claim out as a transfer from its creator, with the same checks as `ft_transfer`
+    /// except for the creator's cooldown and withdrawal protection, which the escrow already got
+    /// past. Needs a commitment to `preimage` and `recipient_id` from an earlier block.
+    pub fn redeem_claim(preimage: String, recipient_id: AccountId) {
+        let mut contract = Self::load();
+        let hash: ClaimHash = Sha256::digest(preimage.as_bytes()).into();
+        let committed_at = contract
+            .claim_commitments
+            .remove(claim_commitment(&preimage, &recipient_id))
+            .expect("Commit to the claim code and recipient first");
+        let now = env::block_timestamp();
+        assert!(
+            committed_at < now,
+            "The claim code can only be revealed after the block it was committed in"
+        );
+        assert!(
+            now < committed_at + CLAIM_COMMITMENT_TTL,
+            "The commitment has expired"
+        );
+
+        let claim = contract
+            .claim_codes
+            .remove(hash)
+            .expect("Invalid claim code");
+        assert!(now < claim.expires_at.0, "The claim code has expired");
+
+        // The escrow goes back to the creator first, so the payout is a transfer from them.
+        let creator_balance = contract.balance_of(&claim.creator_id).unwrap_or_default();
+        contract.set_balance(
+            &claim.creator_id,
+            creator_balance
+                .checked_add(claim.amount.0)
+                .expect("Balance overflowed"),
+        );
+        contract.assert_memo_not_required(&recipient_id);
+        contract.transfer(
+            &claim.creator_id,
+            &recipient_id,
+            claim.amount.0,
+            TransferKind::ClaimPayout,
+        );
+        FtEvent::ClaimCodeRedeemed {
+            hash,
+            recipient_id: *recipient_id,
+            amount: claim.amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Returns an expired, unredeemed claim to its creator. Callable by anyone.
+    pub fn refund_claim(hash: String) {
+        let mut contract = Self::load();
+        let hash = parse_claim_hash(&hash);
+
+        let claim = contract
+            .claim_codes
+            .remove(hash)
+            .expect("Claim code not found");
+        assert!(
+            env::block_timestamp() >= claim.expires_at.0,
+            "The claim code has not expired yet"
+        );
+
+        let creator_balance = contract.balance_of(&claim.creator_id).unwrap_or_default();
+        contract.set_balance(
+            &claim.creator_id,
+            creator_balance
+                .checked_add(claim.amount.0)
+                .expect("Balance overflowed"),
+        );
+        FtEvent::ClaimCodeRefunded {
+            hash,
+            creator_id: claim.creator_id,
+            amount: claim.amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn claim_code(hash: String) -> Option<ClaimCode> {
+        let contract = Self::load();
+        contract.claim_codes.get(&parse_claim_hash(&hash)).cloned()
+    }
+}
@@ -0,0 +1,299 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::{ClawbackAuthority, FtEvent};
+use crate::roles::Role;
+use crate::timelock::TimelockOperation;
+use crate::{decimals, L1xFtErc20};
+
+const MAX_CLAWBACK_REASON_LEN: usize = 256;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ClawbackStatus {
+    /// Approved by the compliance role, waiting for the owner to schedule the governance approval.
+    Proposed,
+    /// The governance approval is in the timelock.
+    Scheduled,
+    Executed,
+    Cancelled,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct ClawbackApproval {
+    approver_id: Address,
+    approved_at: U128,
+}
+
+/// A forced transfer of `amount` from `account_id` to `recipient_id`.
+///
+/// A compliance officer proposes it, which is the first approval. The owner then schedules the
+/// second through the timelock, and the tokens are seized when that operation executes. Both
+/// approvals stay on the record.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct Clawback {
+    account_id: Address,
+    recipient_id: Address,
+    amount: U128,
+    reason: String,
+    status: ClawbackStatus,
+    compliance_approval: ClawbackApproval,
+    timelock_operation_id: Option<U64>,
+    governance_approval: Option<ClawbackApproval>,
+    /// The token decimals `amount` is in, rescaled on read like invoices.
+    decimals: u8,
+}
+
+impl Clawback {
+    fn rescale_to(&mut self, decimals: u8) {
+        self.amount = decimals::rescale(self.decimals, decimals, self.amount.0).into();
+        self.decimals = decimals;
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// The owner can't be a compliance officer, so the two approvals of a clawback come from
+    /// different keys.
+    pub fn add_compliance_officer(officer_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(
+            officer_id,
+            contract_owner_address(),
+            "The owner cannot be a compliance officer"
+        );
+        assert!(
+            contract.grant_role(Role::Compliance, officer_id),
+            "This address is already a compliance officer"
+        );
+
+        contract.save();
+    }
+
+    /// Clawbacks the officer proposed can no longer execute.
+    pub fn remove_compliance_officer(officer_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.revoke_role(Role::Compliance, &officer_id),
+            "This address is not a compliance officer"
+        );
+
+        contract.save();
+    }
+
+    pub fn compliance_officers() -> Vec<Address> {
+        Self::role_members(Role::Compliance)
+    }
+
+    /// Records the caller's compliance approval of seizing `amount` from `account_id`.
+    pub fn propose_clawback(
+        account_id: Address,
+        recipient_id: Address,
+        amount: U128,
+        reason: String,
+    ) -> U64 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        assert_ne!(
+            account_id, recipient_id,
+            "Account and recipient cannot be the same"
+        );
+        assert!(
+            reason.len() <= MAX_CLAWBACK_REASON_LEN,
+            "Reason is longer than {MAX_CLAWBACK_REASON_LEN} bytes"
+        );
+        let mut contract = Self::load();
+        let officer_id = caller_address();
+        assert!(
+            contract.has_role(Role::Compliance, &officer_id),
+            "Only a compliance officer can call this function"
+        );
+        assert_ne!(
+            officer_id,
+            contract_owner_address(),
+            "The owner cannot approve a clawback for compliance"
+        );
+
+        let clawback_id = contract.next_clawback_id;
+        contract.next_clawback_id = clawback_id.checked_add(1).expect("Clawback id overflowed");
+        contract.clawbacks.insert(
+            clawback_id,
+            Clawback {
+                account_id,
+                recipient_id,
+                amount,
+                reason: reason.clone(),
+                status: ClawbackStatus::Proposed,
+                compliance_approval: ClawbackApproval {
+                    approver_id: officer_id,
+                    approved_at: env::block_timestamp().into(),
+                },
+                timelock_operation_id: None,
+                governance_approval: None,
+                decimals: contract.metadata.decimals,
+            },
+        );
+        FtEvent::ClawbackProposed {
+            clawback_id,
+            account_id,
+            recipient_id,
+            amount: amount.0,
+            reason,
+        }
+        .emit();
+        FtEvent::ClawbackApproved {
+            clawback_id,
+            authority: ClawbackAuthority::Compliance,
+            approver_id: officer_id,
+        }
+        .emit();
+
+        contract.save();
+        clawback_id.into()
+    }
+
+    /// Schedules the governance approval. The clawback executes with `timelock_execute`.
+    pub fn approve_clawback(clawback_id: U64) -> U64 {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let mut clawback = contract
+            .clawbacks
+            .get(&clawback_id.0)
+            .cloned()
+            .expect("Clawback not found");
+        assert!(
+            clawback.status == ClawbackStatus::Proposed,
+            "The clawback is not awaiting approval"
+        );
+        let operation_id =
+            contract.timelock_schedule(TimelockOperation::ApproveClawback { clawback_id });
+        clawback.status = ClawbackStatus::Scheduled;
+        clawback.timelock_operation_id = Some(operation_id);
+        contract.clawbacks.insert(clawback_id.0, clawback);
+
+        contract.save();
+        operation_id
+    }
+
+    /// Callable by the owner or any compliance officer while the clawback hasn't executed. Also
+    /// drops its timelock operation.
+    pub fn cancel_clawback(clawback_id: U64) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+        assert!(
+            caller_id == contract_owner_address()
+                || contract.has_role(Role::Compliance, &caller_id),
+            "Only the owner or a compliance officer can cancel a clawback"
+        );
+
+        let mut clawback = contract
+            .clawbacks
+            .get(&clawback_id.0)
+            .cloned()
+            .expect("Clawback not found");
+        assert!(
+            matches!(
+                clawback.status,
+                ClawbackStatus::Proposed | ClawbackStatus::Scheduled
+            ),
+            "The clawback is no longer pending"
+        );
+        if let Some(operation_id) = clawback.timelock_operation_id {
+            contract.timelock_operations.remove(operation_id.0);
+        }
+        clawback.status = ClawbackStatus::Cancelled;
+        contract.clawbacks.insert(clawback_id.0, clawback);
+        FtEvent::ClawbackCancelled {
+            clawback_id: clawback_id.0,
+            cancelled_by: caller_id,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn clawback(clawback_id: U64) -> Option<Clawback> {
+        let contract = Self::load();
+        contract.clawback_entry(clawback_id.0)
+    }
+
+    fn clawback_entry(&self, clawback_id: u64) -> Option<Clawback> {
+        let mut clawback = self.clawbacks.get(&clawback_id).cloned()?;
+        clawback.rescale_to(self.metadata.decimals);
+        Some(clawback)
+    }
+
+    /// Records the governance approval and seizes the tokens. Frozen tokens are taken first and
+    /// their freeze is released. Held and prepared amounts stay, as in `evacuate`.
+    pub(crate) fn execute_clawback(&mut self, clawback_id: u64, operation_id: u64) {
+        let mut clawback = self
+            .clawback_entry(clawback_id)
+            .expect("Clawback not found");
+        assert!(
+            clawback.status == ClawbackStatus::Scheduled
+                && clawback.timelock_operation_id == Some(operation_id.into()),
+            "The clawback is not scheduled"
+        );
+        assert!(
+            self.has_role(Role::Compliance, &clawback.compliance_approval.approver_id),
+            "The compliance approver is no longer a compliance officer"
+        );
+
+        let amount = clawback.amount.0;
+        let balance = self.balance_of(&clawback.account_id).unwrap_or_default();
+        let available = balance
+            .saturating_sub(self.held_amount(&clawback.account_id))
+            .saturating_sub(self.prepared_amount(&clawback.account_id));
+        assert!(available >= amount, "Not enough balance to claw back");
+        let frozen = self.frozen_amount(&clawback.account_id).min(amount);
+        if frozen > 0 {
+            self.release_freeze(&clawback.account_id, frozen);
+        }
+        self.set_balance(&clawback.account_id, balance - amount);
+        let recipient_balance = self.balance_of(&clawback.recipient_id).unwrap_or_default();
+        self.set_balance(
+            &clawback.recipient_id,
+            recipient_balance
+                .checked_add(amount)
+                .expect("Balance overflowed"),
+        );
+
+        let approver_id = caller_address();
+        clawback.governance_approval = Some(ClawbackApproval {
+            approver_id,
+            approved_at: env::block_timestamp().into(),
+        });
+        clawback.status = ClawbackStatus::Executed;
+        let (account_id, recipient_id) = (clawback.account_id, clawback.recipient_id);
+        self.clawbacks.insert(clawback_id, clawback);
+        FtEvent::ClawbackApproved {
+            clawback_id,
+            authority: ClawbackAuthority::Governance,
+            approver_id,
+        }
+        .emit();
+        FtEvent::ClawbackExecuted {
+            clawback_id,
+            account_id,
+            recipient_id,
+            amount,
+        }
+        .emit();
+    }
+}
@@ -0,0 +1,145 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// One day, in seconds.
+const CLEANUP_REWARD_PERIOD: u128 = 86_400;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+pub struct CleanupReward {
+    per_record: U128,
+    max_per_day: U128,
+}
+
+/// The reward terms and what was paid out in the current day.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub(crate) struct CleanupRewards {
+    reward: CleanupReward,
+    day: u128,
+    paid: u128,
+}
+
+impl CleanupRewards {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        self.reward.per_record = rescale(self.reward.per_record.0).into();
+        self.reward.max_per_day = rescale(self.reward.max_per_day.0).into();
+        self.paid = rescale(self.paid);
+    }
+
+    /// Takes up to `amount` out of what is left for today.
+    fn take(&mut self, amount: u128, now: u128) -> u128 {
+        let day = now / CLEANUP_REWARD_PERIOD;
+        if day != self.day {
+            self.day = day;
+            self.paid = 0;
+        }
+        let amount = amount.min(self.reward.max_per_day.0.saturating_sub(self.paid));
+        self.paid += amount;
+        amount
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Prunes expired holds, timed-out prepared transfers, expired idempotency and spending keys,
+    /// and zero allowances for up to `max_items` accounts, picking up where the last call
+    /// stopped. Returns the number of pruned records.
+    ///
+    /// The caller is paid the reward per pruned record from the treasury, except for its own
+    /// records, up to the daily maximum and as far as the treasury has funds. The cap is what
+    /// keeps accounts from creating records for each other just to be paid for pruning them.
+    pub fn cleanup(max_items: u32) -> u32 {
+        assert_ne!(max_items, 0, "Max items should be greater than 0");
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let len = contract.accounts.len();
+        let mut pruned = 0u32;
+        let mut rewarded = 0u32;
+        for _ in 0..max_items.min(len) {
+            if contract.cleanup_cursor >= len {
+                contract.cleanup_cursor = 0;
+            }
+            let account_id = *contract
+                .accounts
+                .get(contract.cleanup_cursor)
+                .expect("Indexed account not found");
+            contract.cleanup_cursor += 1;
+            let account_pruned = contract.prune_account_records(&account_id);
+            pruned += account_pruned;
+            if account_id != caller_id {
+                rewarded += account_pruned;
+            }
+        }
+
+        let reward = contract
+            .cleanup_rewards
+            .reward
+            .per_record
+            .0
+            .saturating_mul(rewarded.into())
+            .min(contract.treasury_balance);
+        let reward = contract
+            .cleanup_rewards
+            .take(reward, env::block_timestamp());
+        if reward > 0 {
+            contract.treasury_send(&caller_id, reward);
+        }
+
+        contract.save();
+        pruned
+    }
+
+    pub fn set_cleanup_reward(reward: CleanupReward) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.cleanup_rewards.reward = reward;
+
+        contract.save();
+    }
+
+    pub fn cleanup_reward() -> CleanupReward {
+        let contract = Self::load();
+        contract.cleanup_rewards.into_inner().reward
+    }
+
+    fn prune_account_records(&mut self, account_id: &Address) -> u32 {
+        self.prune_expired_holds(account_id)
+            + self.prune_timed_out_settlements(account_id)
+            + self.prune_idempotency_keys(account_id)
+            + self.prune_expired_spending_keys(account_id)
+            + self.prune_zero_allowances(account_id)
+    }
+
+    fn prune_zero_allowances(&mut self, owner_id: &Address) -> u32 {
+        let Some(allowance) = self.allowances.get(owner_id) else {
+            return 0;
+        };
+        let zero = allowance
+            .spenders
+            .values()
+            .filter(|amount| **amount == 0)
+            .count() as u32;
+        if zero == 0 {
+            return 0;
+        }
+
+        let mut allowance = allowance.clone();
+        allowance.spenders.retain(|_, amount| *amount != 0);
+        if allowance.spenders.is_empty() {
+            self.allowances.remove(*owner_id);
+        } else {
+            self.allowances.insert(*owner_id, allowance);
+        }
+        zero
+    }
+}
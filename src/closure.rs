@@ -0,0 +1,77 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+
+use crate::account_id::AccountId;
+use crate::env::caller_address;
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sends the caller's whole balance to `beneficiary_id`, revokes every allowance granted by
+    /// and to the caller, and drops its per-account settings. Returns the amount sent.
+    ///
+    /// The balance goes out like any other transfer, so it can be held by withdrawal protection.
+    /// Locked, staked, held and pending tokens have to be settled first. The account keeps its
+    /// slot in the account index, which the balances root is built on.
+    pub fn close_account(beneficiary_id: AccountId) -> U128 {
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        let account_id = caller_address();
+        assert_ne!(
+            account_id, *beneficiary_id,
+            "An account can't be its own beneficiary"
+        );
+
+        contract.prune_expired_holds(&account_id);
+        contract.prune_timed_out_settlements(&account_id);
+        assert!(
+            contract.held_amount(&account_id) == 0 && contract.prepared_amount(&account_id) == 0,
+            "The account has active holds or prepared transfers"
+        );
+        #[cfg(feature = "locks")]
+        assert!(!contract.has_locks(&account_id), "The account has locks");
+        assert!(
+            !contract.has_pending_transfers(&account_id),
+            "The account has pending transfers"
+        );
+        #[cfg(feature = "staking")]
+        assert!(!contract.has_stake(&account_id), "The account has stake");
+
+        let balance = contract.balance_of(&account_id).unwrap_or_default();
+        if balance > 0 {
+            contract.transfer_or_hold(&account_id, &beneficiary_id, balance);
+        }
+        contract.balances.remove(account_id);
+        contract.revoke_all_allowances(&account_id);
+        #[cfg(feature = "holds")]
+        contract.holds_by_payer.remove(account_id);
+        contract.trusted_spenders.remove(account_id);
+        contract.memo_required.remove(account_id);
+        contract.spending_keys.remove(account_id);
+        contract.idempotency_keys.remove(account_id);
+        FtEvent::AccountClosed {
+            account_id,
+            beneficiary_id: *beneficiary_id,
+            amount: balance,
+        }
+        .emit();
+
+        contract.save();
+        balance.into()
+    }
+
+    fn revoke_all_allowances(&mut self, account_id: &Address) {
+        if let Some(granted) = self.allowances.remove(*account_id) {
+            for spender_id in granted.spenders.keys() {
+                self.index_approval(account_id, spender_id, 0);
+            }
+        }
+
+        for owner_id in self.take_approvals_received(account_id) {
+            if let Some(allowance) = self.allowances.get_mut(&owner_id) {
+                allowance.spenders.remove(account_id);
+            }
+        }
+    }
+}
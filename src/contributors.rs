@@ -0,0 +1,177 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// Epochs run back to back from `start`. Nothing accrues after `total_epochs`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct EpochSchedule {
+    start: U128,
+    epoch_length: U64,
+    total_epochs: U64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct Contributor {
+    reward_per_epoch: u128,
+    next_epoch: u64,
+}
+
+impl Contributor {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        self.reward_per_epoch = rescale(self.reward_per_epoch);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ContributorView {
+    reward_per_epoch: U128,
+    next_epoch: U64,
+    claimable: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Can only be set once, so epoch numbers keep their meaning.
+    pub fn set_epoch_schedule(schedule: EpochSchedule) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.epoch_schedule.is_none(),
+            "The epoch schedule is already set"
+        );
+        assert_ne!(
+            schedule.epoch_length.0, 0,
+            "Epoch length should be greater than 0"
+        );
+
+        *contract.epoch_schedule = Some(schedule);
+
+        contract.save();
+    }
+
+    pub fn epoch_schedule() -> Option<EpochSchedule> {
+        let contract = Self::load();
+        contract.epoch_schedule.into_inner()
+    }
+
+    /// Number of epochs completed so far.
+    pub fn current_epoch() -> U64 {
+        let contract = Self::load();
+        contract.completed_epochs().into()
+    }
+
+    /// Rewards accrue from the next epoch to complete.
+    pub fn register_contributor(account_id: AccountId, reward_per_epoch: U128) {
+        assert_ne!(
+            reward_per_epoch.0, 0,
+            "Reward per epoch should be greater than 0"
+        );
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.epoch_schedule.is_some(),
+            "The epoch schedule is not set"
+        );
+        assert!(
+            !contract.contributors.contains_key(&account_id),
+            "This address is already a contributor"
+        );
+
+        contract.assert_no_decimals_migration();
+
+        contract.index_account(&account_id);
+        let next_epoch = contract.completed_epochs();
+        contract.contributors.insert(
+            *account_id,
+            Contributor {
+                reward_per_epoch: reward_per_epoch.0,
+                next_epoch,
+            },
+        );
+
+        contract.save();
+    }
+
+    /// Unclaimed epochs are forfeited.
+    pub fn remove_contributor(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract
+            .contributors
+            .remove(account_id)
+            .expect("This address is not a contributor");
+
+        contract.save();
+    }
+
+    pub fn contributor(account_id: Address) -> Option<ContributorView> {
+        let contract = Self::load();
+        let completed = contract.completed_epochs();
+        contract
+            .contributors
+            .get(&account_id)
+            .map(|contributor| ContributorView {
+                reward_per_epoch: contributor.reward_per_epoch.into(),
+                next_epoch: contributor.next_epoch.into(),
+                claimable: Self::accrued_rewards(contributor, completed).into(),
+            })
+    }
+
+    /// Mints the rewards of every epoch completed since the last claim.
+    pub fn claim_epoch_rewards() -> U128 {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+
+        let mut contributor = contract
+            .contributors
+            .get(&account_id)
+            .cloned()
+            .expect("This address is not a contributor");
+        let completed = contract.completed_epochs();
+        let amount = Self::accrued_rewards(&contributor, completed);
+        assert_ne!(amount, 0, "No rewards to claim");
+
+        contributor.next_epoch = completed;
+        contract.contributors.insert(account_id, contributor);
+        contract.assert_unbacked_mint_allowed();
+        contract.mint(&account_id, amount);
+
+        contract.save();
+        amount.into()
+    }
+
+    fn completed_epochs(&self) -> u64 {
+        let Some(schedule) = &*self.epoch_schedule else {
+            return 0;
+        };
+        let elapsed = env::block_timestamp().saturating_sub(schedule.start.0);
+        let epochs = elapsed / u128::from(schedule.epoch_length.0);
+        epochs.min(schedule.total_epochs.0.into()) as u64
+    }
+
+    fn accrued_rewards(contributor: &Contributor, completed_epochs: u64) -> u128 {
+        let epochs = completed_epochs.saturating_sub(contributor.next_epoch);
+        contributor
+            .reward_per_epoch
+            .checked_mul(epochs.into())
+            .expect("Rewards overflowed")
+    }
+}
@@ -0,0 +1,64 @@
+use l1x_sdk::contract_interaction::ContractCall;
+use l1x_sdk::types::{Address, Gas};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const CROSS_CONTRACT_GAS_LIMIT: Gas = 500_000;
+
+/// Calls `method_name` on another contract with JSON-encoded `args`.
+pub(crate) fn call<A: Serialize>(
+    contract_address: Address,
+    method_name: &str,
+    args: &A,
+    read_only: bool,
+) -> Result<Vec<u8>, String> {
+    call_with_gas_limit(
+        contract_address,
+        method_name,
+        args,
+        read_only,
+        CROSS_CONTRACT_GAS_LIMIT,
+    )
+}
+
+fn call_with_gas_limit<A: Serialize>(
+    contract_address: Address,
+    method_name: &str,
+    args: &A,
+    read_only: bool,
+    gas_limit: Gas,
+) -> Result<Vec<u8>, String> {
+    let call = ContractCall {
+        contract_address,
+        method_name: method_name.to_string(),
+        args: serde_json::to_vec(args).expect("Failed to serialize the call arguments"),
+        read_only,
+        gas_limit,
+    };
+    l1x_sdk::call_contract(&call)
+}
+
+/// Read-only call that decodes the JSON result.
+pub(crate) fn view<A: Serialize, R: DeserializeOwned>(
+    contract_address: Address,
+    method_name: &str,
+    args: &A,
+) -> Result<R, String> {
+    view_with_gas_limit(
+        contract_address,
+        method_name,
+        args,
+        CROSS_CONTRACT_GAS_LIMIT,
+    )
+}
+
+/// Like `view`, but with a caller-chosen gas limit.
+pub(crate) fn view_with_gas_limit<A: Serialize, R: DeserializeOwned>(
+    contract_address: Address,
+    method_name: &str,
+    args: &A,
+    gas_limit: Gas,
+) -> Result<R, String> {
+    let output = call_with_gas_limit(contract_address, method_name, args, true, gas_limit)?;
+    serde_json::from_slice(&output).map_err(|err| err.to_string())
+}
@@ -0,0 +1,268 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::timelock::TimelockOperation;
+use crate::L1xFtErc20;
+
+/// A rescale of every balance to new decimals, worked through the account index in chunks.
+/// Transfers, mints and burns are blocked until it completes.
+///
+/// A first pass only checks the accounts and can still be aborted. It fails if any tokens are
+/// outside the balances and the treasury, or if an account has holds or prepared transfers, since
+/// those amounts aren't rescaled. The second pass rescales the balances and every
+/// per-account amount setting, which can't be changed until the migration completes. Contract-wide
+/// settings are rescaled at the end, and entries that can't be enumerated carry their decimals
+/// and are rescaled when read.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct DecimalsMigration {
+    from: u8,
+    to: u8,
+    next_index: u32,
+    rescaling: bool,
+    /// Sum of the balances seen so far in the current pass.
+    balances: U128,
+}
+
+impl DecimalsMigration {
+    fn rescale(&self, amount: u128) -> u128 {
+        rescale(self.from, self.to, amount)
+    }
+}
+
+/// Converts `amount` from `from` to `to` decimals. Scaling down truncates, so the dust below the
+/// new precision is dropped. Scaling up saturates: only limits and allowances can get that large,
+/// as every balance fits in the total supply.
+pub(crate) fn rescale(from: u8, to: u8, amount: u128) -> u128 {
+    let factor = 10u128.pow(u32::from(to.abs_diff(from)));
+    if to >= from {
+        amount.saturating_mul(factor)
+    } else {
+        amount / factor
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Schedules a switch to `new_decimals`. Once executed with `timelock_execute`, call
+    /// `process_decimals_migration` until it returns `true`, or `abort_decimals_migration` during
+    /// the first pass.
+    pub fn migrate_decimals(new_decimals: u8) -> U64 {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(new_decimals <= 18, "Invalid decimals");
+        assert_ne!(
+            new_decimals, contract.metadata.decimals,
+            "The token already has these decimals"
+        );
+        contract.assert_can_migrate_decimals(new_decimals);
+
+        let operation_id =
+            contract.timelock_schedule(TimelockOperation::MigrateDecimals { new_decimals });
+
+        contract.save();
+        operation_id
+    }
+
+    /// Checks or rescales up to `max_accounts` accounts. Returns `true` once both passes are done
+    /// and the new decimals are in effect.
+    pub fn process_decimals_migration(max_accounts: u32) -> bool {
+        assert_ne!(max_accounts, 0, "Max accounts should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        let mut migration = contract
+            .decimals_migration
+            .clone()
+            .expect("No decimals migration is in progress");
+
+        let end = migration
+            .next_index
+            .saturating_add(max_accounts)
+            .min(contract.accounts.len());
+        let mut balances = migration.balances.0;
+        for index in migration.next_index..end {
+            let account_id = *contract.accounts.get(index).expect("Account not found");
+            let balance = contract.balance_of(&account_id).unwrap_or_default();
+            if migration.rescaling {
+                let balance = migration.rescale(balance);
+                contract.write_balance(&account_id, balance);
+                balances += balance;
+                contract.rescale_account_limits(&migration, &account_id);
+            } else {
+                contract.assert_can_rescale(&account_id);
+                balances += balance;
+            }
+        }
+        migration.next_index = end;
+        migration.balances = balances.into();
+        FtEvent::DecimalsMigrationProgress {
+            processed: end,
+            total: contract.accounts.len(),
+        }
+        .emit();
+
+        let pass_done = end == contract.accounts.len();
+        let complete = pass_done && migration.rescaling;
+        if complete {
+            contract.treasury_balance = migration.rescale(contract.treasury_balance);
+            *contract.total_supply = balances
+                .checked_add(contract.treasury_balance)
+                .expect("total_supply is overflowed");
+            contract.max_supply = contract.max_supply.map(|max| migration.rescale(max));
+            contract.rescale_settings(&migration);
+            contract.metadata.decimals = migration.to;
+            contract.decimals_migration = None;
+            FtEvent::DecimalsMigrated {
+                from: migration.from,
+                to: migration.to,
+            }
+            .emit();
+        } else {
+            if pass_done {
+                assert_eq!(
+                    balances + contract.treasury_balance,
+                    *contract.total_supply,
+                    "Locked, staked, escrowed and pending tokens have to be settled first"
+                );
+                migration.rescaling = true;
+                migration.next_index = 0;
+                migration.balances = 0.into();
+            }
+            contract.decimals_migration = Some(migration);
+        }
+
+        contract.save();
+        complete
+    }
+
+    /// Cancels a migration that hasn't started rescaling yet.
+    pub fn abort_decimals_migration() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        let migration = contract
+            .decimals_migration
+            .take()
+            .expect("No decimals migration is in progress");
+        assert!(
+            !migration.rescaling,
+            "The balances are already being rescaled"
+        );
+
+        contract.save();
+    }
+
+    pub fn decimals_migration() -> Option<DecimalsMigration> {
+        let contract = Self::load();
+        contract.decimals_migration
+    }
+
+    /// Per-account amounts are rescaled as the migration reaches each account, so they can't be
+    /// changed while it runs.
+    pub(crate) fn assert_no_decimals_migration(&self) {
+        assert!(
+            self.decimals_migration.is_none(),
+            "The contract is paused for a decimals migration"
+        );
+    }
+
+    pub(crate) fn start_decimals_migration(&mut self, new_decimals: u8) {
+        assert!(
+            self.decimals_migration.is_none(),
+            "A decimals migration is already in progress"
+        );
+        self.assert_can_migrate_decimals(new_decimals);
+        self.decimals_migration = Some(DecimalsMigration {
+            from: self.metadata.decimals,
+            to: new_decimals,
+            next_index: 0,
+            rescaling: false,
+            balances: 0.into(),
+        });
+    }
+
+    fn assert_can_migrate_decimals(&self, new_decimals: u8) {
+        assert!(
+            !self.wrapped_native && self.underlying.is_none(),
+            "A wrapper token has the decimals of what it wraps"
+        );
+        let factor = 10u128.pow(u32::from(
+            new_decimals.saturating_sub(self.metadata.decimals),
+        ));
+        assert!(
+            self.total_supply.checked_mul(factor).is_some(),
+            "The total supply doesn't fit in the new decimals"
+        );
+    }
+
+    fn assert_can_rescale(&self, account_id: &Address) {
+        assert_eq!(
+            self.held_amount(account_id),
+            0,
+            "{account_id} has open holds"
+        );
+        assert_eq!(
+            self.prepared_amount(account_id),
+            0,
+            "{account_id} has prepared transfers"
+        );
+    }
+
+    fn rescale_account_limits(&mut self, migration: &DecimalsMigration, account_id: &Address) {
+        let rescale = |amount| migration.rescale(amount);
+        if let Some(allowance) = self.allowances.get_mut(account_id) {
+            for amount in allowance.spenders.values_mut() {
+                *amount = rescale(*amount);
+            }
+        }
+        if let Some(frozen) = self.frozen.get_mut(account_id) {
+            *frozen = rescale(*frozen);
+        }
+        if let Some(protection) = self.withdrawal_protection.get_mut(account_id) {
+            protection.rescale(rescale);
+        }
+        if let Some(keys) = self.spending_keys.get_mut(account_id) {
+            keys.rescale(rescale);
+        }
+        #[cfg(feature = "contributors")]
+        if let Some(contributor) = self.contributors.get_mut(account_id) {
+            contributor.rescale(rescale);
+        }
+    }
+
+    fn rescale_settings(&mut self, migration: &DecimalsMigration) {
+        let rescale = |amount| migration.rescale(amount);
+        self.limits.rescale(rescale);
+        self.cleanup_rewards.rescale(rescale);
+        self.mint_breaker.rescale(rescale);
+        self.volume_breaker.rescale(rescale);
+        self.alert_thresholds.rescale(rescale);
+        if let Some(policy) = &mut *self.dust_policy {
+            policy.rescale(rescale);
+        }
+        #[cfg(feature = "names")]
+        if let Some(config) = &mut *self.name_registry {
+            config.rescale(rescale);
+        }
+        #[cfg(feature = "invoices")]
+        {
+            self.invoice_fee = rescale(self.invoice_fee);
+        }
+        #[cfg(feature = "compliance")]
+        self.jurisdiction_rules.rescale(rescale);
+    }
+}
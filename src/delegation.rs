@@ -0,0 +1,305 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address};
+use crate::{L1xFtErc20, BPS_DENOMINATOR};
+
+/// How long delegators are given to settle or leave before a commission increase applies. Seven
+/// days, in seconds.
+const COMMISSION_INCREASE_NOTICE: u128 = 7 * 86_400;
+/// The most a single increase can add to the commission.
+const MAX_COMMISSION_INCREASE_BPS: u16 = 1_000;
+
+/// An operator backed by delegated stake. It keeps `commission_bps` of its delegators' rewards.
+///
+/// Rewards are split when a delegation is settled, so a new commission also applies to what
+/// accrued before it. Increases are therefore only scheduled, and delegators can claim at the old
+/// rate or undelegate until they apply.
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct Operator {
+    commission_bps: u16,
+    total_delegated: u128,
+    delegators: u64,
+    commission: u128,
+    /// A commission increase and when it applies.
+    scheduled_commission: Option<(u16, u128)>,
+}
+
+impl Operator {
+    fn apply_scheduled_commission(&mut self, now: u128) {
+        if let Some((commission_bps, effective_at)) = self.scheduled_commission {
+            if now >= effective_at {
+                self.commission_bps = commission_bps;
+                self.scheduled_commission = None;
+            }
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct Delegation {
+    amount: u128,
+    reward_debt: u128,
+    unclaimed: u128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OperatorView {
+    commission_bps: u16,
+    scheduled_commission_bps: Option<u16>,
+    scheduled_commission_at: Option<U128>,
+    total_delegated: U128,
+    delegators: U64,
+    unclaimed_commission: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DelegationView {
+    amount: U128,
+    pending_rewards: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Registers the caller as an operator, or updates its commission. A lower commission applies
+    /// right away and cancels a scheduled increase. A higher one, by at most
+    /// `MAX_COMMISSION_INCREASE_BPS`, applies after `COMMISSION_INCREASE_NOTICE`.
+    pub fn register_operator(commission_bps: u16) {
+        let mut contract = Self::load();
+        let operator_id = caller_address();
+        assert!(
+            u128::from(commission_bps) <= BPS_DENOMINATOR,
+            "Commission can't exceed 100%"
+        );
+        let now = env::block_timestamp();
+
+        let operator = match contract.operators.get(&operator_id).cloned() {
+            Some(mut operator) => {
+                operator.apply_scheduled_commission(now);
+                if commission_bps <= operator.commission_bps {
+                    operator.commission_bps = commission_bps;
+                    operator.scheduled_commission = None;
+                } else {
+                    assert!(
+                        commission_bps - operator.commission_bps <= MAX_COMMISSION_INCREASE_BPS,
+                        "Commission can't increase by more than {MAX_COMMISSION_INCREASE_BPS} bps at once"
+                    );
+                    operator.scheduled_commission =
+                        Some((commission_bps, now + COMMISSION_INCREASE_NOTICE));
+                }
+                operator
+            }
+            None => Operator {
+                commission_bps,
+                total_delegated: 0,
+                delegators: 0,
+                commission: 0,
+                scheduled_commission: None,
+            },
+        };
+        contract.operators.insert(operator_id, operator);
+
+        contract.save();
+    }
+
+    pub fn delegate_stake(operator_id: Address, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let delegator_id = caller_address();
+
+        let balance = contract.balance_of(&delegator_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&delegator_id) >= amount.0,
+            "Not enough balance to delegate"
+        );
+        contract.set_balance(&delegator_id, balance - amount.0);
+
+        let (mut operator, mut delegation) =
+            contract.settled_delegation(&delegator_id, &operator_id);
+        if delegation.amount == 0 {
+            operator.delegators += 1;
+        }
+        delegation.amount = delegation
+            .amount
+            .checked_add(amount.0)
+            .expect("Delegation overflowed");
+        operator.total_delegated = operator
+            .total_delegated
+            .checked_add(amount.0)
+            .expect("Operator delegation overflowed");
+        contract.add_pool_stake(amount.0);
+        contract.store_delegation(&delegator_id, &operator_id, operator, delegation);
+        l1x_sdk::msg(&format!(
+            "{} delegated {} tokens to {}",
+            delegator_id, amount.0, operator_id
+        ));
+
+        contract.save();
+    }
+
+    /// Moves `amount` back through the unbonding period. Returns when it can be withdrawn.
+    pub fn undelegate(operator_id: Address, amount: U128) -> U128 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let delegator_id = caller_address();
+
+        let (mut operator, mut delegation) =
+            contract.settled_delegation(&delegator_id, &operator_id);
+        assert!(
+            delegation.amount >= amount.0,
+            "Not enough delegated stake to undelegate"
+        );
+        delegation.amount -= amount.0;
+        if delegation.amount == 0 {
+            operator.delegators -= 1;
+        }
+        operator.total_delegated -= amount.0;
+        contract.remove_pool_stake(amount.0);
+        contract.store_delegation(&delegator_id, &operator_id, operator, delegation);
+        let matures_at = contract.unbond_to(&delegator_id, amount.0);
+
+        contract.save();
+        matures_at.into()
+    }
+
+    pub fn claim_delegation_rewards(operator_id: Address) -> U128 {
+        let mut contract = Self::load();
+        let delegator_id = caller_address();
+
+        let (operator, mut delegation) = contract.settled_delegation(&delegator_id, &operator_id);
+        let rewards = std::mem::take(&mut delegation.unclaimed);
+        assert_ne!(rewards, 0, "No rewards to claim");
+        contract.store_delegation(&delegator_id, &operator_id, operator, delegation);
+
+        let balance = contract.balance_of(&delegator_id).unwrap_or_default();
+        contract.set_balance(
+            &delegator_id,
+            balance.checked_add(rewards).expect("Balance overflowed"),
+        );
+
+        contract.save();
+        rewards.into()
+    }
+
+    /// Pays out the commission settled so far. Delegations settle whenever they are touched.
+    pub fn claim_operator_commission() -> U128 {
+        let mut contract = Self::load();
+        let operator_id = caller_address();
+
+        let mut operator = contract
+            .operators
+            .get(&operator_id)
+            .cloned()
+            .expect("Operator not found");
+        let commission = std::mem::take(&mut operator.commission);
+        assert_ne!(commission, 0, "No commission to claim");
+        contract.operators.insert(operator_id, operator);
+
+        let balance = contract.balance_of(&operator_id).unwrap_or_default();
+        contract.set_balance(
+            &operator_id,
+            balance.checked_add(commission).expect("Balance overflowed"),
+        );
+
+        contract.save();
+        commission.into()
+    }
+
+    pub fn operator(operator_id: Address) -> Option<OperatorView> {
+        let contract = Self::load();
+        let mut operator = contract.operators.get(&operator_id)?.clone();
+        operator.apply_scheduled_commission(env::block_timestamp());
+        Some(OperatorView {
+            commission_bps: operator.commission_bps,
+            scheduled_commission_bps: operator
+                .scheduled_commission
+                .map(|(commission_bps, _)| commission_bps),
+            scheduled_commission_at: operator
+                .scheduled_commission
+                .map(|(_, effective_at)| effective_at.into()),
+            total_delegated: operator.total_delegated.into(),
+            delegators: operator.delegators.into(),
+            unclaimed_commission: operator.commission.into(),
+        })
+    }
+
+    pub fn delegation_of(delegator_id: Address, operator_id: Address) -> DelegationView {
+        let contract = Self::load();
+        let (_, delegation) = contract.settled_delegation(&delegator_id, &operator_id);
+        DelegationView {
+            amount: delegation.amount.into(),
+            pending_rewards: delegation.unclaimed.into(),
+        }
+    }
+
+    /// The operator and the delegation, with rewards accrued so far split by the commission.
+    fn settled_delegation(
+        &self,
+        delegator_id: &Address,
+        operator_id: &Address,
+    ) -> (Operator, Delegation) {
+        let mut operator = self
+            .operators
+            .get(operator_id)
+            .cloned()
+            .expect("Operator not found");
+        operator.apply_scheduled_commission(env::block_timestamp());
+        let mut delegation = self
+            .delegations
+            .get(&(*delegator_id, *operator_id))
+            .cloned()
+            .unwrap_or_default();
+
+        let accrued = self.staking_rewards_of(delegation.amount) - delegation.reward_debt;
+        let commission = Self::bps_of(accrued, operator.commission_bps);
+        operator.commission = operator
+            .commission
+            .checked_add(commission)
+            .expect("Commission overflowed");
+        delegation.unclaimed = delegation
+            .unclaimed
+            .checked_add(accrued - commission)
+            .expect("Rewards overflowed");
+        delegation.reward_debt += accrued;
+        (operator, delegation)
+    }
+
+    fn store_delegation(
+        &mut self,
+        delegator_id: &Address,
+        operator_id: &Address,
+        operator: Operator,
+        mut delegation: Delegation,
+    ) {
+        delegation.reward_debt = self.staking_rewards_of(delegation.amount);
+        self.operators.insert(*operator_id, operator);
+        let key = (*delegator_id, *operator_id);
+        let count = self
+            .delegation_counts
+            .get(delegator_id)
+            .copied()
+            .unwrap_or_default();
+        if delegation.amount == 0 && delegation.unclaimed == 0 {
+            if self.delegations.remove(key).is_some() {
+                if count == 1 {
+                    self.delegation_counts.remove(*delegator_id);
+                } else {
+                    self.delegation_counts.insert(*delegator_id, count - 1);
+                }
+            }
+        } else if self.delegations.insert(key, delegation).is_none() {
+            self.delegation_counts.insert(*delegator_id, count + 1);
+        }
+    }
+
+    /// Whether the account has delegations or, as an operator, unclaimed commission.
+    pub(crate) fn has_delegations(&self, account_id: &Address) -> bool {
+        self.delegation_counts.contains_key(account_id)
+            || self
+                .operators
+                .get(account_id)
+                .map_or(false, |operator| operator.commission != 0)
+    }
+}
@@ -0,0 +1,168 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// A distribution too large for one transaction. Items are stored by index and paid out in order.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct DistributionJob {
+    items: u64,
+    total: u128,
+    processed: u64,
+    funded: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DistributionJobView {
+    items: U64,
+    total: U128,
+    processed: U64,
+    funded: bool,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn create_distribution() -> U64 {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let job_id = contract.next_distribution_id;
+        contract.next_distribution_id = job_id.checked_add(1).expect("Distribution id overflowed");
+        contract
+            .distributions
+            .insert(job_id, DistributionJob::default());
+
+        contract.save();
+        job_id.into()
+    }
+
+    /// Appends recipients to a job. Only possible before it is funded.
+    pub fn add_distribution_chunk(job_id: U64, recipients: Vec<(AccountId, U128)>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let mut job = contract
+            .distributions
+            .get(&job_id.0)
+            .cloned()
+            .expect("Distribution not found");
+        assert!(!job.funded, "The distribution is already funded");
+        for (recipient_id, amount) in recipients {
+            assert_ne!(amount.0, 0, "Amount should be greater than 0");
+            contract
+                .distribution_items
+                .insert((job_id.0, job.items), (*recipient_id, amount.0));
+            job.items += 1;
+            job.total = job
+                .total
+                .checked_add(amount.0)
+                .expect("Distribution total overflowed");
+        }
+        contract.distributions.insert(job_id.0, job);
+
+        contract.save();
+    }
+
+    /// Moves the job total out of the owner's balance. No more chunks can be added afterwards.
+    pub fn fund_distribution(job_id: U64) {
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+        assert_eq!(
+            owner_id,
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let mut job = contract
+            .distributions
+            .get(&job_id.0)
+            .cloned()
+            .expect("Distribution not found");
+        assert!(!job.funded, "The distribution is already funded");
+        let balance = contract.balance_of(&owner_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&owner_id) >= job.total,
+            "Not enough balance to fund"
+        );
+        contract.set_balance(&owner_id, balance - job.total);
+        job.funded = true;
+        contract.distributions.insert(job_id.0, job);
+
+        contract.save();
+    }
+
+    /// Pays out up to `max_items` pending recipients. Callable by anyone until the job is complete.
+    pub fn process_distribution(job_id: U64, max_items: U64) -> U64 {
+        assert_ne!(max_items.0, 0, "Max items should be greater than 0");
+        let mut contract = Self::load();
+
+        let mut job = contract
+            .distributions
+            .get(&job_id.0)
+            .cloned()
+            .expect("Distribution not found");
+        assert!(job.funded, "The distribution is not funded");
+        assert!(job.processed < job.items, "The distribution is complete");
+        contract.assert_not_paused();
+
+        // The owner funded the job, so each payout is reported as a transfer from the owner.
+        let funder_id = contract_owner_address();
+        let end = job.items.min(job.processed.saturating_add(max_items.0));
+        let count = end - job.processed;
+        for index in job.processed..end {
+            let (recipient_id, amount) = contract
+                .distribution_items
+                .remove((job_id.0, index))
+                .expect("Distribution item not found");
+            contract.pay_distribution_item(&funder_id, &recipient_id, amount);
+        }
+        job.processed = end;
+        contract.distributions.insert(job_id.0, job);
+        l1x_sdk::msg(&format!(
+            "Distribution {} paid {} recipients",
+            job_id.0, count
+        ));
+
+        contract.save();
+        count.into()
+    }
+
+    pub fn distribution(job_id: U64) -> Option<DistributionJobView> {
+        let contract = Self::load();
+        contract
+            .distributions
+            .get(&job_id.0)
+            .map(|job| DistributionJobView {
+                items: job.items.into(),
+                total: job.total.into(),
+                processed: job.processed.into(),
+                funded: job.funded,
+            })
+    }
+
+    /// Credits one payout after the recipient checks an ordinary transfer from `funder_id` gets.
+    fn pay_distribution_item(&mut self, funder_id: &Address, recipient_id: &Address, amount: u128) {
+        #[cfg(feature = "compliance")]
+        self.assert_not_sanctioned(recipient_id);
+        self.assert_memo_not_required(recipient_id);
+        self.assert_transfer_rules(funder_id, recipient_id, amount);
+
+        let balance = self.balance_of(recipient_id).unwrap_or_default();
+        let new_balance = balance.checked_add(amount).expect("Balance overflowed");
+        self.assert_max_wallet(recipient_id, new_balance);
+        self.set_balance(recipient_id, new_balance);
+        self.record_transfer(funder_id, recipient_id, amount, true);
+    }
+}
@@ -0,0 +1,80 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy)]
+pub enum DustMode {
+    /// Transfers that would leave dust behind fail.
+    Reject,
+    /// The dust is sent along with the transfer. Transfers the sender didn't make themselves,
+    /// like `ft_transfer_from`, can't take more than asked and fail as with `Reject`.
+    Sweep,
+}
+
+/// Keeps senders from being left with `0 < balance < threshold`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct DustPolicy {
+    threshold: U128,
+    mode: DustMode,
+}
+
+impl DustPolicy {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        self.threshold = rescale(self.threshold.0).into();
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `None` turns the policy off.
+    pub fn set_dust_policy(policy: Option<DustPolicy>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if let Some(policy) = &policy {
+            assert_ne!(policy.threshold.0, 0, "Threshold should be greater than 0");
+        }
+
+        *contract.dust_policy = policy;
+
+        contract.save();
+    }
+
+    pub fn dust_policy() -> Option<DustPolicy> {
+        let contract = Self::load();
+        contract.dust_policy.into_inner()
+    }
+
+    /// The amount to actually send from `sender_id`, with any dust swept along.
+    pub(crate) fn apply_dust_policy(&self, sender_id: &Address, amount: u128) -> u128 {
+        let Some(policy) = &*self.dust_policy else {
+            return amount;
+        };
+        let balance = self.balance_of(sender_id).unwrap_or_default();
+        let remainder = balance.saturating_sub(amount);
+        if remainder == 0 || remainder >= policy.threshold.0 {
+            return amount;
+        }
+
+        match policy.mode {
+            DustMode::Sweep if caller_address() == *sender_id => {
+                assert!(
+                    self.spendable_balance(sender_id) >= balance,
+                    "The dust can't be swept while part of the balance is frozen or reserved"
+                );
+                balance
+            }
+            DustMode::Reject | DustMode::Sweep => panic!(
+                "The transfer would leave a balance below the dust threshold of {}",
+                policy.threshold.0
+            ),
+        }
+    }
+}
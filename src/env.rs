@@ -0,0 +1,126 @@
+//! The host functions the contract logic depends on, behind a swappable [`Env`].
+//!
+//! Contract code calls the free functions of this module instead of `l1x_sdk` directly, so the
+//! caller is always resolved through the trusted forwarders. They forward to [`HostEnv`] unless
+//! tests installed another environment with `set_env`. Only logging, events, cross-contract calls
+//! (see `cross_contract`) and the SDK collections go to the host themselves, so an environment
+//! has to share its storage with the host's.
+
+use std::cell::RefCell;
+
+use l1x_sdk::types::Address;
+
+pub(crate) trait Env {
+    fn caller_address(&self) -> Address;
+    fn contract_owner_address(&self) -> Address;
+    fn contract_instance_address(&self) -> Address;
+    fn block_timestamp(&self) -> u128;
+    fn input(&self) -> Option<Vec<u8>>;
+    fn storage_read(&self, key: &[u8]) -> Option<Vec<u8>>;
+    fn storage_write(&self, key: &[u8], value: &[u8]);
+    fn contract_instance_balance(&self) -> u128;
+    fn transfer_to(&self, to: &Address, amount: u128);
+    fn transfer_from_caller(&self, amount: u128);
+}
+
+/// The real host, through `l1x_sdk`.
+pub(crate) struct HostEnv;
+
+impl Env for HostEnv {
+    fn caller_address(&self) -> Address {
+        l1x_sdk::caller_address()
+    }
+
+    fn contract_owner_address(&self) -> Address {
+        l1x_sdk::contract_owner_address()
+    }
+
+    fn contract_instance_address(&self) -> Address {
+        l1x_sdk::contract_instance_address()
+    }
+
+    fn block_timestamp(&self) -> u128 {
+        l1x_sdk::block_timestamp()
+    }
+
+    fn input(&self) -> Option<Vec<u8>> {
+        l1x_sdk::input()
+    }
+
+    fn storage_read(&self, key: &[u8]) -> Option<Vec<u8>> {
+        l1x_sdk::storage_read(key)
+    }
+
+    fn storage_write(&self, key: &[u8], value: &[u8]) {
+        l1x_sdk::storage_write(key, value);
+    }
+
+    fn contract_instance_balance(&self) -> u128 {
+        l1x_sdk::contract_instance_balance()
+    }
+
+    fn transfer_to(&self, to: &Address, amount: u128) {
+        l1x_sdk::transfer_to(to, amount);
+    }
+
+    fn transfer_from_caller(&self, amount: u128) {
+        l1x_sdk::transfer_from_caller(amount);
+    }
+}
+
+thread_local! {
+    static ENV: RefCell<Box<dyn Env>> = RefCell::new(Box::new(HostEnv));
+}
+
+/// Replaces the environment for the rest of the execution.
+#[cfg(test)]
+pub(crate) fn set_env(env: Box<dyn Env>) {
+    ENV.with(|current| *current.borrow_mut() = env);
+}
+
+fn with_env<R>(f: impl FnOnce(&dyn Env) -> R) -> R {
+    ENV.with(|current| f(current.borrow().as_ref()))
+}
+
+/// The caller, or the sender it forwards for if it is a trusted forwarder.
+pub(crate) fn caller_address() -> Address {
+    crate::forwarder::resolve_caller(with_env(|env| env.caller_address()))
+}
+
+pub(crate) fn contract_owner_address() -> Address {
+    with_env(|env| env.contract_owner_address())
+}
+
+pub(crate) fn contract_instance_address() -> Address {
+    with_env(|env| env.contract_instance_address())
+}
+
+pub(crate) fn block_timestamp() -> u128 {
+    with_env(|env| env.block_timestamp())
+}
+
+pub(crate) fn input() -> Option<Vec<u8>> {
+    with_env(|env| env.input())
+}
+
+pub(crate) fn storage_read(key: &[u8]) -> Option<Vec<u8>> {
+    with_env(|env| env.storage_read(key))
+}
+
+pub(crate) fn storage_write(key: &[u8], value: &[u8]) {
+    with_env(|env| env.storage_write(key, value));
+}
+
+pub(crate) fn contract_instance_balance() -> u128 {
+    with_env(|env| env.contract_instance_balance())
+}
+
+/// Sends native L1X from the contract to `to`.
+pub(crate) fn transfer_to(to: &Address, amount: u128) {
+    with_env(|env| env.transfer_to(to, amount));
+}
+
+/// Pulls native L1X from the caller into the contract.
+pub(crate) fn transfer_from_caller(amount: u128) {
+    with_env(|env| env.transfer_from_caller(amount));
+}
@@ -0,0 +1,312 @@
+use std::cell::Cell;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::alerts::{AlertKind, AlertSeverity};
+use crate::namespace;
+use crate::roles::{Role, RoleAction};
+
+/// The kind of transfer a `Tax` event was charged on.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy)]
+pub enum TaxKind {
+    Buy,
+    Sell,
+    Transfer,
+}
+
+/// Which of the two independent authorities a clawback approval came from.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ClawbackAuthority {
+    Compliance,
+    Governance,
+}
+
+thread_local! {
+    /// Events emitted during the current call that `save` hasn't added to the event nonce yet.
+    static PENDING_EVENTS: Cell<u64> = Cell::new(0);
+}
+
+/// Structured contract events, stored on chain with `emit_event_experimental`.
+#[derive(BorshSerialize)]
+pub(crate) enum FtEvent {
+    AccountClosed {
+        account_id: Address,
+        beneficiary_id: Address,
+        amount: u128,
+    },
+    AccountEvacuated {
+        account_id: Address,
+        backup_id: Address,
+        amount: u128,
+    },
+    AccountFlagged {
+        account_id: Address,
+    },
+    AccountFrozen {
+        account_id: Address,
+        amount: u128,
+    },
+    AccountUnfrozen {
+        account_id: Address,
+    },
+    Alert {
+        kind: AlertKind,
+        severity: AlertSeverity,
+        account_id: Address,
+        related_id: Option<Address>,
+        amount: u128,
+        threshold: u128,
+    },
+    BalancesRootCommitted {
+        root: [u8; 32],
+        leaves: u32,
+    },
+    Burn {
+        account_id: Address,
+        amount: u128,
+    },
+    ClaimCodeCreated {
+        creator_id: Address,
+        hash: [u8; 32],
+        amount: u128,
+    },
+    ClaimCodeRedeemed {
+        hash: [u8; 32],
+        recipient_id: Address,
+        amount: u128,
+    },
+    ClaimCodeRefunded {
+        hash: [u8; 32],
+        creator_id: Address,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "compliance"), allow(dead_code))]
+    ClawbackApproved {
+        clawback_id: u64,
+        authority: ClawbackAuthority,
+        approver_id: Address,
+    },
+    #[cfg_attr(not(feature = "compliance"), allow(dead_code))]
+    ClawbackCancelled {
+        clawback_id: u64,
+        cancelled_by: Address,
+    },
+    #[cfg_attr(not(feature = "compliance"), allow(dead_code))]
+    ClawbackExecuted {
+        clawback_id: u64,
+        account_id: Address,
+        recipient_id: Address,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "compliance"), allow(dead_code))]
+    ClawbackProposed {
+        clawback_id: u64,
+        account_id: Address,
+        recipient_id: Address,
+        amount: u128,
+        reason: String,
+    },
+    DecimalsMigrated {
+        from: u8,
+        to: u8,
+    },
+    DecimalsMigrationProgress {
+        processed: u32,
+        total: u32,
+    },
+    #[cfg_attr(not(feature = "holds"), allow(dead_code))]
+    HoldAuthorized {
+        hold_id: u64,
+        payer_id: Address,
+        merchant_id: Address,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "holds"), allow(dead_code))]
+    HoldCaptured {
+        hold_id: u64,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "holds"), allow(dead_code))]
+    HoldVoided {
+        hold_id: u64,
+    },
+    Initialized {
+        holders: u32,
+        total_supply: u128,
+    },
+    #[cfg_attr(not(feature = "invoices"), allow(dead_code))]
+    InvoiceCancelled {
+        invoice_id: u64,
+    },
+    #[cfg_attr(not(feature = "invoices"), allow(dead_code))]
+    InvoiceCreated {
+        invoice_id: u64,
+        issuer_id: Address,
+        payer_id: Address,
+        total: u128,
+    },
+    #[cfg_attr(not(feature = "invoices"), allow(dead_code))]
+    InvoicePaid {
+        invoice_id: u64,
+        amount: u128,
+        outstanding: u128,
+    },
+    MaxTxAmountChanged {
+        old_amount: Option<u128>,
+        new_amount: Option<u128>,
+    },
+    MintBreakerTripped {
+        attempted: u128,
+        minted_in_window: u128,
+        limit: u128,
+    },
+    #[cfg_attr(not(feature = "multi_token"), allow(dead_code))]
+    MtMint {
+        token_id: u64,
+        recipient_id: Address,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "multi_token"), allow(dead_code))]
+    MtTransfer {
+        token_id: u64,
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+    },
+    Multisend {
+        sender_id: Address,
+        recipients: u32,
+        total: u128,
+    },
+    #[cfg_attr(not(feature = "names"), allow(dead_code))]
+    NameRegistered {
+        name: String,
+        owner_id: Address,
+        expires_at: u128,
+    },
+    PendingTransferCreated {
+        sender_id: Address,
+        recipient_id: Address,
+        transfer_id: u64,
+        amount: u128,
+        executable_at: u128,
+    },
+    PendingTransferCancelled {
+        sender_id: Address,
+        transfer_id: u64,
+    },
+    PendingTransferFinalized {
+        sender_id: Address,
+        transfer_id: u64,
+    },
+    #[cfg_attr(not(feature = "reflection"), allow(dead_code))]
+    Reflection {
+        sender_id: Address,
+        amount: u128,
+    },
+    Refund {
+        original_event_nonce: u64,
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+        reason: String,
+    },
+    RoleChanged {
+        role: Role,
+        action: RoleAction,
+        grantor_id: Address,
+        account_id: Address,
+    },
+    Royalty {
+        marketplace_id: Address,
+        creator_id: Address,
+        sender_id: Address,
+        amount: u128,
+    },
+    SettlementAborted {
+        sender_id: Address,
+        settlement_id: String,
+    },
+    SettlementCommitted {
+        sender_id: Address,
+        settlement_id: String,
+    },
+    SettlementPrepared {
+        settlement_id: String,
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "staking"), allow(dead_code))]
+    StakeSlashed {
+        staker_id: Address,
+        slasher_id: Address,
+        amount: u128,
+        reason: String,
+    },
+    #[cfg_attr(not(feature = "tax"), allow(dead_code))]
+    Tax {
+        kind: TaxKind,
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+    },
+    /// `event_nonce` is this event's own nonce, which `refund` takes to find the transfer.
+    Transfer {
+        event_nonce: u64,
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+    },
+    TransferHookDisabled {
+        hook_id: Address,
+        failures: u32,
+    },
+    TransferMemo {
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+        memo: String,
+    },
+    TransferReference {
+        sender_id: Address,
+        recipient_id: Address,
+        amount: u128,
+        reference_id: String,
+    },
+    VolumeBreakerTripped {
+        volume: u128,
+        limit: u128,
+        until: u128,
+    },
+}
+
+/// What is stored on chain: the event with the namespace of the token instance that emitted it,
+/// `#<id>` for a factory token and empty for the root token.
+#[derive(BorshSerialize)]
+pub(crate) struct NamespacedEvent {
+    pub(crate) namespace: String,
+    pub(crate) event: FtEvent,
+}
+
+impl FtEvent {
+    pub(crate) fn emit(self) {
+        let namespace =
+            String::from_utf8(namespace::active_namespace()).expect("Namespaces are valid UTF-8");
+        l1x_sdk::emit_event_experimental(NamespacedEvent {
+            namespace,
+            event: self,
+        });
+        PENDING_EVENTS.with(|pending| pending.set(pending.get() + 1));
+    }
+}
+
+pub(crate) fn pending_events() -> u64 {
+    PENDING_EVENTS.with(Cell::get)
+}
+
+pub(crate) fn take_pending_events() -> u64 {
+    PENDING_EVENTS.with(|pending| pending.replace(0))
+}
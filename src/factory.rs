@@ -0,0 +1,231 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::balance_root::MerkleProof;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::multicall::CallSpec;
+use crate::multiquery::{QueryResult, QuerySpec};
+use crate::roles::Role;
+use crate::{FTMetadata, L1xFtErc20};
+
+/// Number of tokens created so far. Ids start at 1.
+const STORAGE_FACTORY_TOKEN_COUNT_KEY: &[u8; 19] = b"factory_token_count";
+
+pub(crate) fn assert_token_exists(token_id: U64) {
+    assert!(
+        token_id.0 != 0 && token_id.0 <= token_count(),
+        "Token not found"
+    );
+}
+
+/// `#` is rejected in user namespaces, so a factory token can't share keys with a root token.
+pub(crate) fn token_namespace(token_id: u64) -> Vec<u8> {
+    format!("#{token_id}").into_bytes()
+}
+
+fn token_count() -> u64 {
+    env::storage_read(STORAGE_FACTORY_TOKEN_COUNT_KEY)
+        .map(|bytes| u64::try_from_slice(&bytes).unwrap())
+        .unwrap_or_default()
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TokenInfo {
+    token_id: U64,
+    metadata: FTMetadata,
+    total_supply: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Creates an isolated token whose state lives under its own namespace. The factory owner
+    /// owns every token it creates.
+    ///
+    /// Any entrypoint acts on a created token when its call adds a `_token_id` argument. The
+    /// `token_ft_*` entrypoints take the id as a regular parameter instead.
+    pub fn create_token(metadata: FTMetadata, initial_holders: Vec<(AccountId, U128)>) -> U64 {
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        Self::assert_valid_metadata(&metadata);
+
+        let token_id = token_count() + 1;
+        env::storage_write(
+            STORAGE_FACTORY_TOKEN_COUNT_KEY,
+            &token_id.try_to_vec().unwrap(),
+        );
+
+        let namespace = token_namespace(token_id);
+        Self::with_namespace(namespace.clone(), || {
+            let (account_ids, amounts) = initial_holders.into_iter().unzip();
+            let mut contract = Self::initial_state(metadata, &namespace);
+            contract.grant_role(Role::Minter, contract_owner_address());
+            contract.initialize_balance_holders(account_ids, amounts);
+            contract.save();
+        });
+        l1x_sdk::msg(&format!("Token {token_id} created"));
+
+        token_id.into()
+    }
+
+    pub fn list_tokens() -> Vec<TokenInfo> {
+        (1..=token_count())
+            .map(|token_id| {
+                Self::with_token(token_id.into(), || {
+                    let contract = Self::load();
+                    TokenInfo {
+                        token_id: token_id.into(),
+                        metadata: contract.metadata.into_inner(),
+                        total_supply: (*contract.total_supply).into(),
+                    }
+                })
+            })
+            .collect()
+    }
+
+    pub fn token_ft_name(token_id: U64) -> String {
+        Self::with_token(token_id, Self::ft_name)
+    }
+
+    pub fn token_ft_symbol(token_id: U64) -> String {
+        Self::with_token(token_id, Self::ft_symbol)
+    }
+
+    pub fn token_ft_decimals(token_id: U64) -> u8 {
+        Self::with_token(token_id, Self::ft_decimals)
+    }
+
+    pub fn token_ft_icon(token_id: U64) -> Option<String> {
+        Self::with_token(token_id, Self::ft_icon)
+    }
+
+    pub fn token_ft_metadata(token_id: U64) -> FTMetadata {
+        Self::with_token(token_id, Self::ft_metadata)
+    }
+
+    pub fn token_ft_mint(token_id: U64, recipient_id: AccountId, amount: U128) {
+        Self::with_token(token_id, || Self::ft_mint(recipient_id, amount))
+    }
+
+    pub fn token_ft_transfer(
+        token_id: U64,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        Self::with_token(token_id, || {
+            Self::ft_transfer(recipient_id, amount, idempotency_key, reference_id)
+        })
+    }
+
+    pub fn token_ft_transfer_from(
+        token_id: U64,
+        sender_id: Address,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        Self::with_token(token_id, || {
+            Self::ft_transfer_from(
+                sender_id,
+                recipient_id,
+                amount,
+                idempotency_key,
+                reference_id,
+            )
+        })
+    }
+
+    pub fn token_ft_transfer_with_memo(
+        token_id: U64,
+        recipient_id: AccountId,
+        amount: U128,
+        memo: String,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        Self::with_token(token_id, || {
+            Self::ft_transfer_with_memo(recipient_id, amount, memo, idempotency_key, reference_id)
+        })
+    }
+
+    pub fn token_ft_transfer_by_key(
+        token_id: U64,
+        owner_id: Address,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        Self::with_token(token_id, || {
+            Self::ft_transfer_by_key(
+                owner_id,
+                recipient_id,
+                amount,
+                idempotency_key,
+                reference_id,
+            )
+        })
+    }
+
+    #[cfg(feature = "names")]
+    pub fn token_ft_transfer_to_name(token_id: U64, name: String, amount: U128) {
+        Self::with_token(token_id, || Self::ft_transfer_to_name(name, amount))
+    }
+
+    pub fn token_ft_multisend(token_id: U64, transfers: Vec<(AccountId, U128)>) {
+        Self::with_token(token_id, || Self::ft_multisend(transfers))
+    }
+
+    pub fn token_ft_is_fixed_supply(token_id: U64) -> bool {
+        Self::with_token(token_id, Self::ft_is_fixed_supply)
+    }
+
+    pub fn token_ft_total_supply(token_id: U64) -> U128 {
+        Self::with_token(token_id, Self::ft_total_supply)
+    }
+
+    pub fn token_ft_balance_of(token_id: U64, account_id: Address) -> U128 {
+        Self::with_token(token_id, || Self::ft_balance_of(account_id))
+    }
+
+    pub fn token_ft_balance_proof(token_id: U64, account_id: Address) -> MerkleProof {
+        Self::with_token(token_id, || Self::ft_balance_proof(account_id))
+    }
+
+    pub fn token_ft_approve(token_id: U64, spender_id: AccountId, amount: U128) {
+        Self::with_token(token_id, || Self::ft_approve(spender_id, amount))
+    }
+
+    pub fn token_ft_increase_allowance(token_id: U64, spender_id: AccountId, amount: U128) {
+        Self::with_token(token_id, || Self::ft_increase_allowance(spender_id, amount))
+    }
+
+    pub fn token_ft_decrease_allowance(token_id: U64, spender_id: AccountId, amount: U128) {
+        Self::with_token(token_id, || Self::ft_decrease_allowance(spender_id, amount))
+    }
+
+    pub fn token_ft_allowance(token_id: U64, owner_id: Address, spender_id: Address) -> U128 {
+        Self::with_token(token_id, || Self::ft_allowance(owner_id, spender_id))
+    }
+
+    pub fn token_ft_multicall(token_id: U64, calls: Vec<CallSpec>) {
+        Self::with_token(token_id, || Self::ft_multicall(calls))
+    }
+
+    pub fn token_ft_multiquery(token_id: U64, queries: Vec<QuerySpec>) -> Vec<QueryResult> {
+        Self::with_token(token_id, || Self::ft_multiquery(queries))
+    }
+
+    fn with_token<R>(token_id: U64, f: impl FnOnce() -> R) -> R {
+        assert_token_exists(token_id);
+        Self::with_namespace(token_namespace(token_id.0), f)
+    }
+}
@@ -0,0 +1,112 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::{L1xFtErc20, BPS_DENOMINATOR};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub enum FeeDestination {
+    Treasury,
+    /// Shared by current stakers. Falls back to the treasury while nothing is staked.
+    StakersPool,
+    Burn,
+    Account {
+        account_id: Address,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct FeeRoute {
+    destination: FeeDestination,
+    bps: u16,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Splits collected fees between destinations. The weights must add up to 10000 bps; an
+    /// empty table sends everything to the treasury.
+    pub fn set_fee_routes(routes: Vec<FeeRoute>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if !routes.is_empty() {
+            let total_bps: u128 = routes.iter().map(|route| u128::from(route.bps)).sum();
+            assert_eq!(
+                total_bps, BPS_DENOMINATOR,
+                "Fee route weights should add up to {BPS_DENOMINATOR} bps"
+            );
+        }
+        #[cfg(not(feature = "staking"))]
+        assert!(
+            !routes
+                .iter()
+                .any(|route| matches!(route.destination, FeeDestination::StakersPool)),
+            "Staking is not enabled"
+        );
+
+        *contract.fee_routes = routes;
+
+        contract.save();
+    }
+
+    pub fn fee_routes() -> Vec<FeeRoute> {
+        let contract = Self::load();
+        contract.fee_routes.into_inner()
+    }
+
+    /// Sends a fee already debited from `payer_id` to the fee destinations.
+    #[cfg_attr(not(feature = "tax"), allow(dead_code))]
+    pub(crate) fn route_fee(&mut self, payer_id: &Address, amount: u128) {
+        if self.fee_routes.is_empty() {
+            return self.treasury_credit(amount);
+        }
+
+        let routes = self.fee_routes.clone();
+        let mut remaining = amount;
+        for (index, route) in routes.iter().enumerate() {
+            // The last route takes the rounding remainder.
+            let share = if index + 1 == routes.len() {
+                remaining
+            } else {
+                Self::bps_of(amount, route.bps)
+            };
+            if share == 0 {
+                continue;
+            }
+            remaining -= share;
+
+            match &route.destination {
+                FeeDestination::Treasury => self.treasury_credit(share),
+                #[cfg(feature = "staking")]
+                FeeDestination::StakersPool if self.has_stakers() => {
+                    self.distribute_staking_reward(share)
+                }
+                FeeDestination::StakersPool => self.treasury_credit(share),
+                FeeDestination::Burn => {
+                    *self.total_supply = self
+                        .total_supply
+                        .checked_sub(share)
+                        .expect("total_supply is underflowed");
+                    FtEvent::Burn {
+                        account_id: *payer_id,
+                        amount: share,
+                    }
+                    .emit();
+                }
+                FeeDestination::Account { account_id } => {
+                    let balance = self.balance_of(account_id).unwrap_or_default();
+                    self.set_balance(
+                        account_id,
+                        balance.checked_add(share).expect("Balance overflowed"),
+                    );
+                }
+            }
+        }
+    }
+}
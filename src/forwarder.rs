@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::{namespace, L1xFtErc20};
+
+/// Kept outside the contract state because the caller is resolved before the state is loaded.
+const STORAGE_TRUSTED_FORWARDERS_KEY: &[u8; 18] = b"trusted_forwarders";
+/// Argument a trusted forwarder adds to the call's JSON to name the account it acts for.
+const FORWARDED_SENDER_ARG: &str = "_msg_sender";
+
+#[contract]
+impl L1xFtErc20 {
+    /// Calls from a trusted forwarder are attributed to the address in their `_msg_sender`
+    /// argument.
+    pub fn add_trusted_forwarder(forwarder_id: Address) {
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let mut forwarders = load_trusted_forwarders();
+        assert!(
+            forwarders.insert(forwarder_id),
+            "This address is already a trusted forwarder"
+        );
+        store_trusted_forwarders(&forwarders);
+    }
+
+    pub fn remove_trusted_forwarder(forwarder_id: Address) {
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let mut forwarders = load_trusted_forwarders();
+        assert!(
+            forwarders.remove(&forwarder_id),
+            "This address is not a trusted forwarder"
+        );
+        store_trusted_forwarders(&forwarders);
+    }
+
+    pub fn trusted_forwarders() -> Vec<Address> {
+        load_trusted_forwarders().into_iter().collect()
+    }
+}
+
+/// The account a call acts for: the forwarded sender for calls from a trusted forwarder,
+/// otherwise the caller itself.
+pub(crate) fn resolve_caller(caller_id: Address) -> Address {
+    if !load_trusted_forwarders().contains(&caller_id) {
+        return caller_id;
+    }
+
+    let input = env::input().expect("A forwarded call must have arguments");
+    let args: serde_json::Value =
+        serde_json::from_slice(&input).expect("Failed to deserialize input from JSON.");
+    let sender = args
+        .get(FORWARDED_SENDER_ARG)
+        .cloned()
+        .expect("A forwarded call must name the original sender");
+    serde_json::from_value(sender).expect("The forwarded sender should be an address")
+}
+
+fn trusted_forwarders_key() -> Vec<u8> {
+    L1xFtErc20::storage_key(
+        &namespace::active_namespace(),
+        STORAGE_TRUSTED_FORWARDERS_KEY,
+    )
+}
+
+fn load_trusted_forwarders() -> BTreeSet<Address> {
+    env::storage_read(&trusted_forwarders_key())
+        .map(|bytes| {
+            BTreeSet::try_from_slice(&bytes).expect("Failed to read the trusted forwarders")
+        })
+        .unwrap_or_default()
+}
+
+fn store_trusted_forwarders(forwarders: &BTreeSet<Address>) {
+    env::storage_write(
+        &trusted_forwarders_key(),
+        &forwarders
+            .try_to_vec()
+            .expect("Failed to write the trusted forwarders"),
+    );
+}
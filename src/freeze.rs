@@ -0,0 +1,99 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Freezes `amount` of the account's balance. The rest stays transferable.
+    pub fn freeze(account_id: Address, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.assert_no_decimals_migration();
+
+        contract.index_account(&account_id);
+        contract.frozen.insert(account_id, amount.0);
+        FtEvent::AccountFrozen {
+            account_id,
+            amount: amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn unfreeze(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract
+            .frozen
+            .remove(account_id)
+            .expect("This account is not frozen");
+        FtEvent::AccountUnfrozen { account_id }.emit();
+
+        contract.save();
+    }
+
+    pub fn frozen_balance_of(account_id: Address) -> U128 {
+        let contract = Self::load();
+        contract.frozen_amount(&account_id).into()
+    }
+
+    /// Balance that isn't frozen or reserved by holds.
+    pub fn free_balance_of(account_id: Address) -> U128 {
+        let contract = Self::load();
+        contract.spendable_balance(&account_id).into()
+    }
+
+    /// Carries `amount` of the freeze on `from_id` over to `to_id`, along with the tokens.
+    pub(crate) fn move_freeze(&mut self, from_id: &Address, to_id: &Address, amount: u128) {
+        self.release_freeze(from_id, amount);
+
+        let frozen = self
+            .frozen_amount(to_id)
+            .checked_add(amount)
+            .expect("Frozen amount overflowed");
+        self.frozen.insert(*to_id, frozen);
+        FtEvent::AccountFrozen {
+            account_id: *to_id,
+            amount: frozen,
+        }
+        .emit();
+    }
+
+    /// Takes `amount` off the freeze on the account, for frozen tokens that leave it.
+    pub(crate) fn release_freeze(&mut self, account_id: &Address, amount: u128) {
+        let remaining = self.frozen_amount(account_id) - amount;
+        if remaining == 0 {
+            self.frozen.remove(*account_id);
+            FtEvent::AccountUnfrozen {
+                account_id: *account_id,
+            }
+            .emit();
+        } else {
+            self.frozen.insert(*account_id, remaining);
+            FtEvent::AccountFrozen {
+                account_id: *account_id,
+                amount: remaining,
+            }
+            .emit();
+        }
+    }
+
+    pub(crate) fn frozen_amount(&self, account_id: &Address) -> u128 {
+        self.frozen.get(account_id).copied().unwrap_or_default()
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+pub(crate) type HoldIds = BTreeSet<u64>;
+
+/// Funds reserved for a merchant. They stay in the payer's balance but can't be spent.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct Hold {
+    payer_id: Address,
+    merchant_id: Address,
+    amount: U128,
+    expires_at: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn authorize(merchant_id: AccountId, amount: U128, expires_at: U128) -> U64 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        let payer_id = caller_address();
+        assert_ne!(
+            payer_id, *merchant_id,
+            "Payer and merchant cannot be the same"
+        );
+        assert!(
+            expires_at.0 > env::block_timestamp(),
+            "The expiry should be in the future"
+        );
+        assert!(
+            contract.spendable_balance(&payer_id) >= amount.0,
+            "Not enough balance to authorize"
+        );
+
+        let hold_id = contract.next_hold_id;
+        contract.next_hold_id = hold_id.checked_add(1).expect("Hold id overflowed");
+        contract.holds.insert(
+            hold_id,
+            Hold {
+                payer_id,
+                merchant_id: *merchant_id,
+                amount,
+                expires_at,
+            },
+        );
+        let mut hold_ids = contract
+            .holds_by_payer
+            .get(&payer_id)
+            .cloned()
+            .unwrap_or_default();
+        hold_ids.insert(hold_id);
+        contract.holds_by_payer.insert(payer_id, hold_ids);
+        FtEvent::HoldAuthorized {
+            hold_id,
+            payer_id,
+            merchant_id: *merchant_id,
+            amount: amount.0,
+        }
+        .emit();
+
+        contract.save();
+        hold_id.into()
+    }
+
+    /// Settles up to the held amount to the merchant and releases the rest. The settlement is
+    /// an ordinary transfer, so the payer's withdrawal protection still applies.
+    pub fn capture(hold_id: U64, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+
+        let hold = contract.remove_hold(hold_id.0);
+        assert_eq!(
+            caller_address(),
+            hold.merchant_id,
+            "Only the merchant can capture a hold"
+        );
+        assert!(
+            env::block_timestamp() < hold.expires_at.0,
+            "The hold has expired"
+        );
+        assert!(amount.0 <= hold.amount.0, "Amount exceeds the held amount");
+
+        contract.transfer_or_hold(&hold.payer_id, &hold.merchant_id, amount.0);
+        FtEvent::HoldCaptured {
+            hold_id: hold_id.0,
+            amount: amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Releases a hold. The merchant can void at any time, the payer once it has expired.
+    pub fn void(hold_id: U64) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let hold = contract.remove_hold(hold_id.0);
+        let expired = env::block_timestamp() >= hold.expires_at.0;
+        assert!(
+            caller_id == hold.merchant_id || (caller_id == hold.payer_id && expired),
+            "Only the merchant, or the payer after expiry, can void a hold"
+        );
+        FtEvent::HoldVoided { hold_id: hold_id.0 }.emit();
+
+        contract.save();
+    }
+
+    pub fn hold(hold_id: U64) -> Option<Hold> {
+        let contract = Self::load();
+        contract.holds.get(&hold_id.0).cloned()
+    }
+
+    pub fn holds_of(payer_id: Address) -> Vec<(U64, Hold)> {
+        let contract = Self::load();
+        contract
+            .holds_by_payer
+            .get(&payer_id)
+            .map(|hold_ids| {
+                hold_ids
+                    .iter()
+                    .filter_map(|hold_id| {
+                        let hold = contract.holds.get(hold_id)?;
+                        Some(((*hold_id).into(), hold.clone()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn held_balance_of(account_id: Address) -> U128 {
+        let contract = Self::load();
+        contract.held_amount(&account_id).into()
+    }
+
+    /// Sum of the account's unexpired holds.
+    pub(crate) fn held_amount(&self, payer_id: &Address) -> u128 {
+        let Some(hold_ids) = self.holds_by_payer.get(payer_id) else {
+            return 0;
+        };
+        let now = env::block_timestamp();
+        hold_ids
+            .iter()
+            .filter_map(|hold_id| self.holds.get(hold_id))
+            .filter(|hold| now < hold.expires_at.0)
+            .map(|hold| hold.amount.0)
+            .sum()
+    }
+
+    pub(crate) fn prune_expired_holds(&mut self, payer_id: &Address) -> u32 {
+        let Some(hold_ids) = self.holds_by_payer.get(payer_id) else {
+            return 0;
+        };
+        let now = env::block_timestamp();
+        let expired: Vec<u64> = hold_ids
+            .iter()
+            .copied()
+            .filter(|hold_id| {
+                self.holds
+                    .get(hold_id)
+                    .map_or(false, |hold| now >= hold.expires_at.0)
+            })
+            .collect();
+
+        for hold_id in &expired {
+            self.remove_hold(*hold_id);
+            FtEvent::HoldVoided { hold_id: *hold_id }.emit();
+        }
+        expired.len() as u32
+    }
+
+    fn remove_hold(&mut self, hold_id: u64) -> Hold {
+        let hold = self.holds.remove(hold_id).expect("Hold not found");
+        let mut hold_ids = self
+            .holds_by_payer
+            .get(&hold.payer_id)
+            .cloned()
+            .unwrap_or_default();
+        hold_ids.remove(&hold_id);
+        self.holds_by_payer.insert(hold.payer_id, hold_ids);
+        hold
+    }
+}
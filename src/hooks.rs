@@ -0,0 +1,135 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, Gas, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::rules::TransferRule;
+use crate::L1xFtErc20;
+
+pub(crate) const DEFAULT_MAX_HOOK_FAILURES: u32 = 3;
+
+/// An owner-allowlisted transfer hook. Failed calls are counted and the hook is disabled once
+/// they reach the contract's limit in a row.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TransferHook {
+    gas_budget: Gas,
+    consecutive_failures: u32,
+    disabled: bool,
+}
+
+impl TransferHook {
+    /// Gas budget for a call, or `None` if the hook shouldn't be called.
+    pub(crate) fn active_gas_budget(&self) -> Option<Gas> {
+        (!self.disabled).then_some(self.gas_budget)
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Adds a hook to the allowlist or updates its gas budget. This also re-enables a disabled hook.
+    pub fn allow_transfer_hook(hook_id: Address, gas_budget: U64) {
+        assert_ne!(gas_budget.0, 0, "Gas budget should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.transfer_hooks.insert(
+            hook_id,
+            TransferHook {
+                gas_budget: gas_budget.0,
+                consecutive_failures: 0,
+                disabled: false,
+            },
+        );
+
+        contract.save();
+    }
+
+    pub fn disallow_transfer_hook(hook_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            !contract.transfer_rules.iter().any(|entry| matches!(
+                entry.rule(),
+                TransferRule::ExternalHook { hook_id: rule_hook_id } if *rule_hook_id == hook_id
+            )),
+            "The hook is still used by a transfer rule"
+        );
+        assert!(
+            contract.transfer_hooks.remove(hook_id).is_some(),
+            "This hook is not allowlisted"
+        );
+
+        contract.save();
+    }
+
+    pub fn set_max_hook_failures(max_failures: u32) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(max_failures, 0, "Max failures should be greater than 0");
+
+        contract.max_hook_failures = max_failures;
+
+        contract.save();
+    }
+
+    pub fn transfer_hook(hook_id: Address) -> Option<TransferHook> {
+        let contract = Self::load();
+        contract.transfer_hooks.get(&hook_id).cloned()
+    }
+
+    pub fn max_hook_failures() -> u32 {
+        let contract = Self::load();
+        contract.max_hook_failures
+    }
+
+    pub(crate) fn assert_hook_allowed(&self, hook_id: &Address) {
+        assert!(
+            self.transfer_hooks.contains_key(hook_id),
+            "'{}' is not an allowlisted transfer hook",
+            hook_id
+        );
+    }
+
+    /// Resets the failure streak on success; disables the hook once the streak hits the limit.
+    pub(crate) fn record_hook_result(&mut self, hook_id: &Address, succeeded: bool) {
+        let max_failures = self.max_hook_failures;
+        let Some(hook) = self.transfer_hooks.get(hook_id) else {
+            return;
+        };
+        if succeeded && hook.consecutive_failures == 0 {
+            return;
+        }
+
+        let hook = self
+            .transfer_hooks
+            .get_mut(hook_id)
+            .expect("The hook was just read");
+        if succeeded {
+            hook.consecutive_failures = 0;
+            return;
+        }
+        hook.consecutive_failures += 1;
+        if hook.consecutive_failures >= max_failures {
+            hook.disabled = true;
+            FtEvent::TransferHookDisabled {
+                hook_id: *hook_id,
+                failures: hook.consecutive_failures,
+            }
+            .emit();
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// Inline icons are stored in the metadata, so they are kept small. Larger icons go behind a
+/// reference.
+pub(crate) const MAX_INLINE_ICON_LEN: usize = 4 * 1024;
+const MAX_ICON_URL_LEN: usize = 512;
+
+/// An icon hosted elsewhere. Wallets should show it only if its SHA-256 matches `sha256`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct IconReference {
+    url: String,
+    sha256: String,
+}
+
+impl IconReference {
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) fn sha256(&self) -> &str {
+        &self.sha256
+    }
+
+    pub(crate) fn assert_valid(&self) {
+        assert!(!self.url.trim().is_empty(), "The icon URL can't be empty");
+        assert!(
+            self.url.len() <= MAX_ICON_URL_LEN,
+            "The icon URL is too long"
+        );
+        let hash = hex::decode(&self.sha256).expect("The icon hash should be hex");
+        assert_eq!(hash.len(), 32, "The icon hash should be a SHA-256 hash");
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `sha256` is the hex-encoded hash of the icon's content.
+    pub fn set_icon_reference(url: String, sha256: String) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let reference = IconReference {
+            url,
+            sha256: sha256.to_lowercase(),
+        };
+        reference.assert_valid();
+        contract.metadata.icon_reference = Some(reference);
+
+        contract.save();
+    }
+
+    pub fn clear_icon_reference() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.metadata.icon_reference = None;
+
+        contract.save();
+    }
+}
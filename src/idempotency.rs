@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 64;
+const MAX_IDEMPOTENCY_KEYS_PER_ACCOUNT: usize = 256;
+/// One day, in seconds.
+pub(crate) const DEFAULT_IDEMPOTENCY_TTL: u64 = 86_400;
+
+pub(crate) type IdempotencyKeys = BTreeMap<String, IdempotencyRecord>;
+
+/// The transfer an idempotency key was first used for.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    sender_id: Address,
+    recipient_id: Address,
+    amount: U128,
+    used_at: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// How long a used key is remembered. Defaults to `DEFAULT_IDEMPOTENCY_TTL`.
+    pub fn set_idempotency_ttl(ttl: U64) {
+        assert_ne!(ttl.0, 0, "TTL should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.idempotency_ttl = ttl.0;
+
+        contract.save();
+    }
+
+    pub fn idempotency_ttl() -> U64 {
+        let contract = Self::load();
+        contract.idempotency_ttl.into()
+    }
+
+    pub fn idempotency_record(
+        account_id: Address,
+        idempotency_key: String,
+    ) -> Option<IdempotencyRecord> {
+        let contract = Self::load();
+        let record = contract
+            .idempotency_keys
+            .get(&account_id)?
+            .get(&idempotency_key)?
+            .clone();
+        (!contract.is_idempotency_record_expired(&record)).then_some(record)
+    }
+
+    /// Records `idempotency_key` for a transfer submitted by `caller_id`. Returns `true` if the
+    /// same transfer was already submitted with it, in which case the caller should do nothing.
+    pub(crate) fn replayed_idempotency_key(
+        &mut self,
+        caller_id: &Address,
+        idempotency_key: Option<String>,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> bool {
+        let Some(idempotency_key) = idempotency_key else {
+            return false;
+        };
+        assert!(
+            !idempotency_key.is_empty(),
+            "Idempotency key should not be empty"
+        );
+        assert!(
+            idempotency_key.len() <= MAX_IDEMPOTENCY_KEY_LEN,
+            "Idempotency key is longer than {MAX_IDEMPOTENCY_KEY_LEN} bytes"
+        );
+
+        let record = IdempotencyRecord {
+            sender_id: *sender_id,
+            recipient_id: *recipient_id,
+            amount: amount.into(),
+            used_at: env::block_timestamp().into(),
+        };
+        let mut keys = self
+            .idempotency_keys
+            .get(caller_id)
+            .cloned()
+            .unwrap_or_default();
+        keys.retain(|_, used| !self.is_idempotency_record_expired(used));
+        if let Some(used) = keys.get(&idempotency_key) {
+            assert!(
+                used.sender_id == record.sender_id
+                    && used.recipient_id == record.recipient_id
+                    && used.amount == record.amount,
+                "The idempotency key was used for a different transfer"
+            );
+            return true;
+        }
+        if keys.len() >= MAX_IDEMPOTENCY_KEYS_PER_ACCOUNT {
+            // The oldest key goes first, so a busy account never runs out of keys.
+            let oldest = keys
+                .iter()
+                .min_by_key(|(_, used)| used.used_at.0)
+                .map(|(key, _)| key.clone())
+                .expect("The keys are not empty");
+            keys.remove(&oldest);
+        }
+        keys.insert(idempotency_key, record);
+        self.idempotency_keys.insert(*caller_id, keys);
+        false
+    }
+
+    pub(crate) fn prune_idempotency_keys(&mut self, account_id: &Address) -> u32 {
+        let Some(keys) = self.idempotency_keys.get(account_id) else {
+            return 0;
+        };
+        let mut keys = keys.clone();
+        let before = keys.len();
+        keys.retain(|_, used| !self.is_idempotency_record_expired(used));
+        let pruned = (before - keys.len()) as u32;
+        if pruned == 0 {
+            return 0;
+        }
+
+        if keys.is_empty() {
+            self.idempotency_keys.remove(*account_id);
+        } else {
+            self.idempotency_keys.insert(*account_id, keys);
+        }
+        pruned
+    }
+
+    fn is_idempotency_record_expired(&self, record: &IdempotencyRecord) -> bool {
+        env::block_timestamp() >= record.used_at.0.saturating_add(self.idempotency_ttl.into())
+    }
+}
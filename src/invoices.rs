@@ -0,0 +1,323 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::{decimals, L1xFtErc20, TransferKind};
+
+const MAX_INVOICE_MEMO_LEN: usize = 256;
+const MAX_OPEN_INVOICES_PER_PAYER: usize = 32;
+/// Open invoices one issuer can have against the same payer, so no single issuer can take up
+/// all of the payer's slots.
+const MAX_OPEN_INVOICES_PER_ISSUER: usize = 4;
+
+pub(crate) type InvoiceIds = BTreeSet<u64>;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum InvoiceStatus {
+    Open,
+    Paid,
+    Cancelled,
+}
+
+/// A bill from `issuer_id` to `payer_id` that can be paid in several parts.
+///
+/// A payment held by the payer's withdrawal protection counts as `pending` until the transfer is
+/// finalized or cancelled.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct Invoice {
+    issuer_id: Address,
+    payer_id: Address,
+    total: U128,
+    paid: U128,
+    pending: U128,
+    due_date: U128,
+    memo: String,
+    status: InvoiceStatus,
+    /// The token decimals the amounts are in. Invoices are rescaled when read, so a decimals
+    /// migration doesn't have to wait for them to close.
+    decimals: u8,
+}
+
+impl Invoice {
+    fn rescale_to(&mut self, decimals: u8) {
+        let rescale = |amount: U128| decimals::rescale(self.decimals, decimals, amount.0).into();
+        self.total = rescale(self.total);
+        self.paid = rescale(self.paid);
+        self.pending = rescale(self.pending);
+        self.decimals = decimals;
+    }
+
+    fn outstanding(&self) -> u128 {
+        self.total.0 - self.paid.0
+    }
+
+    fn payable(&self) -> u128 {
+        self.outstanding() - self.pending.0
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Bills `payer_id` for `total`, payable to the caller. The caller pays the invoice fee to
+    /// the treasury.
+    pub fn create_invoice(payer_id: AccountId, total: U128, due_date: U128, memo: String) -> U64 {
+        assert_ne!(total.0, 0, "Total should be greater than 0");
+        assert!(
+            memo.len() <= MAX_INVOICE_MEMO_LEN,
+            "Memo is longer than {MAX_INVOICE_MEMO_LEN} bytes"
+        );
+        assert!(
+            due_date.0 > env::block_timestamp(),
+            "The due date should be in the future"
+        );
+        let mut contract = Self::load();
+        let issuer_id = caller_address();
+        assert_ne!(issuer_id, *payer_id, "Issuer and payer cannot be the same");
+        contract.charge_invoice_fee(&issuer_id);
+
+        let invoice_id = contract.next_invoice_id;
+        contract.next_invoice_id = invoice_id.checked_add(1).expect("Invoice id overflowed");
+        contract.invoices.insert(
+            invoice_id,
+            Invoice {
+                issuer_id,
+                payer_id: *payer_id,
+                total,
+                paid: 0.into(),
+                pending: 0.into(),
+                due_date,
+                memo,
+                status: InvoiceStatus::Open,
+                decimals: contract.metadata.decimals,
+            },
+        );
+        let mut invoice_ids = contract
+            .open_invoices_by_payer
+            .get(&payer_id)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            invoice_ids.len() < MAX_OPEN_INVOICES_PER_PAYER,
+            "The payer has too many open invoices"
+        );
+        let issued = invoice_ids
+            .iter()
+            .filter_map(|invoice_id| contract.invoices.get(invoice_id))
+            .filter(|invoice| invoice.issuer_id == issuer_id)
+            .count();
+        assert!(
+            issued < MAX_OPEN_INVOICES_PER_ISSUER,
+            "Too many open invoices to this payer"
+        );
+        invoice_ids.insert(invoice_id);
+        contract
+            .open_invoices_by_payer
+            .insert(*payer_id, invoice_ids);
+        FtEvent::InvoiceCreated {
+            invoice_id,
+            issuer_id,
+            payer_id: *payer_id,
+            total: total.0,
+        }
+        .emit();
+
+        contract.save();
+        invoice_id.into()
+    }
+
+    /// Pays part or all of what is left on an open invoice. Overdue invoices can still be paid.
+    /// A payment held by the payer's withdrawal protection only counts once it is finalized.
+    pub fn pay_invoice(invoice_id: U64, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let payer_id = caller_address();
+
+        let mut invoice = contract.open_invoice(invoice_id.0);
+        assert_eq!(
+            payer_id, invoice.payer_id,
+            "Only the payer can pay an invoice"
+        );
+        assert!(
+            amount.0 <= invoice.payable(),
+            "Amount exceeds the outstanding balance"
+        );
+
+        let kind = TransferKind::InvoicePayment { invoice_id };
+        match contract.transfer_or_hold_as(&payer_id, &invoice.issuer_id, amount.0, kind) {
+            Some(_) => {
+                invoice.pending = (invoice.pending.0 + amount.0).into();
+                contract.invoices.insert(invoice_id.0, invoice);
+            }
+            None => {
+                contract.invoices.insert(invoice_id.0, invoice);
+                contract.settle_invoice_payment(invoice_id.0, amount.0, false);
+            }
+        }
+
+        contract.save();
+    }
+
+    /// Closes an open invoice. The issuer can cancel it, and the payer can decline it. Partial
+    /// payments already made are not returned.
+    pub fn cancel_invoice(invoice_id: U64) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let mut invoice = contract.open_invoice(invoice_id.0);
+        assert!(
+            caller_id == invoice.issuer_id || caller_id == invoice.payer_id,
+            "Only the issuer or the payer can cancel an invoice"
+        );
+
+        invoice.status = InvoiceStatus::Cancelled;
+        contract.close_invoice(invoice_id.0, &invoice.payer_id);
+        contract.invoices.insert(invoice_id.0, invoice);
+        FtEvent::InvoiceCancelled {
+            invoice_id: invoice_id.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn invoice(invoice_id: U64) -> Option<Invoice> {
+        let contract = Self::load();
+        contract.invoice_entry(invoice_id.0)
+    }
+
+    pub fn open_invoices_of(payer_id: Address) -> Vec<(U64, Invoice)> {
+        let contract = Self::load();
+        contract
+            .open_invoices_by_payer
+            .get(&payer_id)
+            .map(|invoice_ids| {
+                invoice_ids
+                    .iter()
+                    .filter_map(|invoice_id| {
+                        let invoice = contract.invoice_entry(*invoice_id)?;
+                        Some(((*invoice_id).into(), invoice))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// What `payer_id` still owes across their open invoices.
+    pub fn outstanding_balance_of(payer_id: Address) -> U128 {
+        let contract = Self::load();
+        contract
+            .open_invoices_by_payer
+            .get(&payer_id)
+            .map(|invoice_ids| {
+                invoice_ids
+                    .iter()
+                    .filter_map(|invoice_id| contract.invoice_entry(*invoice_id))
+                    .map(|invoice| invoice.outstanding())
+                    .sum::<u128>()
+            })
+            .unwrap_or_default()
+            .into()
+    }
+
+    pub fn set_invoice_fee(fee: U128) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.invoice_fee = fee.0;
+
+        contract.save();
+    }
+
+    pub fn invoice_fee() -> U128 {
+        let contract = Self::load();
+        contract.invoice_fee.into()
+    }
+
+    fn charge_invoice_fee(&mut self, issuer_id: &Address) {
+        let fee = self.invoice_fee;
+        if fee == 0 {
+            return;
+        }
+        let balance = self.balance_of(issuer_id).unwrap_or_default();
+        assert!(
+            self.spendable_balance(issuer_id) >= fee,
+            "Not enough balance to pay the invoice fee"
+        );
+        self.set_balance(issuer_id, balance - fee);
+        self.treasury_credit(fee);
+    }
+
+    /// The invoice with its amounts in the current decimals.
+    fn invoice_entry(&self, invoice_id: u64) -> Option<Invoice> {
+        let mut invoice = self.invoices.get(&invoice_id).cloned()?;
+        invoice.rescale_to(self.metadata.decimals);
+        Some(invoice)
+    }
+
+    fn open_invoice(&self, invoice_id: u64) -> Invoice {
+        let invoice = self.invoice_entry(invoice_id).expect("Invoice not found");
+        assert!(
+            invoice.status == InvoiceStatus::Open,
+            "The invoice is not open"
+        );
+        invoice
+    }
+
+    /// Credits a settled payment, from `pending` if it was held first. The invoice is paid once
+    /// nothing is left outstanding.
+    pub(crate) fn settle_invoice_payment(
+        &mut self,
+        invoice_id: u64,
+        amount: u128,
+        was_pending: bool,
+    ) {
+        let mut invoice = self.invoice_entry(invoice_id).expect("Invoice not found");
+        if was_pending {
+            invoice.pending = (invoice.pending.0 - amount).into();
+        }
+        invoice.paid = (invoice.paid.0 + amount).into();
+        let outstanding = invoice.outstanding();
+        if outstanding == 0 && invoice.status == InvoiceStatus::Open {
+            invoice.status = InvoiceStatus::Paid;
+            self.close_invoice(invoice_id, &invoice.payer_id);
+        }
+        self.invoices.insert(invoice_id, invoice);
+        FtEvent::InvoicePaid {
+            invoice_id,
+            amount,
+            outstanding,
+        }
+        .emit();
+    }
+
+    /// Drops a held payment that was cancelled.
+    pub(crate) fn release_invoice_payment(&mut self, invoice_id: u64, amount: u128) {
+        let mut invoice = self.invoice_entry(invoice_id).expect("Invoice not found");
+        invoice.pending = (invoice.pending.0 - amount).into();
+        self.invoices.insert(invoice_id, invoice);
+    }
+
+    fn close_invoice(&mut self, invoice_id: u64, payer_id: &Address) {
+        let mut invoice_ids = self
+            .open_invoices_by_payer
+            .get(payer_id)
+            .cloned()
+            .unwrap_or_default();
+        invoice_ids.remove(&invoice_id);
+        if invoice_ids.is_empty() {
+            self.open_invoices_by_payer.remove(*payer_id);
+        } else {
+            self.open_invoices_by_payer.insert(*payer_id, invoice_ids);
+        }
+    }
+}
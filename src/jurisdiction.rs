@@ -0,0 +1,218 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::roles::Role;
+use crate::L1xFtErc20;
+
+/// Per-country transfer rules. Accounts are tagged with a numeric jurisdiction code by registrars.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct JurisdictionRules {
+    enabled: bool,
+    blocked_pairs: BTreeSet<(u16, u16)>,
+    holding_limits: BTreeMap<u16, u128>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JurisdictionRulesView {
+    enabled: bool,
+    registrars: Vec<Address>,
+    blocked_pairs: Vec<(u16, u16)>,
+    holding_limits: Vec<(u16, U128)>,
+}
+
+impl JurisdictionRules {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        for limit in self.holding_limits.values_mut() {
+            *limit = rescale(*limit);
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// When enabled, both sides of a transfer need a registered jurisdiction.
+    pub fn set_jurisdiction_rules_enabled(enabled: bool) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.jurisdiction_rules.enabled = enabled;
+
+        contract.save();
+    }
+
+    pub fn add_jurisdiction_registrar(registrar_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.grant_role(Role::JurisdictionRegistrar, registrar_id),
+            "This address is already a registrar"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_jurisdiction_registrar(registrar_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.revoke_role(Role::JurisdictionRegistrar, &registrar_id),
+            "This address is not a registrar"
+        );
+
+        contract.save();
+    }
+
+    /// Callable by the owner or a registrar. `None` clears the account's jurisdiction.
+    pub fn set_jurisdiction(account_id: Address, code: Option<u16>) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+        assert!(
+            caller_id == contract_owner_address()
+                || contract.has_role(Role::JurisdictionRegistrar, &caller_id),
+            "Only the owner or a registrar can set jurisdictions"
+        );
+
+        match code {
+            Some(code) => {
+                contract.jurisdictions.insert(account_id, code);
+            }
+            None => {
+                contract.jurisdictions.remove(account_id);
+            }
+        }
+
+        contract.save();
+    }
+
+    pub fn jurisdiction_of(account_id: Address) -> Option<u16> {
+        let contract = Self::load();
+        contract.jurisdictions.get(&account_id).copied()
+    }
+
+    pub fn set_jurisdiction_pair_blocked(from: u16, to: u16, blocked: bool) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        if blocked {
+            contract.jurisdiction_rules.blocked_pairs.insert((from, to));
+        } else {
+            contract
+                .jurisdiction_rules
+                .blocked_pairs
+                .remove(&(from, to));
+        }
+
+        contract.save();
+    }
+
+    /// Caps the balance of every account in the jurisdiction. `None` removes the cap.
+    pub fn set_jurisdiction_holding_limit(code: u16, limit: Option<U128>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        match limit {
+            Some(limit) => {
+                contract
+                    .jurisdiction_rules
+                    .holding_limits
+                    .insert(code, limit.0);
+            }
+            None => {
+                contract.jurisdiction_rules.holding_limits.remove(&code);
+            }
+        }
+
+        contract.save();
+    }
+
+    pub fn jurisdiction_rules() -> JurisdictionRulesView {
+        let contract = Self::load();
+        let registrars = contract
+            .roles
+            .get(&Role::JurisdictionRegistrar)
+            .map(|registrars| registrars.iter().copied().collect())
+            .unwrap_or_default();
+        let rules = contract.jurisdiction_rules.into_inner();
+
+        JurisdictionRulesView {
+            enabled: rules.enabled,
+            registrars,
+            blocked_pairs: rules.blocked_pairs.into_iter().collect(),
+            holding_limits: rules
+                .holding_limits
+                .into_iter()
+                .map(|(code, limit)| (code, limit.into()))
+                .collect(),
+        }
+    }
+
+    /// Explains why the jurisdiction rules would reject the transfer, if they would.
+    pub fn jurisdiction_restriction(
+        sender_id: Address,
+        recipient_id: Address,
+        amount: U128,
+    ) -> Option<String> {
+        let contract = Self::load();
+        contract.jurisdiction_violation(&sender_id, &recipient_id, amount.0)
+    }
+
+    pub(crate) fn jurisdiction_violation(
+        &self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> Option<String> {
+        let rules = &self.jurisdiction_rules;
+        if !rules.enabled {
+            return None;
+        }
+
+        let Some(&from) = self.jurisdictions.get(sender_id) else {
+            return Some(format!("'{}' has no registered jurisdiction", sender_id));
+        };
+        let Some(&to) = self.jurisdictions.get(recipient_id) else {
+            return Some(format!("'{}' has no registered jurisdiction", recipient_id));
+        };
+        if rules.blocked_pairs.contains(&(from, to)) {
+            return Some(format!(
+                "Transfers from jurisdiction {} to jurisdiction {} are blocked",
+                from, to
+            ));
+        }
+        if let Some(&limit) = rules.holding_limits.get(&to) {
+            let balance = self.balance_of(recipient_id).unwrap_or_default();
+            if balance.saturating_add(amount) > limit {
+                return Some(format!(
+                    "'{}' would exceed the holding limit of jurisdiction {}",
+                    recipient_id, to
+                ));
+            }
+        }
+        None
+    }
+}
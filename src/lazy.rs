@@ -0,0 +1,172 @@
+use std::cell::{OnceCell, RefCell};
+use std::collections::BTreeMap;
+use std::io;
+use std::ops::{Deref, DerefMut};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{env, L1xFtErc20};
+
+thread_local! {
+    /// The token states of the running call scope, by namespace.
+    static SCOPED_STATES: RefCell<Option<BTreeMap<Vec<u8>, ScopedState>>> = RefCell::new(None);
+}
+
+enum ScopedState {
+    /// Loaded by a nested call that hasn't handed it back yet. `modified` is set once an
+    /// earlier call of the scope saved changes to it.
+    CheckedOut {
+        modified: bool,
+    },
+    Saved(L1xFtErc20),
+}
+
+/// Runs `f` as one call scope. The entrypoints it calls share one copy of each token state: it is
+/// loaded by the first of them, handed from one to the next in memory, and written to storage once,
+/// when the outermost scope ends.
+pub(crate) fn call_scope<R>(f: impl FnOnce() -> R) -> R {
+    struct Scope;
+
+    impl Drop for Scope {
+        fn drop(&mut self) {
+            SCOPED_STATES.with(|states| states.take());
+        }
+    }
+
+    let outermost = SCOPED_STATES.with(|states| {
+        let mut states = states.borrow_mut();
+        let outermost = states.is_none();
+        states.get_or_insert_with(BTreeMap::new);
+        outermost
+    });
+    if !outermost {
+        return f();
+    }
+
+    let _scope = Scope;
+    let result = f();
+    let states = SCOPED_STATES
+        .with(|states| states.take())
+        .unwrap_or_default();
+    for (namespace, state) in states {
+        match state {
+            ScopedState::Saved(mut contract) => contract.write(&namespace),
+            ScopedState::CheckedOut { modified } => {
+                assert!(!modified, "The contract state was loaded and never saved")
+            }
+        }
+    }
+    result
+}
+
+/// Takes the state of `namespace` from the running call scope, calling `load` on its first use.
+/// Outside a scope it just calls `load`.
+pub(crate) fn check_out(namespace: &[u8], load: impl FnOnce() -> L1xFtErc20) -> L1xFtErc20 {
+    let scoped = SCOPED_STATES.with(|states| {
+        let mut states = states.borrow_mut();
+        let states = states.as_mut()?;
+        let state = states
+            .entry(namespace.to_vec())
+            .or_insert(ScopedState::CheckedOut { modified: false });
+        match std::mem::replace(state, ScopedState::CheckedOut { modified: true }) {
+            ScopedState::Saved(contract) => Some(contract),
+            ScopedState::CheckedOut { modified } => {
+                *state = ScopedState::CheckedOut { modified };
+                assert!(!modified, "The contract state is already loaded");
+                None
+            }
+        }
+    });
+    scoped.unwrap_or_else(load)
+}
+
+/// Hands the state of `namespace` back to the running call scope. Outside a scope it is returned
+/// for the caller to write.
+pub(crate) fn check_in(namespace: &[u8], contract: L1xFtErc20) -> Option<L1xFtErc20> {
+    SCOPED_STATES.with(|states| match states.borrow_mut().as_mut() {
+        Some(states) => {
+            states.insert(namespace.to_vec(), ScopedState::Saved(contract));
+            None
+        }
+        None => Some(contract),
+    })
+}
+
+/// A part of the contract state stored under its own key instead of inside the main struct.
+///
+/// It is read from storage on first access, or starts out as the default if it was never written,
+/// and is cached for the rest of the call. A mutable borrow
+/// marks it dirty and `flush` writes back dirty sections only, so calls that merely read a section
+/// don't pay for serializing it, and calls that never touch it don't pay for deserializing it.
+pub(crate) struct LazySection<T> {
+    key: Vec<u8>,
+    value: OnceCell<T>,
+    dirty: bool,
+}
+
+impl<T: BorshSerialize + BorshDeserialize + Default> LazySection<T> {
+    pub(crate) fn new(key: Vec<u8>, value: T) -> Self {
+        Self {
+            key,
+            value: OnceCell::from(value),
+            dirty: true,
+        }
+    }
+
+    pub(crate) fn into_inner(self) -> T {
+        self.get();
+        self.value.into_inner().unwrap()
+    }
+
+    /// Writes the section back if it was modified since the last flush.
+    pub(crate) fn flush(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Some(value) = self.value.get() {
+            env::storage_write(&self.key, &value.try_to_vec().unwrap());
+        }
+        self.dirty = false;
+    }
+
+    fn get(&self) -> &T {
+        self.value
+            .get_or_init(|| match env::storage_read(&self.key) {
+                Some(bytes) => T::try_from_slice(&bytes).unwrap(),
+                None => T::default(),
+            })
+    }
+}
+
+impl<T: BorshSerialize + BorshDeserialize + Default> Deref for LazySection<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T: BorshSerialize + BorshDeserialize + Default> DerefMut for LazySection<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.get();
+        self.dirty = true;
+        self.value.get_mut().unwrap()
+    }
+}
+
+/// Only the key is part of the main struct.
+impl<T> BorshSerialize for LazySection<T> {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.key.serialize(writer)
+    }
+}
+
+impl<T> BorshDeserialize for LazySection<T> {
+    fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        Ok(Self {
+            key: Vec::deserialize(buf)?,
+            value: OnceCell::new(),
+            dirty: false,
+        })
+    }
+}
@@ -1,21 +1,261 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use l1x_sdk::store::LookupMap;
-use l1x_sdk::types::{Address, U128};
-use l1x_sdk::{caller_address, contract, contract_owner_address};
+use l1x_sdk::contract;
+use l1x_sdk::store::{LookupMap, Vector};
+use l1x_sdk::types::{Address, U128, U64};
 use serde::{Deserialize, Serialize};
 
+mod account_id;
+mod alerts;
+mod approvals;
+mod audit;
+mod backup;
+mod balance_root;
+mod budgets;
+mod checkpoint;
+mod claim_codes;
+#[cfg(feature = "compliance")]
+mod clawback;
+mod cleanup;
+mod closure;
+#[cfg(feature = "contributors")]
+mod contributors;
+mod cross_contract;
+mod decimals;
+#[cfg(feature = "staking")]
+mod delegation;
+mod distribution;
+mod dust;
+mod env;
+mod events;
+#[cfg(feature = "factory")]
+mod factory;
+mod fee_routes;
+mod forwarder;
+mod freeze;
+#[cfg(feature = "holds")]
+mod holds;
+mod hooks;
+mod icon;
+mod idempotency;
+#[cfg(feature = "invoices")]
+mod invoices;
+#[cfg(feature = "compliance")]
+mod jurisdiction;
+mod lazy;
+mod limits;
+mod locales;
+#[cfg(feature = "locks")]
+mod lock;
+mod memo;
+mod mint_breaker;
+#[cfg(feature = "multi_token")]
+mod multi_token;
+mod multicall;
+mod multiquery;
+mod multisend;
+#[cfg(feature = "names")]
+mod names;
+mod namespace;
+mod pair_policies;
+mod pause;
+#[cfg(feature = "reflection")]
+mod reflection;
+mod refunds;
+mod rescue;
+mod reserve;
+mod roles;
+mod royalties;
+mod rules;
+#[cfg(feature = "compliance")]
+mod sanctions;
+mod settlement;
+mod spending_keys;
+#[cfg(feature = "staking")]
+mod staking;
+#[cfg(feature = "tax")]
+mod tax;
+#[cfg(test)]
+mod tests;
+mod timelock;
+mod transfer_burn;
+mod treasury;
+mod volume_breaker;
+mod wallet_metadata;
+mod withdrawal_delay;
+mod wrapped_native;
+#[cfg(feature = "wrapper")]
+mod wrapper;
+
+use account_id::AccountId;
+use alerts::AlertThresholds;
+use approvals::{SpenderRegistry, TrustedSpenders};
+use backup::BackupRecord;
+use balance_root::{BalancesRoot, NodeHash};
+use budgets::SpendingBudget;
+use claim_codes::ClaimCode;
+#[cfg(feature = "compliance")]
+use clawback::Clawback;
+use cleanup::CleanupRewards;
+#[cfg(feature = "contributors")]
+use contributors::{Contributor, EpochSchedule};
+use decimals::DecimalsMigration;
+#[cfg(feature = "staking")]
+use delegation::{Delegation, Operator};
+use distribution::DistributionJob;
+use dust::DustPolicy;
+use env::{caller_address, contract_owner_address};
+use events::FtEvent;
+use fee_routes::FeeRoute;
+#[cfg(feature = "holds")]
+use holds::{Hold, HoldIds};
+use hooks::TransferHook;
+use icon::IconReference;
+use idempotency::{IdempotencyKeys, DEFAULT_IDEMPOTENCY_TTL};
+#[cfg(feature = "invoices")]
+use invoices::{Invoice, InvoiceIds};
+#[cfg(feature = "compliance")]
+use jurisdiction::JurisdictionRules;
+use lazy::LazySection;
+use limits::TransferLimits;
+use locales::LocalizedMetadata;
+#[cfg(feature = "locks")]
+use lock::{FTLocks, LockPenaltyConfig};
+use mint_breaker::MintBreaker;
+#[cfg(feature = "multi_token")]
+use multi_token::SubToken;
+#[cfg(feature = "names")]
+use names::{NameRecord, NameRegistryConfig};
+use pair_policies::{PairKey, StampedPairPolicy};
+#[cfg(feature = "reflection")]
+use reflection::ReflectionState;
+use refunds::TransferRecord;
+use roles::Role;
+use royalties::Royalty;
+use rules::TransferRuleEntry;
+#[cfg(feature = "compliance")]
+use sanctions::{SanctionsCacheEntry, SanctionsConfig};
+use settlement::{PreparedTransfer, SettlementConfig, SettlementIds};
+use spending_keys::SpendingKeys;
+#[cfg(feature = "staking")]
+use staking::{SlashRecord, StakeInfo, StakingConfig, StakingPool};
+#[cfg(feature = "tax")]
+use tax::TaxConfig;
+use timelock::TimelockEntry;
+use transfer_burn::TransferBurnConfig;
+use volume_breaker::VolumeBreaker;
+use withdrawal_delay::{PendingTransfers, WithdrawalProtection};
+
 const STORAGE_CONTRACT_KEY: &[u8; 6] = b"l1x-ft";
 const STORAGE_BALANCES_KEY: &[u8; 8] = b"balances";
 const STORAGE_ALLOWANCES_KEY: &[u8; 9] = b"allowance";
+#[cfg(feature = "locks")]
+const STORAGE_LOCKS_KEY: &[u8; 5] = b"locks";
+const STORAGE_TIMELOCK_KEY: &[u8; 8] = b"timelock";
+const STORAGE_LAST_TRANSFER_KEY: &[u8; 13] = b"last_transfer";
+const STORAGE_WITHDRAWAL_PROTECTION_KEY: &[u8; 21] = b"withdrawal_protection";
+const STORAGE_PENDING_TRANSFERS_KEY: &[u8; 17] = b"pending_transfers";
+const STORAGE_BACKUPS_KEY: &[u8; 7] = b"backups";
+const STORAGE_SPENDING_KEYS_KEY: &[u8; 13] = b"spending_keys";
+const STORAGE_CLAIM_CODES_KEY: &[u8; 11] = b"claim_codes";
+const STORAGE_CLAIM_COMMITMENTS_KEY: &[u8; 17] = b"claim_commitments";
+#[cfg(feature = "multi_token")]
+const STORAGE_SUB_TOKENS_KEY: &[u8; 10] = b"sub_tokens";
+#[cfg(feature = "multi_token")]
+const STORAGE_SUB_BALANCES_KEY: &[u8; 12] = b"sub_balances";
+const STORAGE_MEMO_REQUIRED_KEY: &[u8; 13] = b"memo_required";
+#[cfg(feature = "holds")]
+const STORAGE_HOLDS_KEY: &[u8; 5] = b"holds";
+#[cfg(feature = "holds")]
+const STORAGE_HOLDS_BY_PAYER_KEY: &[u8; 14] = b"holds_by_payer";
+const STORAGE_FROZEN_KEY: &[u8; 6] = b"frozen";
+#[cfg(feature = "compliance")]
+const STORAGE_SANCTIONS_CACHE_KEY: &[u8; 15] = b"sanctions_cache";
+#[cfg(feature = "compliance")]
+const STORAGE_JURISDICTIONS_KEY: &[u8; 13] = b"jurisdictions";
+#[cfg(feature = "compliance")]
+const STORAGE_CLAWBACKS_KEY: &[u8; 9] = b"clawbacks";
+const STORAGE_WHITELIST_KEY: &[u8; 9] = b"whitelist";
+const STORAGE_TRUSTED_SPENDERS_KEY: &[u8; 16] = b"trusted_spenders";
+const STORAGE_DISTRIBUTIONS_KEY: &[u8; 13] = b"distributions";
+const STORAGE_DISTRIBUTION_ITEMS_KEY: &[u8; 18] = b"distribution_items";
+#[cfg(feature = "contributors")]
+const STORAGE_CONTRIBUTORS_KEY: &[u8; 12] = b"contributors";
+#[cfg(feature = "staking")]
+const STORAGE_STAKES_KEY: &[u8; 6] = b"stakes";
+#[cfg(feature = "staking")]
+const STORAGE_SLASH_HISTORY_KEY: &[u8; 13] = b"slash_history";
+#[cfg(feature = "staking")]
+const STORAGE_OPERATORS_KEY: &[u8; 9] = b"operators";
+#[cfg(feature = "staking")]
+const STORAGE_DELEGATIONS_KEY: &[u8; 11] = b"delegations";
+#[cfg(feature = "staking")]
+const STORAGE_DELEGATION_COUNTS_KEY: &[u8; 17] = b"delegation_counts";
+const STORAGE_ACCOUNTS_KEY: &[u8; 8] = b"accounts";
+const STORAGE_INDEXED_ACCOUNTS_KEY: &[u8; 16] = b"indexed_accounts";
+const STORAGE_MERKLE_NODES_KEY: &[u8; 12] = b"merkle_nodes";
+const STORAGE_MERKLE_DIRTY_KEY: &[u8; 12] = b"merkle_dirty";
+const STORAGE_MERKLE_DIRTY_FLAGS_KEY: &[u8; 18] = b"merkle_dirty_flags";
+const STORAGE_MERKLE_LEAF_BALANCES_KEY: &[u8; 20] = b"merkle_leaf_balances";
+const STORAGE_BALANCE_CHANGES_KEY: &[u8; 15] = b"balance_changes";
+const STORAGE_ROLES_KEY: &[u8; 5] = b"roles";
+const STORAGE_METADATA_KEY: &[u8; 8] = b"metadata";
+#[cfg(feature = "staking")]
+const STORAGE_STAKING_CONFIG_KEY: &[u8; 14] = b"staking_config";
+#[cfg(feature = "compliance")]
+const STORAGE_JURISDICTION_RULES_KEY: &[u8; 18] = b"jurisdiction_rules";
+const STORAGE_SPENDER_REGISTRY_KEY: &[u8; 16] = b"spender_registry";
+const STORAGE_MINT_BREAKER_KEY: &[u8; 12] = b"mint_breaker";
+const STORAGE_TOTAL_SUPPLY_KEY: &[u8; 12] = b"total_supply";
+const STORAGE_LOCALIZED_METADATA_KEY: &[u8; 18] = b"localized_metadata";
+const STORAGE_APPROVALS_RECEIVED_KEY: &[u8; 18] = b"approvals_received";
+const STORAGE_APPROVALS_RECEIVED_COUNT_KEY: &[u8; 24] = b"approvals_received_count";
+const STORAGE_APPROVAL_POSITIONS_KEY: &[u8; 18] = b"approval_positions";
+const STORAGE_TRANSFER_HOOKS_KEY: &[u8; 14] = b"transfer_hooks";
+const STORAGE_PREPARED_TRANSFERS_KEY: &[u8; 18] = b"prepared_transfers";
+const STORAGE_SETTLEMENTS_BY_SENDER_KEY: &[u8; 21] = b"settlements_by_sender";
+const STORAGE_IDEMPOTENCY_KEYS_KEY: &[u8; 16] = b"idempotency_keys";
+#[cfg(feature = "invoices")]
+const STORAGE_INVOICES_KEY: &[u8; 8] = b"invoices";
+#[cfg(feature = "invoices")]
+const STORAGE_OPEN_INVOICES_BY_PAYER_KEY: &[u8; 22] = b"open_invoices_by_payer";
+const STORAGE_TRANSFER_RECORDS_KEY: &[u8; 16] = b"transfer_records";
+const STORAGE_ROYALTIES_KEY: &[u8; 9] = b"royalties";
+const STORAGE_PAIR_POLICIES_KEY: &[u8; 13] = b"pair_policies";
+const STORAGE_SPENDING_BUDGETS_KEY: &[u8; 16] = b"spending_budgets";
+#[cfg(feature = "names")]
+const STORAGE_NAMES_KEY: &[u8; 5] = b"names";
+#[cfg(feature = "locks")]
+const STORAGE_LOCK_PENALTY_KEY: &[u8; 12] = b"lock_penalty";
+const STORAGE_TRANSFER_BURN_KEY: &[u8; 13] = b"transfer_burn";
+#[cfg(feature = "tax")]
+const STORAGE_TAX_KEY: &[u8; 3] = b"tax";
+#[cfg(feature = "reflection")]
+const STORAGE_REFLECTION_KEY: &[u8; 10] = b"reflection";
+const STORAGE_LIMITS_KEY: &[u8; 6] = b"limits";
+#[cfg(feature = "compliance")]
+const STORAGE_SANCTIONS_KEY: &[u8; 9] = b"sanctions";
+#[cfg(feature = "contributors")]
+const STORAGE_EPOCH_SCHEDULE_KEY: &[u8; 14] = b"epoch_schedule";
+const STORAGE_VOLUME_BREAKER_KEY: &[u8; 14] = b"volume_breaker";
+const STORAGE_ALERT_THRESHOLDS_KEY: &[u8; 16] = b"alert_thresholds";
+const STORAGE_SETTLEMENT_CONFIG_KEY: &[u8; 17] = b"settlement_config";
+const STORAGE_FEE_ROUTES_KEY: &[u8; 10] = b"fee_routes";
+const STORAGE_CLEANUP_REWARDS_KEY: &[u8; 15] = b"cleanup_rewards";
+const STORAGE_DUST_POLICY_KEY: &[u8; 11] = b"dust_policy";
+#[cfg(feature = "names")]
+const STORAGE_NAME_REGISTRY_KEY: &[u8; 13] = b"name_registry";
 
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
 pub struct FTMetadata {
     name: String,
     decimals: u8,
     symbol: String,
     icon: Option<String>,
+    icon_reference: Option<IconReference>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
@@ -40,7 +280,7 @@ impl FTAllowance {
                     .expect("amount overflowed")
             }
             None => {
-                self.spenders.insert(spender_id.clone(), amount);
+                self.spenders.insert(*spender_id, amount);
             }
         };
     }
@@ -67,42 +307,554 @@ enum AllowanceUpdateOp {
     Spend,
 }
 
+/// What a transfer is for. Refunds pay no fees and can't be refunded themselves. Claim payouts
+/// come out of an escrow the sender funded earlier, so the sender's dust policy, cooldown and
+/// withdrawal protection don't apply to them. Invoice payments pay no fees, so the issuer gets
+/// what the invoice is credited with.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub(crate) enum TransferKind {
+    Regular,
+    Refund {
+        original_event_nonce: U64,
+    },
+    ClaimPayout,
+    #[cfg_attr(not(feature = "invoices"), allow(dead_code))]
+    InvoicePayment {
+        invoice_id: U64,
+    },
+}
+
+impl TransferKind {
+    fn pays_fees(self) -> bool {
+        !matches!(
+            self,
+            TransferKind::Refund { .. } | TransferKind::InvoicePayment { .. }
+        )
+    }
+
+    fn is_refundable(self) -> bool {
+        !matches!(self, TransferKind::Refund { .. })
+    }
+
+    fn is_escrow_payout(self) -> bool {
+        self == TransferKind::ClaimPayout
+    }
+}
+
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct L1xFtErc20 {
-    metadata: FTMetadata,
+    metadata: LazySection<FTMetadata>,
     balances: LookupMap<Address, u128>,
     allowances: LookupMap<Address, FTAllowance>,
-    total_supply: u128,
-    authorized_callers: BTreeSet<Address>,
+    total_supply: LazySection<u128>,
+    #[cfg(feature = "locks")]
+    locks: LookupMap<Address, FTLocks>,
+    #[cfg(feature = "locks")]
+    next_lock_id: u64,
+    #[cfg(feature = "locks")]
+    lock_penalty: LazySection<LockPenaltyConfig>,
+    treasury_balance: u128,
+    timelock_delay: u64,
+    timelock_operations: LookupMap<u64, TimelockEntry>,
+    next_timelock_operation_id: u64,
+    transfer_burn: LazySection<TransferBurnConfig>,
+    #[cfg(feature = "tax")]
+    tax: LazySection<TaxConfig>,
+    #[cfg(feature = "reflection")]
+    reflection: LazySection<ReflectionState>,
+    limits: LazySection<TransferLimits>,
+    last_transfer_at: LookupMap<Address, u128>,
+    withdrawal_protection: LookupMap<Address, WithdrawalProtection>,
+    pending_transfers: LookupMap<Address, PendingTransfers>,
+    next_pending_transfer_id: u64,
+    backups: LookupMap<Address, BackupRecord>,
+    evacuation_delay: u64,
+    spending_keys: LookupMap<Address, SpendingKeys>,
+    claim_codes: LookupMap<[u8; 32], ClaimCode>,
+    claim_commitments: LookupMap<[u8; 32], u128>,
+    reserve_oracle: Option<Address>,
+    #[cfg(feature = "multi_token")]
+    sub_tokens: LookupMap<u64, SubToken>,
+    #[cfg(feature = "multi_token")]
+    sub_balances: LookupMap<(u64, Address), u128>,
+    wrapped_native: bool,
+    wrapped_native_reserve: u128,
+    underlying: Option<Address>,
+    memo_required: LookupMap<Address, bool>,
+    #[cfg(feature = "holds")]
+    holds: LookupMap<u64, Hold>,
+    #[cfg(feature = "holds")]
+    holds_by_payer: LookupMap<Address, HoldIds>,
+    #[cfg(feature = "holds")]
+    next_hold_id: u64,
+    frozen: LookupMap<Address, u128>,
+    #[cfg(feature = "compliance")]
+    sanctions: LazySection<Option<SanctionsConfig>>,
+    #[cfg(feature = "compliance")]
+    sanctions_cache: LookupMap<Address, SanctionsCacheEntry>,
+    #[cfg(feature = "compliance")]
+    jurisdictions: LookupMap<Address, u16>,
+    #[cfg(feature = "compliance")]
+    jurisdiction_rules: LazySection<JurisdictionRules>,
+    #[cfg(feature = "compliance")]
+    clawbacks: LookupMap<u64, Clawback>,
+    #[cfg(feature = "compliance")]
+    next_clawback_id: u64,
+    transfer_rules: Vec<TransferRuleEntry>,
+    whitelist: LookupMap<Address, bool>,
+    trusted_spenders: LookupMap<Address, TrustedSpenders>,
+    spender_registry: LazySection<SpenderRegistry>,
+    max_multisend_batch: u32,
+    distributions: LookupMap<u64, DistributionJob>,
+    distribution_items: LookupMap<(u64, u64), (Address, u128)>,
+    next_distribution_id: u64,
+    max_supply: Option<u128>,
+    #[cfg(feature = "contributors")]
+    epoch_schedule: LazySection<Option<EpochSchedule>>,
+    #[cfg(feature = "contributors")]
+    contributors: LookupMap<Address, Contributor>,
+    #[cfg(feature = "staking")]
+    staking_pool: StakingPool,
+    #[cfg(feature = "staking")]
+    stakes: LookupMap<Address, StakeInfo>,
+    #[cfg(feature = "staking")]
+    staking_config: LazySection<StakingConfig>,
+    #[cfg(feature = "staking")]
+    slash_history: LookupMap<Address, Vec<SlashRecord>>,
+    #[cfg(feature = "staking")]
+    operators: LookupMap<Address, Operator>,
+    #[cfg(feature = "staking")]
+    delegations: LookupMap<(Address, Address), Delegation>,
+    #[cfg(feature = "staking")]
+    delegation_counts: LookupMap<Address, u32>,
+    paused_until: Option<u128>,
+    mint_breaker: LazySection<MintBreaker>,
+    volume_breaker: LazySection<VolumeBreaker>,
+    alert_thresholds: LazySection<AlertThresholds>,
+    accounts: Vector<Address>,
+    indexed_accounts: LookupMap<Address, u32>,
+    state_sequence: u64,
+    merkle_nodes: LookupMap<(u8, u32), NodeHash>,
+    merkle_dirty: Vector<u32>,
+    merkle_dirty_flags: LookupMap<u32, bool>,
+    merkle_leaf_balances: LookupMap<u32, u128>,
+    balances_root: Option<BalancesRoot>,
+    merkle_rehash_cursor: Option<u32>,
+    event_nonce: u64,
+    holder_count: u64,
+    balance_changes: Vector<(u64, Address)>,
+    roles: LookupMap<Role, BTreeSet<Address>>,
+    fixed_supply: bool,
+    decimals_migration: Option<DecimalsMigration>,
+    localized_metadata: LookupMap<String, LocalizedMetadata>,
+    approvals_received: LookupMap<(Address, u32), Address>,
+    approvals_received_count: LookupMap<Address, u32>,
+    approval_positions: LookupMap<(Address, Address), u32>,
+    transfer_hooks: LookupMap<Address, TransferHook>,
+    max_hook_failures: u32,
+    settlement_config: LazySection<Option<SettlementConfig>>,
+    prepared_transfers: LookupMap<(Address, String), PreparedTransfer>,
+    settlements_by_sender: LookupMap<Address, SettlementIds>,
+    idempotency_keys: LookupMap<Address, IdempotencyKeys>,
+    idempotency_ttl: u64,
+    #[cfg(feature = "invoices")]
+    invoices: LookupMap<u64, Invoice>,
+    #[cfg(feature = "invoices")]
+    open_invoices_by_payer: LookupMap<Address, InvoiceIds>,
+    #[cfg(feature = "invoices")]
+    next_invoice_id: u64,
+    #[cfg(feature = "invoices")]
+    invoice_fee: u128,
+    transfer_records: LookupMap<u64, TransferRecord>,
+    fee_routes: LazySection<Vec<FeeRoute>>,
+    royalties: LookupMap<Address, Royalty>,
+    pair_policies: LookupMap<PairKey, StampedPairPolicy>,
+    spending_budgets: LookupMap<(Address, Address), SpendingBudget>,
+    cleanup_cursor: u32,
+    cleanup_rewards: LazySection<CleanupRewards>,
+    dust_policy: LazySection<Option<DustPolicy>>,
+    #[cfg(feature = "names")]
+    name_registry: LazySection<Option<NameRegistryConfig>>,
+    #[cfg(feature = "names")]
+    names: LookupMap<String, NameRecord>,
 }
 
 #[contract]
 impl L1xFtErc20 {
-    pub fn new(metadata: FTMetadata, account_ids: Vec<Address>, amounts: Vec<U128>) {
+    #[allow(clippy::new_ret_no_self)]
+    /// `namespace` prefixes every storage key of the token, the contract state included, so
+    /// several token states can share one storage space. Calls reach a namespaced token by adding
+    /// a `_namespace` argument. Without a namespace the keys are left unprefixed.
+    ///
+    /// With `fixed_supply` the initial balances are all there will ever be: every mint path is
+    /// disabled for good.
+    pub fn new(
+        metadata: FTMetadata,
+        account_ids: Vec<AccountId>,
+        amounts: Vec<U128>,
+        namespace: Option<String>,
+        fixed_supply: bool,
+    ) {
+        let namespace = namespace.unwrap_or_default();
+        Self::assert_valid_namespace(&namespace);
+
+        Self::with_namespace(namespace.clone().into_bytes(), || {
+            Self::assert_can_initialize(&metadata);
+            let mut contract = Self::initial_state(metadata, namespace.as_bytes());
+            contract.grant_role(Role::Minter, contract_owner_address());
+            contract.initialize_balance_holders(account_ids, amounts);
+            contract.fixed_supply = fixed_supply;
+            contract.save();
+        });
+    }
+
+    /// Initializes the contract with the whole `total_supply` held by `treasury_id`.
+    pub fn new_with_supply(metadata: FTMetadata, total_supply: U128, treasury_id: AccountId) {
+        Self::new(metadata, vec![treasury_id], vec![total_supply], None, false);
+    }
+
+    /// Initializes the contract without an icon, with the whole `total_supply` held by the owner.
+    pub fn new_with_default_meta(name: String, symbol: String, decimals: u8, total_supply: U128) {
+        let metadata = FTMetadata {
+            name,
+            decimals,
+            symbol,
+            icon: None,
+            icon_reference: None,
+        };
+        let owner_id =
+            AccountId::try_from(contract_owner_address()).unwrap_or_else(|error| panic!("{error}"));
+        Self::new(metadata, vec![owner_id], vec![total_supply], None, false);
+    }
+
+    fn assert_can_initialize(metadata: &FTMetadata) {
         assert_eq!(
             caller_address(),
             contract_owner_address(),
             "Only the owner can call this function"
         );
+        let key = Self::storage_key(&namespace::active_namespace(), STORAGE_CONTRACT_KEY);
         assert!(
-            l1x_sdk::storage_read(STORAGE_CONTRACT_KEY).is_none(),
+            env::storage_read(&key).is_none(),
             "The contract is already initialized"
         );
 
+        Self::assert_valid_metadata(metadata);
+    }
+
+    fn assert_valid_metadata(metadata: &FTMetadata) {
         assert!(metadata.decimals <= 18, "Invalid decimals");
+        assert!(!metadata.name.trim().is_empty(), "The name can't be empty");
+        assert!(
+            !metadata.symbol.trim().is_empty(),
+            "The symbol can't be empty"
+        );
+        assert!(
+            metadata
+                .icon
+                .as_ref()
+                .map_or(true, |icon| !icon.trim().is_empty()),
+            "The icon can't be empty, leave it out instead"
+        );
+        assert!(
+            metadata
+                .icon
+                .as_ref()
+                .map_or(true, |icon| icon.len() <= icon::MAX_INLINE_ICON_LEN),
+            "The inline icon is too large, use an icon reference instead"
+        );
+        if let Some(reference) = &metadata.icon_reference {
+            reference.assert_valid();
+        }
+    }
 
-        let mut contract = Self {
-            metadata,
-            balances: LookupMap::new(STORAGE_BALANCES_KEY.to_vec()),
-            allowances: LookupMap::new(STORAGE_ALLOWANCES_KEY.to_vec()),
-            total_supply: Default::default(),
-            authorized_callers: BTreeSet::from([contract_owner_address()]),
-        };
-        contract.initialize_balance_holders(account_ids, amounts);
-        contract.save();
+    fn assert_valid_namespace(namespace: &str) {
+        assert!(namespace.len() <= 32, "The namespace is too long");
+        assert!(
+            namespace
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_'),
+            "The namespace can only contain letters, digits, '-' and '_'"
+        );
+    }
+
+    /// Namespaced keys start with the namespace length, which is never a letter like the first
+    /// byte of a root key, so the keys of different instances can't collide however the rest of
+    /// the bytes line up.
+    pub(crate) fn storage_key(namespace: &[u8], key: &[u8]) -> Vec<u8> {
+        if namespace.is_empty() {
+            return key.to_vec();
+        }
+        let len = u8::try_from(namespace.len()).expect("The namespace is too long");
+        [&[len], namespace, key].concat()
+    }
+
+    fn initial_state(metadata: FTMetadata, namespace: &[u8]) -> Self {
+        Self {
+            metadata: LazySection::new(
+                Self::storage_key(namespace, STORAGE_METADATA_KEY),
+                metadata,
+            ),
+            balances: LookupMap::new(Self::storage_key(namespace, STORAGE_BALANCES_KEY)),
+            allowances: LookupMap::new(Self::storage_key(namespace, STORAGE_ALLOWANCES_KEY)),
+            total_supply: LazySection::new(
+                Self::storage_key(namespace, STORAGE_TOTAL_SUPPLY_KEY),
+                0,
+            ),
+            #[cfg(feature = "locks")]
+            locks: LookupMap::new(Self::storage_key(namespace, STORAGE_LOCKS_KEY)),
+            #[cfg(feature = "locks")]
+            next_lock_id: 0,
+            #[cfg(feature = "locks")]
+            lock_penalty: LazySection::new(
+                Self::storage_key(namespace, STORAGE_LOCK_PENALTY_KEY),
+                LockPenaltyConfig::default(),
+            ),
+            treasury_balance: 0,
+            timelock_delay: 0,
+            timelock_operations: LookupMap::new(Self::storage_key(namespace, STORAGE_TIMELOCK_KEY)),
+            next_timelock_operation_id: 0,
+            transfer_burn: LazySection::new(
+                Self::storage_key(namespace, STORAGE_TRANSFER_BURN_KEY),
+                TransferBurnConfig::default(),
+            ),
+            #[cfg(feature = "tax")]
+            tax: LazySection::new(
+                Self::storage_key(namespace, STORAGE_TAX_KEY),
+                TaxConfig::default(),
+            ),
+            #[cfg(feature = "reflection")]
+            reflection: LazySection::new(
+                Self::storage_key(namespace, STORAGE_REFLECTION_KEY),
+                ReflectionState::default(),
+            ),
+            limits: LazySection::new(
+                Self::storage_key(namespace, STORAGE_LIMITS_KEY),
+                TransferLimits::default(),
+            ),
+            last_transfer_at: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_LAST_TRANSFER_KEY,
+            )),
+            withdrawal_protection: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_WITHDRAWAL_PROTECTION_KEY,
+            )),
+            pending_transfers: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_PENDING_TRANSFERS_KEY,
+            )),
+            next_pending_transfer_id: 0,
+            backups: LookupMap::new(Self::storage_key(namespace, STORAGE_BACKUPS_KEY)),
+            evacuation_delay: 0,
+            spending_keys: LookupMap::new(Self::storage_key(namespace, STORAGE_SPENDING_KEYS_KEY)),
+            claim_codes: LookupMap::new(Self::storage_key(namespace, STORAGE_CLAIM_CODES_KEY)),
+            claim_commitments: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_CLAIM_COMMITMENTS_KEY,
+            )),
+            reserve_oracle: None,
+            #[cfg(feature = "multi_token")]
+            sub_tokens: LookupMap::new(Self::storage_key(namespace, STORAGE_SUB_TOKENS_KEY)),
+            #[cfg(feature = "multi_token")]
+            sub_balances: LookupMap::new(Self::storage_key(namespace, STORAGE_SUB_BALANCES_KEY)),
+            wrapped_native: false,
+            wrapped_native_reserve: 0,
+            underlying: None,
+            memo_required: LookupMap::new(Self::storage_key(namespace, STORAGE_MEMO_REQUIRED_KEY)),
+            #[cfg(feature = "holds")]
+            holds: LookupMap::new(Self::storage_key(namespace, STORAGE_HOLDS_KEY)),
+            #[cfg(feature = "holds")]
+            holds_by_payer: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_HOLDS_BY_PAYER_KEY,
+            )),
+            #[cfg(feature = "holds")]
+            next_hold_id: 0,
+            frozen: LookupMap::new(Self::storage_key(namespace, STORAGE_FROZEN_KEY)),
+            #[cfg(feature = "compliance")]
+            sanctions: LazySection::new(Self::storage_key(namespace, STORAGE_SANCTIONS_KEY), None),
+            #[cfg(feature = "compliance")]
+            sanctions_cache: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_SANCTIONS_CACHE_KEY,
+            )),
+            #[cfg(feature = "compliance")]
+            jurisdictions: LookupMap::new(Self::storage_key(namespace, STORAGE_JURISDICTIONS_KEY)),
+            #[cfg(feature = "compliance")]
+            jurisdiction_rules: LazySection::new(
+                Self::storage_key(namespace, STORAGE_JURISDICTION_RULES_KEY),
+                JurisdictionRules::default(),
+            ),
+            #[cfg(feature = "compliance")]
+            clawbacks: LookupMap::new(Self::storage_key(namespace, STORAGE_CLAWBACKS_KEY)),
+            #[cfg(feature = "compliance")]
+            next_clawback_id: 0,
+            transfer_rules: rules::default_transfer_rules(),
+            whitelist: LookupMap::new(Self::storage_key(namespace, STORAGE_WHITELIST_KEY)),
+            trusted_spenders: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_TRUSTED_SPENDERS_KEY,
+            )),
+            spender_registry: LazySection::new(
+                Self::storage_key(namespace, STORAGE_SPENDER_REGISTRY_KEY),
+                SpenderRegistry::default(),
+            ),
+            max_multisend_batch: multisend::DEFAULT_MAX_MULTISEND_BATCH,
+            distributions: LookupMap::new(Self::storage_key(namespace, STORAGE_DISTRIBUTIONS_KEY)),
+            distribution_items: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_DISTRIBUTION_ITEMS_KEY,
+            )),
+            next_distribution_id: 0,
+            max_supply: None,
+            #[cfg(feature = "contributors")]
+            epoch_schedule: LazySection::new(
+                Self::storage_key(namespace, STORAGE_EPOCH_SCHEDULE_KEY),
+                None,
+            ),
+            #[cfg(feature = "contributors")]
+            contributors: LookupMap::new(Self::storage_key(namespace, STORAGE_CONTRIBUTORS_KEY)),
+            #[cfg(feature = "staking")]
+            staking_pool: StakingPool::default(),
+            #[cfg(feature = "staking")]
+            stakes: LookupMap::new(Self::storage_key(namespace, STORAGE_STAKES_KEY)),
+            #[cfg(feature = "staking")]
+            staking_config: LazySection::new(
+                Self::storage_key(namespace, STORAGE_STAKING_CONFIG_KEY),
+                StakingConfig::default(),
+            ),
+            #[cfg(feature = "staking")]
+            slash_history: LookupMap::new(Self::storage_key(namespace, STORAGE_SLASH_HISTORY_KEY)),
+            #[cfg(feature = "staking")]
+            operators: LookupMap::new(Self::storage_key(namespace, STORAGE_OPERATORS_KEY)),
+            #[cfg(feature = "staking")]
+            delegations: LookupMap::new(Self::storage_key(namespace, STORAGE_DELEGATIONS_KEY)),
+            #[cfg(feature = "staking")]
+            delegation_counts: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_DELEGATION_COUNTS_KEY,
+            )),
+            paused_until: None,
+            mint_breaker: LazySection::new(
+                Self::storage_key(namespace, STORAGE_MINT_BREAKER_KEY),
+                MintBreaker::default(),
+            ),
+            volume_breaker: LazySection::new(
+                Self::storage_key(namespace, STORAGE_VOLUME_BREAKER_KEY),
+                VolumeBreaker::default(),
+            ),
+            alert_thresholds: LazySection::new(
+                Self::storage_key(namespace, STORAGE_ALERT_THRESHOLDS_KEY),
+                AlertThresholds::default(),
+            ),
+            accounts: Vector::new(Self::storage_key(namespace, STORAGE_ACCOUNTS_KEY)),
+            indexed_accounts: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_INDEXED_ACCOUNTS_KEY,
+            )),
+            state_sequence: 0,
+            merkle_nodes: LookupMap::new(Self::storage_key(namespace, STORAGE_MERKLE_NODES_KEY)),
+            merkle_dirty: Vector::new(Self::storage_key(namespace, STORAGE_MERKLE_DIRTY_KEY)),
+            merkle_dirty_flags: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_MERKLE_DIRTY_FLAGS_KEY,
+            )),
+            merkle_leaf_balances: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_MERKLE_LEAF_BALANCES_KEY,
+            )),
+            balances_root: None,
+            merkle_rehash_cursor: None,
+            event_nonce: 0,
+            holder_count: 0,
+            balance_changes: Vector::new(Self::storage_key(namespace, STORAGE_BALANCE_CHANGES_KEY)),
+            roles: LookupMap::new(Self::storage_key(namespace, STORAGE_ROLES_KEY)),
+            fixed_supply: false,
+            decimals_migration: None,
+            localized_metadata: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_LOCALIZED_METADATA_KEY,
+            )),
+            approvals_received: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_APPROVALS_RECEIVED_KEY,
+            )),
+            approvals_received_count: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_APPROVALS_RECEIVED_COUNT_KEY,
+            )),
+            approval_positions: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_APPROVAL_POSITIONS_KEY,
+            )),
+            transfer_hooks: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_TRANSFER_HOOKS_KEY,
+            )),
+            max_hook_failures: hooks::DEFAULT_MAX_HOOK_FAILURES,
+            settlement_config: LazySection::new(
+                Self::storage_key(namespace, STORAGE_SETTLEMENT_CONFIG_KEY),
+                None,
+            ),
+            prepared_transfers: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_PREPARED_TRANSFERS_KEY,
+            )),
+            settlements_by_sender: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_SETTLEMENTS_BY_SENDER_KEY,
+            )),
+            idempotency_keys: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_IDEMPOTENCY_KEYS_KEY,
+            )),
+            idempotency_ttl: DEFAULT_IDEMPOTENCY_TTL,
+            #[cfg(feature = "invoices")]
+            invoices: LookupMap::new(Self::storage_key(namespace, STORAGE_INVOICES_KEY)),
+            #[cfg(feature = "invoices")]
+            open_invoices_by_payer: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_OPEN_INVOICES_BY_PAYER_KEY,
+            )),
+            #[cfg(feature = "invoices")]
+            next_invoice_id: 0,
+            #[cfg(feature = "invoices")]
+            invoice_fee: 0,
+            transfer_records: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_TRANSFER_RECORDS_KEY,
+            )),
+            fee_routes: LazySection::new(
+                Self::storage_key(namespace, STORAGE_FEE_ROUTES_KEY),
+                Vec::new(),
+            ),
+            royalties: LookupMap::new(Self::storage_key(namespace, STORAGE_ROYALTIES_KEY)),
+            pair_policies: LookupMap::new(Self::storage_key(namespace, STORAGE_PAIR_POLICIES_KEY)),
+            spending_budgets: LookupMap::new(Self::storage_key(
+                namespace,
+                STORAGE_SPENDING_BUDGETS_KEY,
+            )),
+            cleanup_cursor: 0,
+            cleanup_rewards: LazySection::new(
+                Self::storage_key(namespace, STORAGE_CLEANUP_REWARDS_KEY),
+                CleanupRewards::default(),
+            ),
+            dust_policy: LazySection::new(
+                Self::storage_key(namespace, STORAGE_DUST_POLICY_KEY),
+                None,
+            ),
+            #[cfg(feature = "names")]
+            name_registry: LazySection::new(
+                Self::storage_key(namespace, STORAGE_NAME_REGISTRY_KEY),
+                None,
+            ),
+            #[cfg(feature = "names")]
+            names: LookupMap::new(Self::storage_key(namespace, STORAGE_NAMES_KEY)),
+        }
     }
 
-    fn initialize_balance_holders(&mut self, account_ids: Vec<Address>, amounts: Vec<U128>) {
+    fn initialize_balance_holders(&mut self, account_ids: Vec<AccountId>, amounts: Vec<U128>) {
         assert_eq!(
             account_ids.len(),
             amounts.len(),
@@ -110,24 +862,30 @@ impl L1xFtErc20 {
         );
 
         assert_eq!(
-            self.total_supply, 0,
+            *self.total_supply, 0,
             "Contract has already been initialized"
         );
-        // Create a vector to store unique account IDs
         let mut unique_account_ids = BTreeSet::new();
-        for (account_id, amount) in account_ids.into_iter().zip(amounts) {
-            if !unique_account_ids.contains(&account_id) {
-                // If it's not present, insert it into unique_account_ids
-                unique_account_ids.insert(account_id);
-
-                // Update balances and total supply
-                self.balances.insert(account_id, amount.0);
-                self.total_supply = self
-                    .total_supply
-                    .checked_add(amount.0)
-                    .expect("total_supply is overflowed");
-            }
+        for (account_id, amount) in account_ids.into_iter().map(Address::from).zip(amounts) {
+            assert!(
+                unique_account_ids.insert(account_id),
+                "'{}' is listed more than once",
+                account_id
+            );
+            assert_ne!(amount.0, 0, "'{}' has a zero amount", account_id);
+
+            self.set_balance(&account_id, amount.0);
+            *self.total_supply = self
+                .total_supply
+                .checked_add(amount.0)
+                .expect("total_supply is overflowed");
+        }
+
+        FtEvent::Initialized {
+            holders: unique_account_ids.len() as u32,
+            total_supply: *self.total_supply,
         }
+        .emit();
     }
 
     pub fn add_authorized_caller(authorized_caller: Address) {
@@ -138,10 +896,9 @@ impl L1xFtErc20 {
             "Authorized caller can be added by contract owner only"
         );
         assert!(
-            !contract.authorized_callers.contains(&authorized_caller),
+            contract.grant_role(Role::Minter, authorized_caller),
             "This address is already an authorized caller"
         );
-        contract.authorized_callers.insert(authorized_caller);
         l1x_sdk::msg(&format!(
             "Authorized caller: {:?} has been added successfully",
             authorized_caller
@@ -149,14 +906,32 @@ impl L1xFtErc20 {
         contract.save();
     }
 
+    pub fn remove_authorized_caller(authorized_caller: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            contract_owner_address(),
+            caller_address(),
+            "Authorized caller can be removed by contract owner only"
+        );
+        assert!(
+            contract.revoke_role(Role::Minter, &authorized_caller),
+            "This address is not an authorized caller"
+        );
+        l1x_sdk::msg(&format!(
+            "Authorized caller: {:?} has been removed successfully",
+            authorized_caller
+        ));
+        contract.save();
+    }
+
     pub fn ft_name() -> String {
         let contract = Self::load();
-        contract.metadata.name
+        contract.metadata.into_inner().name
     }
 
     pub fn ft_symbol() -> String {
         let contract = Self::load();
-        contract.metadata.symbol
+        contract.metadata.into_inner().symbol
     }
 
     pub fn ft_decimals() -> u8 {
@@ -166,51 +941,99 @@ impl L1xFtErc20 {
 
     pub fn ft_icon() -> Option<String> {
         let contract = Self::load();
-        contract.metadata.icon
+        contract.metadata.into_inner().icon
     }
 
     pub fn ft_metadata() -> FTMetadata {
         let contract = Self::load();
-        contract.metadata
+        contract.metadata.into_inner()
     }
 
-    pub fn ft_mint(recipient_id: Address, amount: U128) {
+    pub fn ft_mint(recipient_id: AccountId, amount: U128) {
         let mut contract = Self::load();
         assert!(
-            contract.authorized_callers.contains(&caller_address()),
+            contract.has_role(Role::Minter, &caller_address()),
             "Only authorized caller can mint tokens"
         );
         assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        contract.assert_unbacked_mint_allowed();
 
-        contract.mint(&recipient_id, amount.0);
+        if contract.record_minter_mint(amount.0) {
+            contract.mint(&recipient_id, amount.0);
+        }
 
         contract.save();
     }
 
-    pub fn ft_transfer(recipient_id: Address, amount: U128) {
+    /// A retried call with the same `idempotency_key` does nothing. `reference_id` is echoed
+    /// in a `TransferReference` event.
+    pub fn ft_transfer(
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
         assert_ne!(amount.0, 0, "Amount should be greater than 0");
         let mut contract = Self::load();
 
-        let sender_id = l1x_sdk::caller_address();
-        contract.transfer(&sender_id, &recipient_id, amount.into());
+        let sender_id = env::caller_address();
+        if contract.replayed_idempotency_key(
+            &sender_id,
+            idempotency_key,
+            &sender_id,
+            &recipient_id,
+            amount.0,
+        ) {
+            contract.release();
+            return;
+        }
+        contract.assert_memo_not_required(&recipient_id);
+        contract.transfer_or_hold(&sender_id, &recipient_id, amount.into());
+        Self::emit_transfer_reference(&sender_id, &recipient_id, amount.0, reference_id);
 
         contract.save()
     }
 
-    pub fn ft_transfer_from(sender_id: Address, recipient_id: Address, amount: U128) {
+    /// A retried call with the same `idempotency_key` does nothing. `reference_id` is echoed
+    /// in a `TransferReference` event.
+    pub fn ft_transfer_from(
+        sender_id: Address,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
         assert_ne!(amount.0, 0, "Amount should be greater than 0");
         let mut contract = Self::load();
         let spender_id = caller_address();
+        if contract.replayed_idempotency_key(
+            &spender_id,
+            idempotency_key,
+            &sender_id,
+            &recipient_id,
+            amount.0,
+        ) {
+            contract.release();
+            return;
+        }
 
+        contract.assert_memo_not_required(&recipient_id);
         contract.allowance_update(AllowanceUpdateOp::Spend, &sender_id, &spender_id, amount.0);
-        contract.transfer(&sender_id, &recipient_id, amount.into());
+        contract.spend_budget(&sender_id, &spender_id, amount.0);
+        contract.transfer_or_hold(&sender_id, &recipient_id, amount.into());
+        Self::emit_transfer_reference(&sender_id, &recipient_id, amount.0, reference_id);
 
         contract.save();
     }
 
+    pub fn ft_is_fixed_supply() -> bool {
+        let contract = Self::load();
+        contract.fixed_supply
+    }
+
     pub fn ft_total_supply() -> U128 {
         let contract = Self::load();
-        contract.total_supply.into()
+        (*contract.total_supply).into()
     }
 
     pub fn ft_balance_of(account_id: Address) -> U128 {
@@ -218,26 +1041,33 @@ impl L1xFtErc20 {
         contract.balance_of(&account_id).unwrap_or_default().into()
     }
 
-    pub fn ft_approve(spender_id: Address, amount: U128) {
+    pub fn ft_approve(spender_id: AccountId, amount: U128) {
         let mut contract = Self::load();
         let owner_id = caller_address();
         assert_ne!(
-            owner_id, spender_id,
+            owner_id, *spender_id,
             "User cannot approve themselves as a spender"
         );
 
         contract.assert_if_no_balance(&owner_id);
+        if amount.0 != 0 {
+            contract.assert_approval_allowed(&owner_id, &spender_id);
+        }
         contract.allowance_update(AllowanceUpdateOp::Set, &owner_id, &spender_id, amount.0);
 
         contract.save();
     }
 
-    pub fn ft_increase_allowance(spender_id: Address, amount: U128) {
+    pub fn ft_increase_allowance(spender_id: AccountId, amount: U128) {
         assert_ne!(amount.0, 0, "Amount should be greater than 0");
         let mut contract = Self::load();
         let owner_id = caller_address();
-        assert_ne!(owner_id, spender_id, "Owner and spender cannot be the same");
+        assert_ne!(
+            owner_id, *spender_id,
+            "Owner and spender cannot be the same"
+        );
         contract.assert_if_no_balance(&owner_id);
+        contract.assert_approval_allowed(&owner_id, &spender_id);
         contract.allowance_update(
             AllowanceUpdateOp::Increase,
             &owner_id,
@@ -248,11 +1078,14 @@ impl L1xFtErc20 {
         contract.save();
     }
 
-    pub fn ft_decrease_allowance(spender_id: Address, amount: U128) {
+    pub fn ft_decrease_allowance(spender_id: AccountId, amount: U128) {
         assert_ne!(amount.0, 0, "Amount should be greater than 0");
         let mut contract = Self::load();
         let owner_id = caller_address();
-        assert_ne!(owner_id, spender_id, "Owner and spender cannot be the same");
+        assert_ne!(
+            owner_id, *spender_id,
+            "Owner and spender cannot be the same"
+        );
         contract.assert_if_no_balance(&owner_id);
         contract.allowance_update(
             AllowanceUpdateOp::Decrease,
@@ -271,46 +1104,112 @@ impl L1xFtErc20 {
             Some(allowance) => allowance.get(&spender_id).into(),
             None => 0.into(),
         }
-    } 
+    }
 
     fn mint(&mut self, recipient_id: &Address, amount: u128) {
-        let receiver_balance = self.balance_of(&recipient_id).unwrap_or_default();
+        assert!(!self.fixed_supply, "The token has a fixed supply");
+        self.assert_not_paused();
+        let receiver_balance = self.balance_of(recipient_id).unwrap_or_default();
 
         let total_supply = self
             .total_supply
             .checked_add(amount)
             .expect("total_supply is overflowed");
-        self.total_supply = total_supply;
-        self.balances.insert(
-            recipient_id.clone(),
-            receiver_balance
-                .checked_add(amount)
-                .expect("Balance overflowed"),
-        );
+        self.assert_within_max_supply(total_supply);
+        self.assert_within_reserve(total_supply);
+        *self.total_supply = total_supply;
+        let new_balance = receiver_balance
+            .checked_add(amount)
+            .expect("Balance overflowed");
+        self.assert_max_wallet(recipient_id, new_balance);
+        self.set_balance(recipient_id, new_balance);
 
         l1x_sdk::msg(&format!("Minted {} tokens for {}", amount, recipient_id));
     }
 
-    fn transfer(&mut self, sender_id: &Address, recipient_id: &Address, amount: u128) {
+    fn burn(&mut self, account_id: &Address, amount: u128) {
+        self.assert_not_paused();
+        let balance = self.balance_of(account_id).unwrap_or_default();
+        assert!(
+            self.spendable_balance(account_id) >= amount,
+            "Not enough balance to burn"
+        );
+        self.set_balance(account_id, balance - amount);
+        *self.total_supply = self
+            .total_supply
+            .checked_sub(amount)
+            .expect("total_supply is underflowed");
+
+        FtEvent::Burn {
+            account_id: *account_id,
+            amount,
+        }
+        .emit();
+    }
+
+    fn transfer(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        kind: TransferKind,
+    ) {
         assert_ne!(sender_id, recipient_id, "Self transfer is not allowed");
-        let sender_balance = self.balance_of(&sender_id).unwrap_or_default();
-        assert!(sender_balance >= amount, "Not enough balance to transfer");
-        self.balances.insert(
-            sender_id.clone(),
+        let amount = if kind.is_escrow_payout() {
+            amount
+        } else {
+            self.apply_dust_policy(sender_id, amount)
+        };
+        self.assert_not_paused();
+        self.assert_not_compromised(sender_id);
+        #[cfg(feature = "compliance")]
+        {
+            self.assert_not_sanctioned(sender_id);
+            self.assert_not_sanctioned(recipient_id);
+        }
+        self.assert_transfer_rules(sender_id, recipient_id, amount);
+        if !kind.is_escrow_payout() {
+            self.enforce_transfer_cooldown(sender_id);
+        }
+        let sender_balance = self.balance_of(sender_id).unwrap_or_default();
+        assert!(
+            self.spendable_balance(sender_id) >= amount,
+            "Not enough balance to transfer"
+        );
+        self.set_balance(
+            sender_id,
             sender_balance
                 .checked_sub(amount)
                 .expect("Balance overflowed"),
         );
-        let receiver_balance = self.balance_of(&recipient_id).unwrap_or_default();
-        self.balances.insert(
-            recipient_id.clone(),
-            receiver_balance
-                .checked_add(amount)
-                .expect("Balance overflowed"),
-        );
+        let received = if kind.pays_fees() {
+            let mut fees = 0;
+            #[cfg(feature = "tax")]
+            {
+                fees += self.apply_transfer_tax(sender_id, recipient_id, amount);
+            }
+            fees += self.apply_transfer_burn(sender_id, recipient_id, amount);
+            #[cfg(feature = "reflection")]
+            {
+                fees += self.apply_reflection_fee(sender_id, amount);
+            }
+            fees += self.apply_royalty(sender_id, recipient_id, amount);
+            amount - fees
+        } else {
+            amount
+        };
+        self.record_transfer_volume(amount);
+        self.check_transfer_alert(sender_id, recipient_id, amount);
+        let receiver_balance = self.balance_of(recipient_id).unwrap_or_default();
+        let new_balance = receiver_balance
+            .checked_add(received)
+            .expect("Balance overflowed");
+        self.assert_max_wallet(recipient_id, new_balance);
+        self.set_balance(recipient_id, new_balance);
+        self.record_transfer(sender_id, recipient_id, received, kind.is_refundable());
         l1x_sdk::msg(&format!(
             "Transferred {} tokens from {} to {}",
-            amount, sender_id, recipient_id
+            received, sender_id, recipient_id
         ));
     }
 
@@ -321,23 +1220,29 @@ impl L1xFtErc20 {
         spender_id: &Address,
         amount: u128,
     ) {
+        let old_allowance = self
+            .allowances
+            .get(owner_id)
+            .map_or(0, |allowance| allowance.get(spender_id));
+        self.assert_no_decimals_migration();
+        self.index_account(owner_id);
         let allowance = self.allowances.get_mut(owner_id);
 
         match update_op {
             AllowanceUpdateOp::Set => match allowance {
-                Some(allowance_ref) => allowance_ref.set(spender_id.clone(), amount),
+                Some(allowance_ref) => allowance_ref.set(*spender_id, amount),
                 None => {
                     let mut new_allowance = FTAllowance::default();
-                    new_allowance.set(spender_id.clone(), amount);
-                    self.allowances.insert(owner_id.clone(), new_allowance);
+                    new_allowance.set(*spender_id, amount);
+                    self.allowances.insert(*owner_id, new_allowance);
                 }
             },
             AllowanceUpdateOp::Increase => match allowance {
                 Some(allowance_ref) => allowance_ref.increase(spender_id, amount),
                 None => {
                     let mut new_allowance = FTAllowance::default();
-                    new_allowance.set(spender_id.clone(), amount);
-                    self.allowances.insert(owner_id.clone(), new_allowance);
+                    new_allowance.set(*spender_id, amount);
+                    self.allowances.insert(*owner_id, new_allowance);
                 }
             },
             AllowanceUpdateOp::Decrease => match allowance {
@@ -349,49 +1254,139 @@ impl L1xFtErc20 {
                 None => panic!("{owner_id} didn't set allowance for {spender_id}"),
             },
         }
+
+        let new_allowance = self
+            .allowances
+            .get(owner_id)
+            .map_or(0, |allowance| allowance.get(spender_id));
+        self.index_approval(owner_id, spender_id, new_allowance);
+        self.check_allowance_alert(owner_id, spender_id, old_allowance, new_allowance);
     }
 
     fn balance_of(&self, account_id: &Address) -> Option<u128> {
-        self.balances.get(account_id).copied()
+        let stored = self.balances.get(account_id).copied()?;
+        #[cfg(feature = "reflection")]
+        if !self.reflection.is_excluded(account_id) {
+            return Some(self.reflection.amount_of(stored));
+        }
+        Some(stored)
+    }
+
+    /// Part of the balance that isn't frozen or reserved by holds or prepared transfers.
+    fn spendable_balance(&self, account_id: &Address) -> u128 {
+        self.balance_of(account_id)
+            .unwrap_or_default()
+            .saturating_sub(self.frozen_amount(account_id))
+            .saturating_sub(self.held_amount(account_id))
+            .saturating_sub(self.prepared_amount(account_id))
+    }
+
+    /// Without the holds feature nothing is ever held.
+    #[cfg(not(feature = "holds"))]
+    fn held_amount(&self, _account_id: &Address) -> u128 {
+        0
+    }
+
+    #[cfg(not(feature = "holds"))]
+    fn prune_expired_holds(&mut self, _account_id: &Address) -> u32 {
+        0
+    }
+
+    /// Every balance change goes through here so reflection shares stay consistent, and none
+    /// happens while the contract is paused.
+    fn set_balance(&mut self, account_id: &Address, amount: u128) {
+        self.assert_not_paused();
+        self.write_balance(account_id, amount);
+    }
+
+    /// `set_balance` without the pause check, for the decimals migration that runs while paused.
+    fn write_balance(&mut self, account_id: &Address, amount: u128) {
+        let old_balance = self.balance_of(account_id).unwrap_or_default();
+        self.check_balance_alert(account_id, old_balance, amount);
+        self.index_account(account_id);
+        self.mark_balance_leaf_dirty(account_id);
+        self.record_balance_change(account_id, old_balance, amount);
+        #[cfg(feature = "reflection")]
+        if !self.reflection.is_excluded(account_id) {
+            let old_shares = self.balances.get(account_id).copied().unwrap_or_default();
+            let shares = self.reflection.reshare(old_shares, amount);
+            self.balances.insert(*account_id, shares);
+            return;
+        }
+        self.balances.insert(*account_id, amount);
     }
 
     fn assert_if_no_balance(&self, account_id: &Address) {
         assert_ne!(
-            *self.balances.get(account_id).unwrap_or(&0),
+            self.balance_of(account_id).unwrap_or_default(),
             0,
             "'{}' should have tokens in the balance",
             account_id
         );
     }
 
-    fn load() -> Self {
-        match l1x_sdk::storage_read(STORAGE_CONTRACT_KEY) {
-            Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
-            None => panic!("The contract isn't initialized"),
-        }
+    fn bps_of(amount: u128, bps: u16) -> u128 {
+        amount.checked_mul(bps.into()).expect("amount overflowed") / BPS_DENOMINATOR
     }
 
-    fn save(&mut self) {
-        l1x_sdk::storage_write(STORAGE_CONTRACT_KEY, &self.try_to_vec().unwrap());
+    fn load() -> Self {
+        let namespace = namespace::active_namespace();
+        lazy::check_out(&namespace, || {
+            let key = Self::storage_key(&namespace, STORAGE_CONTRACT_KEY);
+            match env::storage_read(&key) {
+                Some(bytes) => Self::try_from_slice(&bytes).unwrap(),
+                None => panic!("The contract isn't initialized"),
+            }
+        })
     }
 
-    fn mint(&mut self, recipient_id: &Address, amount: u128) {
-        let receiver_balance = self.balance_of(&recipient_id).unwrap_or_default();
+    /// Inside a [`lazy::call_scope`] the state is handed back to the scope, which writes it once
+    /// at the end.
+    fn save(mut self) {
+        self.state_sequence = self.state_sequence.wrapping_add(1);
+        self.event_nonce += events::take_pending_events();
+        let namespace = namespace::active_namespace();
+        if let Some(mut contract) = lazy::check_in(&namespace, self) {
+            contract.write(&namespace);
+        }
+    }
 
-        let total_supply = self
-            .total_supply
-            .checked_add(amount)
-            .expect("total_supply is overflowed");
-        self.total_supply = total_supply;
-        self.balances.insert(
-            recipient_id.clone(),
-            receiver_balance
-                .checked_add(amount)
-                .expect("Balance overflowed"),
-        );
+    /// Hands back a state the call didn't change, so a call scope can pass it on.
+    fn release(self) {
+        lazy::check_in(&namespace::active_namespace(), self);
+    }
 
-        l1x_sdk::msg(&format!("Minted {} tokens for {}", amount, recipient_id));
+    /// Writes the dirty sections and the main struct.
+    pub(crate) fn write(&mut self, namespace: &[u8]) {
+        self.metadata.flush();
+        self.total_supply.flush();
+        #[cfg(feature = "compliance")]
+        self.jurisdiction_rules.flush();
+        self.spender_registry.flush();
+        #[cfg(feature = "staking")]
+        self.staking_config.flush();
+        self.mint_breaker.flush();
+        #[cfg(feature = "locks")]
+        self.lock_penalty.flush();
+        self.transfer_burn.flush();
+        #[cfg(feature = "tax")]
+        self.tax.flush();
+        #[cfg(feature = "reflection")]
+        self.reflection.flush();
+        self.limits.flush();
+        #[cfg(feature = "compliance")]
+        self.sanctions.flush();
+        #[cfg(feature = "contributors")]
+        self.epoch_schedule.flush();
+        self.volume_breaker.flush();
+        self.alert_thresholds.flush();
+        self.settlement_config.flush();
+        self.fee_routes.flush();
+        self.cleanup_rewards.flush();
+        self.dust_policy.flush();
+        #[cfg(feature = "names")]
+        self.name_registry.flush();
+        let key = Self::storage_key(namespace, STORAGE_CONTRACT_KEY);
+        env::storage_write(&key, &self.try_to_vec().unwrap());
     }
 }
-
-
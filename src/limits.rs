@@ -0,0 +1,295 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::timelock::TimelockOperation;
+use crate::L1xFtErc20;
+
+thread_local! {
+    /// Senders whose cooldown the batch being executed has already checked, if one is.
+    static BATCH_SENDERS: RefCell<Option<BTreeSet<Address>>> = RefCell::new(None);
+}
+
+/// Runs `f` as one batch, so each sender's transfer cooldown is checked only on its first
+/// transfer. Nested batches are part of the outer one.
+pub(crate) fn transfer_batch<R>(f: impl FnOnce() -> R) -> R {
+    struct Batch {
+        outermost: bool,
+    }
+
+    impl Drop for Batch {
+        fn drop(&mut self) {
+            if self.outermost {
+                BATCH_SENDERS.with(|senders| senders.take());
+            }
+        }
+    }
+
+    let _batch = Batch {
+        outermost: BATCH_SENDERS.with(|senders| {
+            let mut senders = senders.borrow_mut();
+            let outermost = senders.is_none();
+            senders.get_or_insert_with(BTreeSet::new);
+            outermost
+        }),
+    };
+    f()
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct TransferLimits {
+    max_wallet: Option<u128>,
+    max_wallet_exempt: BTreeSet<Address>,
+    max_tx_amount: Option<u128>,
+    max_tx_exempt: BTreeSet<Address>,
+    transfer_cooldown: u64,
+    cooldown_exempt: BTreeSet<Address>,
+}
+
+impl TransferLimits {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        self.max_wallet = self.max_wallet.map(&rescale);
+        self.max_tx_amount = self.max_tx_amount.map(&rescale);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TransferLimitsView {
+    max_wallet: Option<U128>,
+    max_wallet_exempt: Vec<Address>,
+    max_tx_amount: Option<U128>,
+    max_tx_exempt: Vec<Address>,
+    transfer_cooldown: U64,
+    cooldown_exempt: Vec<Address>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Tightens the max wallet size immediately. Raising or removing it goes through the timelock.
+    pub fn set_max_wallet(max_balance: Option<U128>) -> Option<U64> {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let tightens = match (contract.limits.max_wallet, max_balance) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(current), Some(new)) => new.0 <= current,
+        };
+        let operation_id = if tightens {
+            contract.set_max_wallet_now(max_balance);
+            None
+        } else {
+            Some(contract.timelock_schedule(TimelockOperation::SetMaxWallet { max_balance }))
+        };
+
+        contract.save();
+        operation_id
+    }
+
+    pub fn add_max_wallet_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.max_wallet_exempt.insert(account_id),
+            "This address is already exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_max_wallet_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.max_wallet_exempt.remove(&account_id),
+            "This address is not exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn set_max_tx_amount(max_amount: Option<U128>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let old_amount = contract.limits.max_tx_amount;
+        contract.limits.max_tx_amount = max_amount.map(|max_amount| max_amount.0);
+        FtEvent::MaxTxAmountChanged {
+            old_amount,
+            new_amount: contract.limits.max_tx_amount,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn add_max_tx_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.max_tx_exempt.insert(account_id),
+            "This address is already exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_max_tx_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.max_tx_exempt.remove(&account_id),
+            "This address is not exempt"
+        );
+
+        contract.save();
+    }
+
+    /// Minimum time between two transfers from the same sender. Zero disables the cooldown.
+    pub fn set_transfer_cooldown(cooldown: U64) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.limits.transfer_cooldown = cooldown.0;
+
+        contract.save();
+    }
+
+    pub fn add_cooldown_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.cooldown_exempt.insert(account_id),
+            "This address is already exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_cooldown_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.limits.cooldown_exempt.remove(&account_id),
+            "This address is not exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn last_transfer_at(account_id: Address) -> Option<U128> {
+        let contract = Self::load();
+        contract
+            .last_transfer_at
+            .get(&account_id)
+            .map(|timestamp| (*timestamp).into())
+    }
+
+    pub fn transfer_limits() -> TransferLimitsView {
+        let contract = Self::load();
+        TransferLimitsView {
+            max_wallet: contract.limits.max_wallet.map(Into::into),
+            max_wallet_exempt: contract.limits.max_wallet_exempt.iter().copied().collect(),
+            max_tx_amount: contract.limits.max_tx_amount.map(Into::into),
+            max_tx_exempt: contract.limits.max_tx_exempt.iter().copied().collect(),
+            transfer_cooldown: contract.limits.transfer_cooldown.into(),
+            cooldown_exempt: contract.limits.cooldown_exempt.iter().copied().collect(),
+        }
+    }
+
+    pub(crate) fn set_max_wallet_now(&mut self, max_balance: Option<U128>) {
+        self.limits.max_wallet = max_balance.map(|max_balance| max_balance.0);
+    }
+
+    pub(crate) fn assert_max_wallet(&self, account_id: &Address, new_balance: u128) {
+        if let Some(max_wallet) = self.limits.max_wallet {
+            assert!(
+                new_balance <= max_wallet || self.limits.max_wallet_exempt.contains(account_id),
+                "'{}' would exceed the max wallet size",
+                account_id
+            );
+        }
+    }
+
+    pub(crate) fn max_tx_violation(
+        &self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> Option<String> {
+        let max_tx_amount = self.limits.max_tx_amount?;
+        let allowed = amount <= max_tx_amount
+            || self.limits.max_tx_exempt.contains(sender_id)
+            || self.limits.max_tx_exempt.contains(recipient_id);
+        (!allowed).then(|| "Transfer amount exceeds the max transaction amount".to_string())
+    }
+
+    /// Fails if `sender_id` is still cooling down, otherwise records the transfer time. Within a
+    /// batch only the sender's first transfer is checked.
+    pub(crate) fn enforce_transfer_cooldown(&mut self, sender_id: &Address) {
+        if self.limits.transfer_cooldown == 0 || self.limits.cooldown_exempt.contains(sender_id) {
+            return;
+        }
+        let checked_in_batch = BATCH_SENDERS.with(|senders| {
+            senders
+                .borrow_mut()
+                .as_mut()
+                .map_or(false, |senders| !senders.insert(*sender_id))
+        });
+        if checked_in_batch {
+            return;
+        }
+
+        let now = env::block_timestamp();
+        if let Some(last_transfer_at) = self.last_transfer_at.get(sender_id) {
+            assert!(
+                now >= last_transfer_at + u128::from(self.limits.transfer_cooldown),
+                "Transfer cooldown is active for '{}'",
+                sender_id
+            );
+        }
+        self.last_transfer_at.insert(*sender_id, now);
+    }
+}
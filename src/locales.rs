@@ -0,0 +1,115 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+const MAX_LOCALE_LEN: usize = 35;
+const MAX_DESCRIPTION_LEN: usize = 1024;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct LocalizedMetadata {
+    name: String,
+    description: String,
+}
+
+/// `locale` is the entry that was used, `None` when falling back to the default metadata.
+#[derive(Serialize, Deserialize)]
+pub struct LocalizedMetadataView {
+    locale: Option<String>,
+    name: String,
+    description: Option<String>,
+    symbol: String,
+    decimals: u8,
+}
+
+/// Locale tags are case-insensitive, so they are stored lowercased.
+fn normalize_locale(locale: &str) -> String {
+    assert!(
+        !locale.is_empty() && locale.len() <= MAX_LOCALE_LEN,
+        "Invalid locale"
+    );
+    assert!(
+        locale
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-'),
+        "Invalid locale"
+    );
+    locale.to_ascii_lowercase()
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_localized_metadata(locale: String, name: String, description: String) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(!name.trim().is_empty(), "The name can't be empty");
+        assert!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            "The description is too long"
+        );
+
+        contract.localized_metadata.insert(
+            normalize_locale(&locale),
+            LocalizedMetadata { name, description },
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_localized_metadata(locale: String) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract
+                .localized_metadata
+                .remove(normalize_locale(&locale))
+                .is_some(),
+            "No metadata for this locale"
+        );
+
+        contract.save();
+    }
+
+    /// Looks up `locale`, then its language alone ("pt" for "pt-br"), then falls back to the
+    /// default metadata.
+    pub fn ft_metadata_localized(locale: String) -> LocalizedMetadataView {
+        let contract = Self::load();
+        let locale = normalize_locale(&locale);
+        let language = locale.split('-').next().unwrap_or_default().to_string();
+
+        let localized = [locale, language].into_iter().find_map(|candidate| {
+            contract
+                .localized_metadata
+                .get(&candidate)
+                .cloned()
+                .map(|localized| (candidate, localized))
+        });
+        let metadata = contract.metadata.into_inner();
+        match localized {
+            Some((locale, localized)) => LocalizedMetadataView {
+                locale: Some(locale),
+                name: localized.name,
+                description: Some(localized.description),
+                symbol: metadata.symbol,
+                decimals: metadata.decimals,
+            },
+            None => LocalizedMetadataView {
+                locale: None,
+                name: metadata.name,
+                description: None,
+                symbol: metadata.symbol,
+                decimals: metadata.decimals,
+            },
+        }
+    }
+}
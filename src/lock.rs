@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::treasury::PenaltyDestination;
+use crate::{L1xFtErc20, BPS_DENOMINATOR};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+struct TokenLock {
+    amount: u128,
+    locked_at: u128,
+    unlock_at: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct FTLocks {
+    locks: BTreeMap<u64, TokenLock>,
+}
+
+/// How much of an early unlock is withheld and where it goes.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct LockPenaltyConfig {
+    penalty_bps: u16,
+    destination: PenaltyDestination,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LockView {
+    lock_id: U64,
+    amount: U128,
+    locked_at: U128,
+    unlock_at: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_lock_penalty(penalty_bps: u16, destination: PenaltyDestination) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            u128::from(penalty_bps) <= BPS_DENOMINATOR,
+            "Penalty can't exceed 100%"
+        );
+
+        *contract.lock_penalty = LockPenaltyConfig {
+            penalty_bps,
+            destination,
+        };
+
+        contract.save();
+    }
+
+    pub fn lock_penalty() -> LockPenaltyConfig {
+        let contract = Self::load();
+        contract.lock_penalty.into_inner()
+    }
+
+    pub fn lock(amount: U128, duration: U64) -> U64 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        assert_ne!(duration.0, 0, "Duration should be greater than 0");
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+
+        let balance = contract.balance_of(&owner_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&owner_id) >= amount.0,
+            "Not enough balance to lock"
+        );
+        contract.set_balance(&owner_id, balance - amount.0);
+
+        let now = env::block_timestamp();
+        let lock_id = contract.next_lock_id;
+        contract.next_lock_id = lock_id.checked_add(1).expect("Lock id overflowed");
+
+        let mut locks = contract.locks.get(&owner_id).cloned().unwrap_or_default();
+        locks.locks.insert(
+            lock_id,
+            TokenLock {
+                amount: amount.0,
+                locked_at: now,
+                unlock_at: now
+                    .checked_add(duration.0.into())
+                    .expect("Unlock time overflowed"),
+            },
+        );
+        contract.locks.insert(owner_id, locks);
+
+        l1x_sdk::msg(&format!(
+            "Locked {} tokens of {} with id {}",
+            amount.0, owner_id, lock_id
+        ));
+
+        contract.save();
+        lock_id.into()
+    }
+
+    pub fn unlock(lock_id: U64) -> U128 {
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        let owner_id = caller_address();
+
+        let mut locks = contract
+            .locks
+            .get(&owner_id)
+            .cloned()
+            .expect("The account has no locks");
+        let lock = locks.locks.remove(&lock_id.0).expect("Lock not found");
+        contract.locks.insert(owner_id, locks);
+
+        let penalty = contract.early_unlock_penalty(&lock, env::block_timestamp());
+        let released = lock.amount - penalty;
+
+        let balance = contract.balance_of(&owner_id).unwrap_or_default();
+        contract.set_balance(
+            &owner_id,
+            balance.checked_add(released).expect("Balance overflowed"),
+        );
+
+        if penalty > 0 {
+            match contract.lock_penalty.destination {
+                PenaltyDestination::Treasury => contract.treasury_credit(penalty),
+                PenaltyDestination::Burn => {
+                    *contract.total_supply = contract
+                        .total_supply
+                        .checked_sub(penalty)
+                        .expect("total_supply is underflowed");
+                    FtEvent::Burn {
+                        account_id: owner_id,
+                        amount: penalty,
+                    }
+                    .emit();
+                }
+            }
+        }
+
+        l1x_sdk::msg(&format!(
+            "Unlocked {} tokens of {} from lock {}, penalty {}",
+            released, owner_id, lock_id.0, penalty
+        ));
+
+        contract.save();
+        released.into()
+    }
+
+    pub fn locks_of(account_id: Address) -> Vec<LockView> {
+        let contract = Self::load();
+
+        match contract.locks.get(&account_id) {
+            Some(locks) => locks
+                .locks
+                .iter()
+                .map(|(lock_id, lock)| LockView {
+                    lock_id: (*lock_id).into(),
+                    amount: lock.amount.into(),
+                    locked_at: lock.locked_at.into(),
+                    unlock_at: lock.unlock_at.into(),
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn unlock_penalty_of(account_id: Address, lock_id: U64) -> U128 {
+        let contract = Self::load();
+
+        let lock = contract
+            .locks
+            .get(&account_id)
+            .and_then(|locks| locks.locks.get(&lock_id.0))
+            .expect("Lock not found");
+        contract
+            .early_unlock_penalty(lock, env::block_timestamp())
+            .into()
+    }
+
+    pub(crate) fn has_locks(&self, account_id: &Address) -> bool {
+        self.locks
+            .get(account_id)
+            .map_or(false, |locks| !locks.locks.is_empty())
+    }
+
+    fn early_unlock_penalty(&self, lock: &TokenLock, now: u128) -> u128 {
+        if now >= lock.unlock_at {
+            return 0;
+        }
+        Self::bps_of(lock.amount, self.lock_penalty.penalty_bps)
+    }
+}
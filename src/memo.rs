@@ -0,0 +1,103 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+
+use crate::account_id::AccountId;
+use crate::env::caller_address;
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MAX_MEMO_LEN: usize = 256;
+const MAX_REFERENCE_ID_LEN: usize = 64;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Makes plain transfers into the caller fail, so every deposit carries a memo.
+    pub fn set_memo_required(required: bool) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+
+        if required {
+            contract.memo_required.insert(account_id, true);
+        } else {
+            contract.memo_required.remove(account_id);
+        }
+
+        contract.save();
+    }
+
+    pub fn is_memo_required(account_id: Address) -> bool {
+        let contract = Self::load();
+        contract.memo_required.contains_key(&account_id)
+    }
+
+    pub fn ft_transfer_with_memo(
+        recipient_id: AccountId,
+        amount: U128,
+        memo: String,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        assert!(!memo.is_empty(), "Memo should not be empty");
+        assert!(
+            memo.len() <= MAX_MEMO_LEN,
+            "Memo is longer than {MAX_MEMO_LEN} bytes"
+        );
+        let mut contract = Self::load();
+        let sender_id = caller_address();
+        if contract.replayed_idempotency_key(
+            &sender_id,
+            idempotency_key,
+            &sender_id,
+            &recipient_id,
+            amount.0,
+        ) {
+            contract.release();
+            return;
+        }
+
+        contract.transfer_or_hold(&sender_id, &recipient_id, amount.0);
+        FtEvent::TransferMemo {
+            sender_id,
+            recipient_id: *recipient_id,
+            amount: amount.0,
+            memo,
+        }
+        .emit();
+        Self::emit_transfer_reference(&sender_id, &recipient_id, amount.0, reference_id);
+
+        contract.save();
+    }
+
+    /// Echoes an off-chain reference for the transfer in an event. It isn't stored.
+    pub(crate) fn emit_transfer_reference(
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        reference_id: Option<String>,
+    ) {
+        let Some(reference_id) = reference_id else {
+            return;
+        };
+        assert!(!reference_id.is_empty(), "Reference id should not be empty");
+        assert!(
+            reference_id.len() <= MAX_REFERENCE_ID_LEN,
+            "Reference id is longer than {MAX_REFERENCE_ID_LEN} bytes"
+        );
+        FtEvent::TransferReference {
+            sender_id: *sender_id,
+            recipient_id: *recipient_id,
+            amount,
+            reference_id,
+        }
+        .emit();
+    }
+
+    pub(crate) fn assert_memo_not_required(&self, recipient_id: &Address) {
+        assert!(
+            !self.memo_required.contains_key(recipient_id),
+            "'{}' only accepts transfers with a memo",
+            recipient_id
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::timelock::TimelockOperation;
+use crate::L1xFtErc20;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct MintBreakerConfig {
+    limit: U128,
+    window: U64,
+}
+
+/// Caps what authorized minters can mint within a rolling window.
+///
+/// A mint over the cap is dropped and trips the breaker. Minting stays off until a timelocked reset.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct MintBreaker {
+    config: Option<MintBreakerConfig>,
+    recent_mints: VecDeque<(u128, u128)>,
+    tripped: bool,
+}
+
+impl MintBreaker {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        if let Some(config) = &mut self.config {
+            config.limit = rescale(config.limit.0).into();
+        }
+        for (_, amount) in &mut self.recent_mints {
+            *amount = rescale(*amount);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MintBreakerView {
+    config: Option<MintBreakerConfig>,
+    minted_in_window: U128,
+    tripped: bool,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `None` disables the breaker.
+    pub fn set_mint_breaker(config: Option<MintBreakerConfig>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if let Some(config) = &config {
+            assert_ne!(config.window.0, 0, "Window should be greater than 0");
+        }
+
+        contract.mint_breaker.config = config;
+
+        contract.save();
+    }
+
+    pub fn mint_breaker() -> MintBreakerView {
+        let contract = Self::load();
+        MintBreakerView {
+            config: contract.mint_breaker.config.clone(),
+            minted_in_window: contract.minted_in_window().into(),
+            tripped: contract.mint_breaker.tripped,
+        }
+    }
+
+    /// Schedules re-enabling minting after a trip. It is executed with `timelock_execute`.
+    pub fn resume_minting() -> U64 {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(contract.mint_breaker.tripped, "Minting is not paused");
+
+        let operation_id = contract.timelock_schedule(TimelockOperation::ResumeMinting);
+
+        contract.save();
+        operation_id
+    }
+
+    pub(crate) fn resume_minting_now(&mut self) {
+        self.mint_breaker.tripped = false;
+        self.mint_breaker.recent_mints.clear();
+        l1x_sdk::msg("Minting resumed");
+    }
+
+    /// Records a minter's mint. Returns false, and trips the breaker, if it would go over the window limit.
+    pub(crate) fn record_minter_mint(&mut self, amount: u128) -> bool {
+        assert!(
+            !self.mint_breaker.tripped,
+            "Minting is paused by the circuit breaker"
+        );
+        let Some(config) = self.mint_breaker.config.clone() else {
+            return true;
+        };
+
+        let minted = self.minted_in_window();
+        let window_start = env::block_timestamp().saturating_sub(u128::from(config.window.0));
+        self.mint_breaker
+            .recent_mints
+            .retain(|(minted_at, _)| *minted_at > window_start);
+
+        if minted.saturating_add(amount) > config.limit.0 {
+            self.mint_breaker.tripped = true;
+            FtEvent::MintBreakerTripped {
+                attempted: amount,
+                minted_in_window: minted,
+                limit: config.limit.0,
+            }
+            .emit();
+            return false;
+        }
+        self.mint_breaker
+            .recent_mints
+            .push_back((env::block_timestamp(), amount));
+        true
+    }
+
+    fn minted_in_window(&self) -> u128 {
+        let Some(config) = &self.mint_breaker.config else {
+            return 0;
+        };
+        let window_start = env::block_timestamp().saturating_sub(u128::from(config.window.0));
+        self.mint_breaker
+            .recent_mints
+            .iter()
+            .filter(|(minted_at, _)| *minted_at > window_start)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+}
@@ -0,0 +1,160 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::roles::Role;
+use crate::{FTMetadata, L1xFtErc20};
+
+/// Id of the contract's own token, served by the existing `ft_*` state.
+const BASE_TOKEN_ID: u64 = 0;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct SubToken {
+    metadata: FTMetadata,
+    total_supply: u128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn mt_create(token_id: U64, metadata: FTMetadata) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(token_id.0, BASE_TOKEN_ID, "Token id 0 is the base token");
+        assert!(
+            !contract.sub_tokens.contains_key(&token_id.0),
+            "The token already exists"
+        );
+        assert!(metadata.decimals <= 18, "Invalid decimals");
+
+        contract.sub_tokens.insert(
+            token_id.0,
+            SubToken {
+                metadata,
+                total_supply: 0,
+            },
+        );
+        l1x_sdk::msg(&format!("Token {} created", token_id.0));
+
+        contract.save();
+    }
+
+    pub fn mt_metadata(token_id: U64) -> FTMetadata {
+        let contract = Self::load();
+        if token_id.0 == BASE_TOKEN_ID {
+            return contract.metadata.into_inner();
+        }
+        contract.sub_token(token_id.0).metadata.clone()
+    }
+
+    pub fn mt_total_supply(token_id: U64) -> U128 {
+        let contract = Self::load();
+        if token_id.0 == BASE_TOKEN_ID {
+            return (*contract.total_supply).into();
+        }
+        contract.sub_token(token_id.0).total_supply.into()
+    }
+
+    pub fn mt_balance_of(token_id: U64, account_id: Address) -> U128 {
+        let contract = Self::load();
+        contract.mt_balance(token_id.0, &account_id).into()
+    }
+
+    pub fn mt_mint(token_id: U64, recipient_id: AccountId, amount: U128) {
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        assert!(
+            contract.has_role(Role::Minter, &caller_address()),
+            "Only authorized caller can mint tokens"
+        );
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+
+        if token_id.0 == BASE_TOKEN_ID {
+            contract.assert_unbacked_mint_allowed();
+            if contract.record_minter_mint(amount.0) {
+                contract.mint(&recipient_id, amount.0);
+            }
+        } else {
+            let mut token = contract.sub_token(token_id.0).clone();
+            token.total_supply = token
+                .total_supply
+                .checked_add(amount.0)
+                .expect("total_supply is overflowed");
+            contract.sub_tokens.insert(token_id.0, token);
+
+            let balance = contract.mt_balance(token_id.0, &recipient_id);
+            contract.sub_balances.insert(
+                (token_id.0, *recipient_id),
+                balance.checked_add(amount.0).expect("Balance overflowed"),
+            );
+            FtEvent::MtMint {
+                token_id: token_id.0,
+                recipient_id: *recipient_id,
+                amount: amount.0,
+            }
+            .emit();
+        }
+
+        contract.save();
+    }
+
+    pub fn mt_transfer(token_id: U64, recipient_id: AccountId, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        let sender_id = caller_address();
+
+        if token_id.0 == BASE_TOKEN_ID {
+            contract.assert_memo_not_required(&recipient_id);
+            contract.transfer_or_hold(&sender_id, &recipient_id, amount.0);
+        } else {
+            assert!(
+                contract.sub_tokens.contains_key(&token_id.0),
+                "Token not found"
+            );
+            assert_ne!(sender_id, *recipient_id, "Self transfer is not allowed");
+
+            let sender_balance = contract.mt_balance(token_id.0, &sender_id);
+            assert!(sender_balance >= amount.0, "Not enough balance to transfer");
+            contract
+                .sub_balances
+                .insert((token_id.0, sender_id), sender_balance - amount.0);
+            let receiver_balance = contract.mt_balance(token_id.0, &recipient_id);
+            contract.sub_balances.insert(
+                (token_id.0, *recipient_id),
+                receiver_balance
+                    .checked_add(amount.0)
+                    .expect("Balance overflowed"),
+            );
+            FtEvent::MtTransfer {
+                token_id: token_id.0,
+                sender_id,
+                recipient_id: *recipient_id,
+                amount: amount.0,
+            }
+            .emit();
+        }
+
+        contract.save();
+    }
+
+    fn sub_token(&self, token_id: u64) -> &SubToken {
+        self.sub_tokens.get(&token_id).expect("Token not found")
+    }
+
+    fn mt_balance(&self, token_id: u64, account_id: &Address) -> u128 {
+        if token_id == BASE_TOKEN_ID {
+            return self.balance_of(account_id).unwrap_or_default();
+        }
+        self.sub_balances
+            .get(&(token_id, *account_id))
+            .copied()
+            .unwrap_or_default()
+    }
+}
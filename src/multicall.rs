@@ -0,0 +1,115 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::{lazy, limits, L1xFtErc20};
+
+const MAX_MULTICALL_LEN: usize = 20;
+
+/// An entrypoint `ft_multicall` can run, with its arguments.
+#[derive(Serialize, Deserialize)]
+pub enum CallSpec {
+    Transfer {
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    },
+    TransferFrom {
+        sender_id: Address,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    },
+    TransferWithMemo {
+        recipient_id: AccountId,
+        amount: U128,
+        memo: String,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    },
+    Approve {
+        spender_id: AccountId,
+        amount: U128,
+    },
+    IncreaseAllowance {
+        spender_id: AccountId,
+        amount: U128,
+    },
+    DecreaseAllowance {
+        spender_id: AccountId,
+        amount: U128,
+    },
+    Multisend {
+        transfers: Vec<(AccountId, U128)>,
+    },
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Runs the calls in order as the caller. If any of them fails, none of them take effect.
+    /// The caller's transfer cooldown applies to the whole multicall. The state is loaded by the
+    /// first call and written once, after the last.
+    pub fn ft_multicall(calls: Vec<CallSpec>) {
+        assert!(!calls.is_empty(), "Nothing to call");
+        assert!(
+            calls.len() <= MAX_MULTICALL_LEN,
+            "At most {MAX_MULTICALL_LEN} calls can be made at once"
+        );
+
+        lazy::call_scope(|| {
+            limits::transfer_batch(|| {
+                for call in calls {
+                    Self::execute_call(call);
+                }
+            })
+        });
+    }
+
+    fn execute_call(call: CallSpec) {
+        match call {
+            CallSpec::Transfer {
+                recipient_id,
+                amount,
+                idempotency_key,
+                reference_id,
+            } => Self::ft_transfer(recipient_id, amount, idempotency_key, reference_id),
+            CallSpec::TransferFrom {
+                sender_id,
+                recipient_id,
+                amount,
+                idempotency_key,
+                reference_id,
+            } => Self::ft_transfer_from(
+                sender_id,
+                recipient_id,
+                amount,
+                idempotency_key,
+                reference_id,
+            ),
+            CallSpec::TransferWithMemo {
+                recipient_id,
+                amount,
+                memo,
+                idempotency_key,
+                reference_id,
+            } => Self::ft_transfer_with_memo(
+                recipient_id,
+                amount,
+                memo,
+                idempotency_key,
+                reference_id,
+            ),
+            CallSpec::Approve { spender_id, amount } => Self::ft_approve(spender_id, amount),
+            CallSpec::IncreaseAllowance { spender_id, amount } => {
+                Self::ft_increase_allowance(spender_id, amount)
+            }
+            CallSpec::DecreaseAllowance { spender_id, amount } => {
+                Self::ft_decrease_allowance(spender_id, amount)
+            }
+            CallSpec::Multisend { transfers } => Self::ft_multisend(transfers),
+        }
+    }
+}
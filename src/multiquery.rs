@@ -0,0 +1,72 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::{FTMetadata, L1xFtErc20};
+
+const MAX_MULTIQUERY_LEN: usize = 100;
+
+#[derive(Serialize, Deserialize)]
+pub enum QuerySpec {
+    Balance {
+        account_id: Address,
+    },
+    Allowance {
+        owner_id: Address,
+        spender_id: Address,
+    },
+    Metadata,
+    TotalSupply,
+    Frozen {
+        account_id: Address,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum QueryResult {
+    Balance(U128),
+    Allowance(U128),
+    Metadata(FTMetadata),
+    TotalSupply(U128),
+    Frozen { frozen: bool, amount: U128 },
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Answers several views against the same state in one call. Results are in query order.
+    pub fn ft_multiquery(queries: Vec<QuerySpec>) -> Vec<QueryResult> {
+        assert!(
+            queries.len() <= MAX_MULTIQUERY_LEN,
+            "At most {MAX_MULTIQUERY_LEN} queries can be made at once"
+        );
+        let contract = Self::load();
+
+        queries
+            .into_iter()
+            .map(|query| match query {
+                QuerySpec::Balance { account_id } => QueryResult::Balance(
+                    contract.balance_of(&account_id).unwrap_or_default().into(),
+                ),
+                QuerySpec::Allowance {
+                    owner_id,
+                    spender_id,
+                } => QueryResult::Allowance(
+                    contract
+                        .allowances
+                        .get(&owner_id)
+                        .map_or(0, |allowance| allowance.get(&spender_id))
+                        .into(),
+                ),
+                QuerySpec::Metadata => QueryResult::Metadata((*contract.metadata).clone()),
+                QuerySpec::TotalSupply => QueryResult::TotalSupply((*contract.total_supply).into()),
+                QuerySpec::Frozen { account_id } => {
+                    let amount = contract.frozen_amount(&account_id);
+                    QueryResult::Frozen {
+                        frozen: amount != 0,
+                        amount: amount.into(),
+                    }
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::{limits, L1xFtErc20};
+
+pub(crate) const DEFAULT_MAX_MULTISEND_BATCH: u32 = 100;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sends a different amount to each recipient. Repeated recipients are merged into one transfer.
+    /// The sender's transfer cooldown applies to the batch as a whole.
+    pub fn ft_multisend(transfers: Vec<(AccountId, U128)>) {
+        assert!(!transfers.is_empty(), "Nothing to send");
+        let mut contract = Self::load();
+        let sender_id = caller_address();
+
+        let mut merged: BTreeMap<Address, u128> = BTreeMap::new();
+        for (recipient_id, amount) in transfers {
+            assert_ne!(amount.0, 0, "Amount should be greater than 0");
+            let total = merged.entry(*recipient_id).or_default();
+            *total = total.checked_add(amount.0).expect("Amount overflowed");
+        }
+        assert!(
+            merged.len() <= contract.max_multisend_batch as usize,
+            "Batch exceeds the max size of {} recipients",
+            contract.max_multisend_batch
+        );
+
+        let total = merged
+            .values()
+            .try_fold(0u128, |total, amount| total.checked_add(*amount))
+            .expect("Total amount overflowed");
+        assert!(
+            contract.spendable_balance(&sender_id) >= total,
+            "Not enough balance to transfer"
+        );
+
+        limits::transfer_batch(|| {
+            for (recipient_id, amount) in &merged {
+                contract.assert_memo_not_required(recipient_id);
+                contract.transfer_or_hold(&sender_id, recipient_id, *amount);
+            }
+        });
+        FtEvent::Multisend {
+            sender_id,
+            recipients: merged.len() as u32,
+            total,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn set_max_multisend_batch(max_recipients: u32) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(max_recipients, 0, "Batch size should be greater than 0");
+
+        contract.max_multisend_batch = max_recipients;
+
+        contract.save();
+    }
+
+    pub fn max_multisend_batch() -> u32 {
+        let contract = Self::load();
+        contract.max_multisend_batch
+    }
+}
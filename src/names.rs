@@ -0,0 +1,191 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MIN_NAME_LEN: usize = 3;
+const MAX_NAME_LEN: usize = 32;
+
+/// Registration terms. The fee is paid to the treasury for each registration and renewal, and a
+/// name that isn't renewed within `ttl` can be taken by anyone.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct NameRegistryConfig {
+    fee: U128,
+    ttl: U64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct NameRecord {
+    owner_id: Address,
+    expires_at: U128,
+}
+
+impl NameRegistryConfig {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        self.fee = rescale(self.fee.0).into();
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `None` closes the registry to new registrations and renewals. Registered names keep
+    /// resolving until they expire.
+    pub fn set_name_registry(config: Option<NameRegistryConfig>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if let Some(config) = &config {
+            assert_ne!(config.ttl.0, 0, "TTL should be greater than 0");
+        }
+
+        *contract.name_registry = config;
+
+        contract.save();
+    }
+
+    pub fn name_registry() -> Option<NameRegistryConfig> {
+        let contract = Self::load();
+        contract.name_registry.into_inner()
+    }
+
+    /// Names are 3 to 32 characters of lowercase letters, digits and `-`.
+    pub fn register_name(name: String) {
+        assert_valid_name(&name);
+        let mut contract = Self::load();
+        let account_id = caller_address();
+        let now = env::block_timestamp();
+        if let Some(record) = contract.names.get(&name) {
+            assert!(now >= record.expires_at.0, "The name is already registered");
+        }
+
+        let expires_at = contract.charge_name_fee(&account_id, now);
+        contract.names.insert(
+            name.clone(),
+            NameRecord {
+                owner_id: account_id,
+                expires_at: expires_at.into(),
+            },
+        );
+        FtEvent::NameRegistered {
+            name,
+            owner_id: account_id,
+            expires_at,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Extends the name by another TTL from its current expiry.
+    pub fn renew_name(name: String) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+        let record = contract.active_name(&name);
+        assert_eq!(
+            account_id, record.owner_id,
+            "Only the name's owner can renew it"
+        );
+
+        let expires_at = contract.charge_name_fee(&account_id, record.expires_at.0);
+        contract.names.insert(
+            name.clone(),
+            NameRecord {
+                owner_id: account_id,
+                expires_at: expires_at.into(),
+            },
+        );
+        FtEvent::NameRegistered {
+            name,
+            owner_id: account_id,
+            expires_at,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn release_name(name: String) {
+        let mut contract = Self::load();
+        let record = contract.active_name(&name);
+        assert_eq!(
+            caller_address(),
+            record.owner_id,
+            "Only the name's owner can release it"
+        );
+
+        contract.names.remove(name);
+
+        contract.save();
+    }
+
+    pub fn resolve_name(name: String) -> Option<Address> {
+        let contract = Self::load();
+        let record = contract.names.get(&name)?;
+        (env::block_timestamp() < record.expires_at.0).then_some(record.owner_id)
+    }
+
+    pub fn name_record(name: String) -> Option<NameRecord> {
+        let contract = Self::load();
+        contract.names.get(&name).cloned()
+    }
+
+    pub fn ft_transfer_to_name(name: String, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let sender_id = caller_address();
+
+        let recipient_id = contract.active_name(&name).owner_id;
+        contract.assert_memo_not_required(&recipient_id);
+        contract.transfer_or_hold(&sender_id, &recipient_id, amount.0);
+
+        contract.save();
+    }
+
+    fn active_name(&self, name: &String) -> NameRecord {
+        let record = self.names.get(name).cloned().expect("Name not found");
+        assert!(
+            env::block_timestamp() < record.expires_at.0,
+            "The name has expired"
+        );
+        record
+    }
+
+    /// Moves the fee from `account_id` to the treasury and returns the expiry one TTL after `from`.
+    fn charge_name_fee(&mut self, account_id: &Address, from: u128) -> u128 {
+        let config = self
+            .name_registry
+            .clone()
+            .expect("The name registry is not enabled");
+
+        if config.fee.0 > 0 {
+            let balance = self.balance_of(account_id).unwrap_or_default();
+            assert!(
+                self.spendable_balance(account_id) >= config.fee.0,
+                "Not enough balance to pay the name fee"
+            );
+            self.set_balance(account_id, balance - config.fee.0);
+            self.treasury_credit(config.fee.0);
+        }
+        from.checked_add(config.ttl.0.into())
+            .expect("Name expiry overflowed")
+    }
+}
+
+fn assert_valid_name(name: &str) {
+    assert!(
+        (MIN_NAME_LEN..=MAX_NAME_LEN).contains(&name.len()),
+        "Names should be {MIN_NAME_LEN} to {MAX_NAME_LEN} characters long"
+    );
+    assert!(
+        name.bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-'),
+        "Names can only contain lowercase letters, digits and '-'"
+    );
+}
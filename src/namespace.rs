@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+#[cfg(feature = "factory")]
+use l1x_sdk::types::U64;
+use serde_json::Value;
+
+use crate::env;
+#[cfg(feature = "factory")]
+use crate::factory;
+use crate::L1xFtErc20;
+
+/// Argument any call can add to its JSON to act on the instance initialized under that namespace.
+const NAMESPACE_ARG: &str = "_namespace";
+/// Argument any call can add to its JSON to act on a token created by the factory.
+const TOKEN_ID_ARG: &str = "_token_id";
+
+thread_local! {
+    /// Namespace the current call is bound to, if any. Takes precedence over the call arguments.
+    static ACTIVE_NAMESPACE: RefCell<Option<Vec<u8>>> = RefCell::new(None);
+}
+
+/// Drops the binding a failed call left behind. The host reverts the whole transaction instead.
+#[cfg(test)]
+pub(crate) fn unbind_namespace() {
+    ACTIVE_NAMESPACE.with(|active| active.take());
+}
+
+/// Namespace of the token instance the current call acts on. Empty for the root token.
+pub(crate) fn active_namespace() -> Vec<u8> {
+    ACTIVE_NAMESPACE
+        .with(|namespace| namespace.borrow().clone())
+        .unwrap_or_else(call_namespace)
+}
+
+/// The namespace named by the `_token_id` or `_namespace` argument of the call, empty without
+/// one.
+fn call_namespace() -> Vec<u8> {
+    let Some(args) = env::input().and_then(|input| serde_json::from_slice::<Value>(&input).ok())
+    else {
+        return Vec::new();
+    };
+    match (args.get(TOKEN_ID_ARG), args.get(NAMESPACE_ARG)) {
+        (None, None) => Vec::new(),
+        (Some(token_id), None) => token_id_namespace(token_id),
+        (None, Some(namespace)) => {
+            let namespace: String = serde_json::from_value(namespace.clone())
+                .expect("The namespace should be a string");
+            L1xFtErc20::assert_valid_namespace(&namespace);
+            namespace.into_bytes()
+        }
+        (Some(_), Some(_)) => panic!("A call can't name both a token id and a namespace"),
+    }
+}
+
+#[cfg(feature = "factory")]
+fn token_id_namespace(token_id: &Value) -> Vec<u8> {
+    let token_id: U64 =
+        serde_json::from_value(token_id.clone()).expect("The token id should be a U64 string");
+    factory::assert_token_exists(token_id);
+    factory::token_namespace(token_id.0)
+}
+
+/// Without the factory no token ids exist.
+#[cfg(not(feature = "factory"))]
+fn token_id_namespace(_token_id: &Value) -> Vec<u8> {
+    panic!("Token not found")
+}
+
+impl L1xFtErc20 {
+    /// Runs `f` with `load` and `save` bound to the token instance under `namespace`.
+    pub(crate) fn with_namespace<R>(namespace: Vec<u8>, f: impl FnOnce() -> R) -> R {
+        let previous = ACTIVE_NAMESPACE.with(|active| active.replace(Some(namespace)));
+        let result = f();
+        ACTIVE_NAMESPACE.with(|active| active.replace(previous));
+        result
+    }
+}
@@ -0,0 +1,119 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::decimals;
+use crate::env::{caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// `None` on either side of a pair matches any account.
+pub(crate) type PairKey = (Option<Address>, Option<Address>);
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub enum PairPolicy {
+    Allow,
+    Deny,
+    Limit { max_amount: U128 },
+}
+
+/// A policy with the token decimals its limit is in. Policies aren't enumerable, so a decimals
+/// migration leaves them be and they are rescaled when read.
+pub(crate) type StampedPairPolicy = (PairPolicy, u8);
+
+impl PairPolicy {
+    fn rescaled(&self, from: u8, to: u8) -> PairPolicy {
+        match self {
+            PairPolicy::Limit { max_amount } => PairPolicy::Limit {
+                max_amount: decimals::rescale(from, to, max_amount.0).into(),
+            },
+            policy => policy.clone(),
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sets the policy for transfers from `sender_id` to `recipient_id`. It is enforced by the
+    /// `PairPolicy` transfer rule, where the most specific matching pair wins.
+    pub fn set_pair_policy(
+        sender_id: Option<Address>,
+        recipient_id: Option<Address>,
+        policy: PairPolicy,
+    ) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.pair_policies.insert(
+            (sender_id, recipient_id),
+            (policy, contract.metadata.decimals),
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_pair_policy(sender_id: Option<Address>, recipient_id: Option<Address>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract
+                .pair_policies
+                .remove((sender_id, recipient_id))
+                .is_some(),
+            "This pair has no policy"
+        );
+
+        contract.save();
+    }
+
+    pub fn pair_policy(
+        sender_id: Option<Address>,
+        recipient_id: Option<Address>,
+    ) -> Option<PairPolicy> {
+        let contract = Self::load();
+        contract
+            .pair_policies
+            .get(&(sender_id, recipient_id))
+            .map(|(policy, decimals)| policy.rescaled(*decimals, contract.metadata.decimals))
+    }
+
+    /// Checks the exact pair first, then the sender with any recipient, any sender with the
+    /// recipient, and finally the catch-all pair.
+    pub(crate) fn pair_policy_violation(
+        &self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> Option<String> {
+        let (policy, decimals) = [
+            (Some(*sender_id), Some(*recipient_id)),
+            (Some(*sender_id), None),
+            (None, Some(*recipient_id)),
+            (None, None),
+        ]
+        .iter()
+        .find_map(|pair| self.pair_policies.get(pair))?;
+
+        match policy.rescaled(*decimals, self.metadata.decimals) {
+            PairPolicy::Allow => None,
+            PairPolicy::Deny => Some(format!(
+                "Transfers from '{}' to '{}' are not allowed",
+                sender_id, recipient_id
+            )),
+            PairPolicy::Limit { max_amount } => (amount > max_amount.0).then(|| {
+                format!(
+                    "Transfers from '{}' to '{}' are limited to {}",
+                    sender_id, recipient_id, max_amount.0
+                )
+            }),
+        }
+    }
+}
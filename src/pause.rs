@@ -0,0 +1,75 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{U128, U64};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// `paused_until` value of an indefinite pause.
+const PAUSED_INDEFINITELY: u128 = u128::MAX;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Blocks every balance change until `unpause` is called.
+    pub fn pause() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.paused_until = Some(PAUSED_INDEFINITELY);
+        l1x_sdk::msg("Contract paused");
+
+        contract.save();
+    }
+
+    /// Pauses for `duration`. The pause lifts by itself, even if the owner key is lost.
+    pub fn pause_for(duration: U64) {
+        assert_ne!(duration.0, 0, "Duration should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let until = env::block_timestamp()
+            .checked_add(duration.0.into())
+            .expect("Pause end overflowed");
+        contract.paused_until = Some(until);
+        l1x_sdk::msg(&format!("Contract paused until {}", until));
+
+        contract.save();
+    }
+
+    pub fn unpause() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.paused_until = None;
+        l1x_sdk::msg("Contract unpaused");
+
+        contract.save();
+    }
+
+    /// End of the current pause, `u128::MAX` for an indefinite one. `None` when not paused.
+    pub fn paused_until() -> Option<U128> {
+        let contract = Self::load();
+        contract
+            .paused_until
+            .filter(|until| env::block_timestamp() < *until)
+            .map(Into::into)
+    }
+
+    pub(crate) fn assert_not_paused(&self) {
+        if let Some(until) = self.paused_until {
+            assert!(env::block_timestamp() >= until, "The contract is paused");
+        }
+        self.assert_no_decimals_migration();
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MAX_REFLECTION_BPS: u16 = 1_000;
+
+/// Share accounting for holders that take part in reflection.
+///
+/// Balances of included accounts are stored as shares of `pool`. A reflection fee is added to
+/// `pool` without minting shares, so every included holder's balance grows pro rata without
+/// touching their entries. Until the first fee `pool == total_shares` and shares equal amounts.
+/// Excluded accounts store plain amounts.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct ReflectionState {
+    reflection_bps: u16,
+    pool: u128,
+    total_shares: u128,
+    excluded: BTreeSet<Address>,
+}
+
+impl ReflectionState {
+    pub(crate) fn is_excluded(&self, account_id: &Address) -> bool {
+        self.excluded.contains(account_id)
+    }
+
+    pub(crate) fn amount_of(&self, shares: u128) -> u128 {
+        if shares == 0 {
+            return 0;
+        }
+        (U256::from(shares) * U256::from(self.pool) / U256::from(self.total_shares)).as_u128()
+    }
+
+    /// Replaces `old_shares` with shares worth `amount` and returns the new shares.
+    pub(crate) fn reshare(&mut self, old_shares: u128, amount: u128) -> u128 {
+        let old_amount = self.amount_of(old_shares);
+        self.pool -= old_amount;
+        self.total_shares -= old_shares;
+
+        let shares = if self.total_shares == 0 || self.pool == 0 {
+            amount
+        } else {
+            (U256::from(amount) * U256::from(self.total_shares) / U256::from(self.pool)).as_u128()
+        };
+        self.pool = self
+            .pool
+            .checked_add(amount)
+            .expect("Reflection pool overflowed");
+        self.total_shares = self
+            .total_shares
+            .checked_add(shares)
+            .expect("Reflection shares overflowed");
+        shares
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReflectionInfo {
+    reflection_bps: u16,
+    pool: U128,
+    total_shares: U128,
+    excluded: Vec<Address>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_reflection_fee(reflection_bps: u16) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            reflection_bps <= MAX_REFLECTION_BPS,
+            "Reflection fee can't exceed {MAX_REFLECTION_BPS} bps"
+        );
+
+        contract.reflection.reflection_bps = reflection_bps;
+
+        contract.save();
+    }
+
+    pub fn exclude_from_reflection(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            !contract.reflection.is_excluded(&account_id),
+            "This address is already excluded"
+        );
+
+        let shares = contract
+            .balances
+            .get(&account_id)
+            .copied()
+            .unwrap_or_default();
+        let amount = contract.reflection.amount_of(shares);
+        contract.reflection.reshare(shares, 0);
+        contract.reflection.excluded.insert(account_id);
+        contract.balances.insert(account_id, amount);
+
+        contract.save();
+    }
+
+    pub fn include_in_reflection(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.reflection.excluded.remove(&account_id),
+            "This address is not excluded"
+        );
+
+        let amount = contract
+            .balances
+            .get(&account_id)
+            .copied()
+            .unwrap_or_default();
+        let shares = contract.reflection.reshare(0, amount);
+        contract.balances.insert(account_id, shares);
+
+        contract.save();
+    }
+
+    pub fn reflection_info() -> ReflectionInfo {
+        let contract = Self::load();
+        ReflectionInfo {
+            reflection_bps: contract.reflection.reflection_bps,
+            pool: contract.reflection.pool.into(),
+            total_shares: contract.reflection.total_shares.into(),
+            excluded: contract.reflection.excluded.iter().copied().collect(),
+        }
+    }
+
+    /// Distributes the reflection fee on `amount` to included holders and returns the fee.
+    ///
+    /// The caller is expected to have already debited `amount` from the sender.
+    pub(crate) fn apply_reflection_fee(&mut self, sender_id: &Address, amount: u128) -> u128 {
+        let reflection_bps = self.reflection.reflection_bps;
+        if reflection_bps == 0 || self.reflection.total_shares == 0 {
+            return 0;
+        }
+
+        let fee = Self::bps_of(amount, reflection_bps);
+        if fee > 0 {
+            self.reflection.pool = self
+                .reflection
+                .pool
+                .checked_add(fee)
+                .expect("Reflection pool overflowed");
+            self.mark_all_balance_leaves_dirty();
+            FtEvent::Reflection {
+                sender_id: *sender_id,
+                amount: fee,
+            }
+            .emit();
+        }
+        fee
+    }
+}
@@ -0,0 +1,162 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::decimals;
+use crate::env::{self, caller_address};
+use crate::events::{self, FtEvent};
+use crate::{L1xFtErc20, TransferKind};
+
+const MAX_REFUND_REASON_LEN: usize = 256;
+/// How long a transfer can be refunded. Thirty days, in seconds.
+const REFUND_WINDOW: u64 = 30 * 86_400;
+/// Records are kept in a ring of this many slots, so the newest transfers overwrite the oldest.
+const MAX_TRANSFER_RECORDS: u64 = 65_536;
+
+/// A transfer as it was received, identified by the nonce of its `Transfer` event.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TransferRecord {
+    event_nonce: U64,
+    recorded_at: U128,
+    sender_id: Address,
+    recipient_id: Address,
+    amount: U128,
+    refunded: U128,
+    /// The token decimals the amounts are in. Records aren't enumerable, so a decimals migration
+    /// leaves them be and they are rescaled when read.
+    decimals: u8,
+}
+
+impl TransferRecord {
+    fn rescale_to(&mut self, decimals: u8) {
+        self.amount = decimals::rescale(self.decimals, decimals, self.amount.0).into();
+        self.refunded = decimals::rescale(self.decimals, decimals, self.refunded.0).into();
+        self.decimals = decimals;
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sends up to the received amount of an earlier transfer back to its sender. Only the
+    /// recipient of that transfer can refund it, within `REFUND_WINDOW` of receiving it.
+    ///
+    /// The refund pays no fees, so the sender gets back exactly `amount`, and can't be refunded
+    /// itself. It can be held by the recipient's withdrawal protection like any other payout.
+    pub fn refund(original_event_nonce: U64, amount: U128, reason: String) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        assert!(
+            reason.len() <= MAX_REFUND_REASON_LEN,
+            "Reason is longer than {MAX_REFUND_REASON_LEN} bytes"
+        );
+        let mut contract = Self::load();
+        let merchant_id = caller_address();
+
+        let mut record = contract
+            .live_transfer_record(original_event_nonce.0)
+            .expect("Transfer not found or the refund window has passed");
+        assert_eq!(
+            merchant_id, record.recipient_id,
+            "Only the recipient can refund a transfer"
+        );
+        let refunded = record
+            .refunded
+            .0
+            .checked_add(amount.0)
+            .expect("Amount overflowed");
+        assert!(
+            refunded <= record.amount.0,
+            "Amount exceeds what is left to refund"
+        );
+        record.refunded = refunded.into();
+        let customer_id = record.sender_id;
+        contract
+            .transfer_records
+            .insert(original_event_nonce.0 % MAX_TRANSFER_RECORDS, record);
+
+        contract.transfer_or_hold_as(
+            &merchant_id,
+            &customer_id,
+            amount.0,
+            TransferKind::Refund {
+                original_event_nonce,
+            },
+        );
+        FtEvent::Refund {
+            original_event_nonce: original_event_nonce.0,
+            sender_id: merchant_id,
+            recipient_id: customer_id,
+            amount: amount.0,
+            reason,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn transfer_record(event_nonce: U64) -> Option<TransferRecord> {
+        let contract = Self::load();
+        contract.live_transfer_record(event_nonce.0)
+    }
+
+    /// Emits the `Transfer` event and, if it can be refunded, keeps a record of it.
+    pub(crate) fn record_transfer(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        refundable: bool,
+    ) {
+        let event_nonce = self.event_nonce + events::pending_events();
+        FtEvent::Transfer {
+            event_nonce,
+            sender_id: *sender_id,
+            recipient_id: *recipient_id,
+            amount,
+        }
+        .emit();
+        if !refundable {
+            return;
+        }
+        self.transfer_records.insert(
+            event_nonce % MAX_TRANSFER_RECORDS,
+            TransferRecord {
+                event_nonce: event_nonce.into(),
+                recorded_at: env::block_timestamp().into(),
+                sender_id: *sender_id,
+                recipient_id: *recipient_id,
+                amount: amount.into(),
+                refunded: 0.into(),
+                decimals: self.metadata.decimals,
+            },
+        );
+    }
+
+    /// Makes a refund that was held and then cancelled available again.
+    pub(crate) fn release_refund(&mut self, original_event_nonce: u64, amount: u128) {
+        let slot = original_event_nonce % MAX_TRANSFER_RECORDS;
+        let decimals = self.metadata.decimals;
+        let Some(record) = self.transfer_records.get_mut(&slot) else {
+            return;
+        };
+        if record.event_nonce.0 == original_event_nonce {
+            record.rescale_to(decimals);
+            record.refunded = (record.refunded.0 - amount).into();
+        }
+    }
+
+    /// The record of `event_nonce`, unless its slot was overwritten or the refund window passed.
+    fn live_transfer_record(&self, event_nonce: u64) -> Option<TransferRecord> {
+        let mut record = self
+            .transfer_records
+            .get(&(event_nonce % MAX_TRANSFER_RECORDS))
+            .filter(|record| {
+                record.event_nonce.0 == event_nonce
+                    && env::block_timestamp()
+                        < record.recorded_at.0.saturating_add(REFUND_WINDOW.into())
+            })
+            .cloned()?;
+        record.rescale_to(self.metadata.decimals);
+        Some(record)
+    }
+}
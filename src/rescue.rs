@@ -0,0 +1,83 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde_json::json;
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address, contract_instance_address, contract_owner_address};
+use crate::timelock::TimelockOperation;
+use crate::{cross_contract, wrapped_native, L1xFtErc20};
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sends out another token that was mistakenly transferred to this contract's address.
+    pub fn rescue_token(token_id: Address, to: AccountId, amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(
+            token_id,
+            contract_instance_address(),
+            "The contract's own token can't be rescued"
+        );
+        assert_ne!(
+            Some(token_id),
+            contract.underlying,
+            "The wrapped underlying token can't be rescued"
+        );
+
+        cross_contract::call(
+            token_id,
+            "ft_transfer",
+            &json!({
+                "recipient_id": to,
+                "amount": amount,
+            }),
+            false,
+        )
+        .unwrap_or_else(|err| panic!("Failed to rescue the token: {err}"));
+        l1x_sdk::msg(&format!(
+            "Rescued {} of token {} to {}",
+            amount.0, token_id, *to
+        ));
+    }
+
+    /// Schedules sending out native L1X held by the contract. It is executed with `timelock_execute`.
+    pub fn rescue_native(to: AccountId, amount: U128) -> U64 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let operation_id =
+            contract.timelock_schedule(TimelockOperation::RescueNative { to: *to, amount });
+
+        contract.save();
+        operation_id
+    }
+
+    /// Native balance that isn't backing the wrapped tokens of any token instance.
+    pub fn rescuable_native_balance() -> U128 {
+        let contract = Self::load();
+        contract.rescuable_native().into()
+    }
+
+    pub(crate) fn rescue_native_now(&mut self, to: &Address, amount: u128) {
+        assert!(
+            amount <= self.rescuable_native(),
+            "Not enough rescuable native balance"
+        );
+        env::transfer_to(to, amount);
+        l1x_sdk::msg(&format!("Rescued {} native to {}", amount, to));
+    }
+
+    fn rescuable_native(&self) -> u128 {
+        env::contract_instance_balance().saturating_sub(wrapped_native::total_native_reserve())
+    }
+}
@@ -0,0 +1,78 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde_json::json;
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::{cross_contract, L1xFtErc20};
+
+const ATTESTED_RESERVE_METHOD: &str = "attested_reserve";
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sets the contract whose `attested_reserve()` caps `total_supply` on mint. `None` disables the gate.
+    pub fn set_reserve_oracle(oracle_id: Option<Address>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.reserve_oracle = oracle_id;
+        l1x_sdk::msg(&format!("Reserve oracle set to {:?}", oracle_id));
+
+        contract.save();
+    }
+
+    pub fn reserve_oracle() -> Option<Address> {
+        let contract = Self::load();
+        contract.reserve_oracle
+    }
+
+    /// Hard cap on `total_supply` for every mint. `None` removes the cap.
+    pub fn set_max_supply(max_supply: Option<U128>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if let Some(max_supply) = max_supply {
+            assert!(
+                max_supply.0 >= *contract.total_supply,
+                "Max supply can't be below the current total supply"
+            );
+        }
+
+        contract.max_supply = max_supply.map(|max_supply| max_supply.0);
+
+        contract.save();
+    }
+
+    pub fn max_supply() -> Option<U128> {
+        let contract = Self::load();
+        contract.max_supply.map(Into::into)
+    }
+
+    pub(crate) fn assert_within_max_supply(&self, new_total_supply: u128) {
+        if let Some(max_supply) = self.max_supply {
+            assert!(
+                new_total_supply <= max_supply,
+                "Minting would push total_supply above the max supply"
+            );
+        }
+    }
+
+    pub(crate) fn assert_within_reserve(&self, new_total_supply: u128) {
+        let Some(oracle_id) = self.reserve_oracle else {
+            return;
+        };
+
+        let reserve: U128 = cross_contract::view(oracle_id, ATTESTED_RESERVE_METHOD, &json!({}))
+            .unwrap_or_else(|err| panic!("Failed to query the reserve oracle: {err}"));
+        assert!(
+            new_total_supply <= reserve.0,
+            "Minting would push total_supply above the attested reserve"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::env::caller_address;
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+/// Role members live in their own storage entries, so only calls that check a role read them.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum Role {
+    Minter,
+    Slasher,
+    JurisdictionRegistrar,
+    Compliance,
+}
+
+#[derive(BorshSerialize, Clone, Copy)]
+pub(crate) enum RoleAction {
+    Granted,
+    Revoked,
+}
+
+impl Role {
+    pub(crate) const ALL: [Role; 4] = [
+        Role::Minter,
+        Role::Slasher,
+        Role::JurisdictionRegistrar,
+        Role::Compliance,
+    ];
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Role::Minter => "MINTER",
+            Role::Slasher => "SLASHER",
+            Role::JurisdictionRegistrar => "JURISDICTION_REGISTRAR",
+            Role::Compliance => "COMPLIANCE",
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn role_members(role: Role) -> Vec<Address> {
+        let contract = Self::load();
+        contract
+            .roles
+            .get(&role)
+            .map(|members| members.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn has_role(&self, role: Role, account_id: &Address) -> bool {
+        self.roles
+            .get(&role)
+            .map_or(false, |members| members.contains(account_id))
+    }
+
+    /// Returns `false` if the account already had the role.
+    pub(crate) fn grant_role(&mut self, role: Role, account_id: Address) -> bool {
+        let granted = match self.roles.get_mut(&role) {
+            Some(members) => members.insert(account_id),
+            None => {
+                self.roles.insert(role, BTreeSet::from([account_id]));
+                true
+            }
+        };
+        if granted {
+            Self::emit_role_changed(role, RoleAction::Granted, account_id);
+        }
+        granted
+    }
+
+    /// Returns `false` if the account didn't have the role.
+    pub(crate) fn revoke_role(&mut self, role: Role, account_id: &Address) -> bool {
+        let revoked = self
+            .roles
+            .get_mut(&role)
+            .map_or(false, |members| members.remove(account_id));
+        if revoked {
+            Self::emit_role_changed(role, RoleAction::Revoked, *account_id);
+        }
+        revoked
+    }
+
+    fn emit_role_changed(role: Role, action: RoleAction, account_id: Address) {
+        FtEvent::RoleChanged {
+            role,
+            action,
+            grantor_id: caller_address(),
+            account_id,
+        }
+        .emit();
+    }
+}
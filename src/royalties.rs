@@ -0,0 +1,104 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MAX_ROYALTY_BPS: u16 = 1_000;
+
+/// A creator's cut of transfers to or from a marketplace.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct Royalty {
+    creator_id: Address,
+    bps: u16,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_royalty(marketplace_id: Address, creator_id: AccountId, bps: u16) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert_ne!(bps, 0, "Royalty should be greater than 0");
+        assert!(
+            bps <= MAX_ROYALTY_BPS,
+            "Royalty can't exceed {MAX_ROYALTY_BPS} bps"
+        );
+        assert_ne!(
+            marketplace_id, *creator_id,
+            "Marketplace and creator cannot be the same"
+        );
+
+        contract.royalties.insert(
+            marketplace_id,
+            Royalty {
+                creator_id: *creator_id,
+                bps,
+            },
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_royalty(marketplace_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.royalties.remove(marketplace_id).is_some(),
+            "This address has no royalty"
+        );
+
+        contract.save();
+    }
+
+    pub fn royalty_of(marketplace_id: Address) -> Option<Royalty> {
+        let contract = Self::load();
+        contract.royalties.get(&marketplace_id).cloned()
+    }
+
+    /// Credits the creator's royalty when either side is a registered marketplace, and returns it.
+    /// The recipient's royalty wins if both sides are marketplaces.
+    ///
+    /// The caller is expected to have already debited `amount` from the sender.
+    pub(crate) fn apply_royalty(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> u128 {
+        let Some((marketplace_id, royalty)) = [recipient_id, sender_id]
+            .into_iter()
+            .find_map(|account_id| Some((*account_id, self.royalties.get(account_id)?.clone())))
+        else {
+            return 0;
+        };
+
+        let paid = Self::bps_of(amount, royalty.bps);
+        if paid > 0 {
+            let balance = self.balance_of(&royalty.creator_id).unwrap_or_default();
+            self.set_balance(
+                &royalty.creator_id,
+                balance.checked_add(paid).expect("Balance overflowed"),
+            );
+            FtEvent::Royalty {
+                marketplace_id,
+                creator_id: royalty.creator_id,
+                sender_id: *sender_id,
+                amount: paid,
+            }
+            .emit();
+        }
+        paid
+    }
+}
@@ -0,0 +1,262 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::{cross_contract, L1xFtErc20};
+
+const CHECK_TRANSFER_METHOD: &str = "check_transfer";
+
+/// A check in the transfer pipeline.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub enum TransferRule {
+    MaxTxAmount,
+    Freeze,
+    Whitelist,
+    Jurisdiction,
+    PairPolicy,
+    /// Asks `check_transfer(sender_id, recipient_id, amount)` on `hook_id` for a rejection reason.
+    /// The hook has to be allowlisted. A failed call doesn't reject the transfer but counts
+    /// towards disabling the hook.
+    ExternalHook {
+        hook_id: Address,
+    },
+}
+
+impl TransferRule {
+    fn code(&self) -> &'static str {
+        match self {
+            TransferRule::MaxTxAmount => "MAX_TX_AMOUNT",
+            TransferRule::Freeze => "FREEZE",
+            TransferRule::Whitelist => "WHITELIST",
+            TransferRule::Jurisdiction => "JURISDICTION",
+            TransferRule::PairPolicy => "PAIR_POLICY",
+            TransferRule::ExternalHook { .. } => "EXTERNAL_HOOK",
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TransferRuleEntry {
+    rule: TransferRule,
+    enabled: bool,
+}
+
+impl TransferRuleEntry {
+    pub(crate) fn rule(&self) -> &TransferRule {
+        &self.rule
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TransferRuleFailure {
+    index: u32,
+    code: String,
+    reason: String,
+}
+
+pub(crate) fn default_transfer_rules() -> Vec<TransferRuleEntry> {
+    [
+        TransferRule::MaxTxAmount,
+        TransferRule::Freeze,
+        TransferRule::Jurisdiction,
+    ]
+    .into_iter()
+    .map(|rule| TransferRuleEntry {
+        rule,
+        enabled: true,
+    })
+    .collect()
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Replaces the whole pipeline. Rules run in the given order and the first failure rejects the transfer.
+    pub fn set_transfer_rules(rules: Vec<TransferRuleEntry>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        for entry in &rules {
+            if let TransferRule::ExternalHook { hook_id } = &entry.rule {
+                contract.assert_hook_allowed(hook_id);
+            }
+        }
+        contract.transfer_rules = rules;
+
+        contract.save();
+    }
+
+    pub fn set_transfer_rule_enabled(index: u32, enabled: bool) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract
+            .transfer_rules
+            .get_mut(index as usize)
+            .expect("Transfer rule not found")
+            .enabled = enabled;
+
+        contract.save();
+    }
+
+    pub fn transfer_rules() -> Vec<TransferRuleEntry> {
+        let contract = Self::load();
+        contract.transfer_rules
+    }
+
+    pub fn add_to_whitelist(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.whitelist.insert(account_id, true).is_none(),
+            "This address is already whitelisted"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_from_whitelist(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.whitelist.remove(account_id).is_some(),
+            "This address is not whitelisted"
+        );
+
+        contract.save();
+    }
+
+    pub fn is_whitelisted(account_id: Address) -> bool {
+        let contract = Self::load();
+        contract.whitelist.contains_key(&account_id)
+    }
+
+    /// The first rule that would reject the transfer, if any.
+    pub fn transfer_rule_failure(
+        sender_id: Address,
+        recipient_id: Address,
+        amount: U128,
+    ) -> Option<TransferRuleFailure> {
+        let contract = Self::load();
+        contract.evaluate_transfer_rules(&sender_id, &recipient_id, amount.0, &mut Vec::new())
+    }
+
+    pub(crate) fn assert_transfer_rules(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) {
+        let mut hook_results = Vec::new();
+        if let Some(failure) =
+            self.evaluate_transfer_rules(sender_id, recipient_id, amount, &mut hook_results)
+        {
+            panic!(
+                "Transfer rejected by rule {} ({}): {}",
+                failure.index, failure.code, failure.reason
+            );
+        }
+        for (hook_id, succeeded) in hook_results {
+            self.record_hook_result(&hook_id, succeeded);
+        }
+    }
+
+    /// `hook_results` collects whether each called hook answered, for failure tracking.
+    fn evaluate_transfer_rules(
+        &self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        hook_results: &mut Vec<(Address, bool)>,
+    ) -> Option<TransferRuleFailure> {
+        self.transfer_rules
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.enabled)
+            .find_map(|(index, entry)| {
+                let reason = self.transfer_rule_violation(
+                    &entry.rule,
+                    sender_id,
+                    recipient_id,
+                    amount,
+                    hook_results,
+                )?;
+                Some(TransferRuleFailure {
+                    index: index as u32,
+                    code: entry.rule.code().to_string(),
+                    reason,
+                })
+            })
+    }
+
+    fn transfer_rule_violation(
+        &self,
+        rule: &TransferRule,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        hook_results: &mut Vec<(Address, bool)>,
+    ) -> Option<String> {
+        match rule {
+            TransferRule::MaxTxAmount => self.max_tx_violation(sender_id, recipient_id, amount),
+            TransferRule::Freeze => {
+                let balance = self.balance_of(sender_id).unwrap_or_default();
+                let unfrozen = balance.saturating_sub(self.frozen_amount(sender_id));
+                (amount > unfrozen).then(|| "Amount exceeds the unfrozen balance".to_string())
+            }
+            TransferRule::Whitelist => [sender_id, recipient_id]
+                .into_iter()
+                .find(|account_id| !self.whitelist.contains_key(account_id))
+                .map(|account_id| format!("'{}' is not whitelisted", account_id)),
+            #[cfg(feature = "compliance")]
+            TransferRule::Jurisdiction => {
+                self.jurisdiction_violation(sender_id, recipient_id, amount)
+            }
+            #[cfg(not(feature = "compliance"))]
+            TransferRule::Jurisdiction => None,
+            TransferRule::PairPolicy => self.pair_policy_violation(sender_id, recipient_id, amount),
+            TransferRule::ExternalHook { hook_id } => {
+                let gas_budget = self.transfer_hooks.get(hook_id)?.active_gas_budget()?;
+                let args = json!({
+                    "sender_id": sender_id,
+                    "recipient_id": recipient_id,
+                    "amount": U128(amount),
+                });
+                match cross_contract::view_with_gas_limit::<_, Option<String>>(
+                    *hook_id,
+                    CHECK_TRANSFER_METHOD,
+                    &args,
+                    gas_budget,
+                ) {
+                    Ok(reason) => {
+                        hook_results.push((*hook_id, true));
+                        reason
+                    }
+                    Err(err) => {
+                        l1x_sdk::msg(&format!("The transfer hook {hook_id} failed: {err}"));
+                        hook_results.push((*hook_id, false));
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
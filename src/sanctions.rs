@@ -0,0 +1,101 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U64};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::{cross_contract, L1xFtErc20};
+
+const IS_SANCTIONED_METHOD: &str = "is_sanctioned";
+
+/// Oracle consulted on every transfer. `fail_open` lets transfers through when the oracle call fails.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct SanctionsConfig {
+    oracle_id: Address,
+    fail_open: bool,
+    cache_ttl: U64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub(crate) struct SanctionsCacheEntry {
+    oracle_id: Address,
+    sanctioned: bool,
+    checked_at: u128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `None` disables the check.
+    pub fn set_sanctions_oracle(config: Option<SanctionsConfig>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        *contract.sanctions = config;
+
+        contract.save();
+    }
+
+    pub fn sanctions_config() -> Option<SanctionsConfig> {
+        let contract = Self::load();
+        contract.sanctions.into_inner()
+    }
+
+    /// Asks the oracle directly, bypassing the cache.
+    pub fn is_sanctioned(account_id: Address) -> bool {
+        let contract = Self::load();
+        let config = contract
+            .sanctions
+            .as_ref()
+            .expect("No sanctions oracle is configured");
+        cross_contract::view(
+            config.oracle_id,
+            IS_SANCTIONED_METHOD,
+            &json!({ "account_id": account_id }),
+        )
+        .unwrap_or_else(|err| panic!("Failed to query the sanctions oracle: {err}"))
+    }
+
+    pub(crate) fn assert_not_sanctioned(&mut self, account_id: &Address) {
+        let Some(config) = self.sanctions.clone() else {
+            return;
+        };
+        let now = env::block_timestamp();
+
+        let cached = self.sanctions_cache.get(account_id).and_then(|entry| {
+            let fresh = entry.oracle_id == config.oracle_id
+                && now < entry.checked_at + u128::from(config.cache_ttl.0);
+            fresh.then_some(entry.sanctioned)
+        });
+        let sanctioned = match cached {
+            Some(sanctioned) => sanctioned,
+            None => {
+                let result = cross_contract::view(
+                    config.oracle_id,
+                    IS_SANCTIONED_METHOD,
+                    &json!({ "account_id": account_id }),
+                );
+                match result {
+                    Ok(sanctioned) => {
+                        self.sanctions_cache.insert(
+                            *account_id,
+                            SanctionsCacheEntry {
+                                oracle_id: config.oracle_id,
+                                sanctioned,
+                                checked_at: now,
+                            },
+                        );
+                        sanctioned
+                    }
+                    Err(_) if config.fail_open => false,
+                    Err(err) => panic!("Failed to query the sanctions oracle: {err}"),
+                }
+            }
+        };
+        assert!(!sanctioned, "'{}' is sanctioned", account_id);
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MAX_SETTLEMENT_ID_LEN: usize = 64;
+
+pub(crate) type SettlementIds = BTreeSet<String>;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct SettlementConfig {
+    coordinator_id: Address,
+    timeout: U64,
+}
+
+/// A prepared transfer. The amount stays in the sender's balance but can't be spent until the
+/// coordinator commits or aborts it, or it times out.
+///
+/// Settlement ids are scoped by sender, so an id can't be taken by someone else first.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct PreparedTransfer {
+    sender_id: Address,
+    recipient_id: Address,
+    amount: U128,
+    expires_at: U128,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Sets the account that commits and aborts prepared transfers, and how long a prepared
+    /// transfer waits for it before it's aborted on its own.
+    pub fn set_settlement_coordinator(coordinator_id: Address, timeout: U64) {
+        assert_ne!(timeout.0, 0, "Timeout should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        *contract.settlement_config = Some(SettlementConfig {
+            coordinator_id,
+            timeout,
+        });
+
+        contract.save();
+    }
+
+    pub fn settlement_config() -> Option<SettlementConfig> {
+        let contract = Self::load();
+        contract.settlement_config.into_inner()
+    }
+
+    /// Reserves `amount` of the caller's balance for `recipient_id` under `settlement_id`.
+    pub fn prepare_transfer(recipient_id: AccountId, amount: U128, settlement_id: String) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        assert!(
+            !settlement_id.is_empty(),
+            "Settlement id should not be empty"
+        );
+        assert!(
+            settlement_id.len() <= MAX_SETTLEMENT_ID_LEN,
+            "Settlement id is longer than {MAX_SETTLEMENT_ID_LEN} bytes"
+        );
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        let sender_id = caller_address();
+        assert_ne!(sender_id, *recipient_id, "Self transfer is not allowed");
+        let config = contract
+            .settlement_config
+            .clone()
+            .expect("No settlement coordinator is set");
+        assert!(
+            !contract
+                .prepared_transfers
+                .contains_key(&(sender_id, settlement_id.clone())),
+            "Settlement id is already in use"
+        );
+        assert!(
+            contract.spendable_balance(&sender_id) >= amount.0,
+            "Not enough balance to prepare the transfer"
+        );
+
+        let expires_at = env::block_timestamp()
+            .checked_add(config.timeout.0.into())
+            .expect("Settlement expiry overflowed");
+        contract.prepared_transfers.insert(
+            (sender_id, settlement_id.clone()),
+            PreparedTransfer {
+                sender_id,
+                recipient_id: *recipient_id,
+                amount,
+                expires_at: expires_at.into(),
+            },
+        );
+        let mut settlement_ids = contract
+            .settlements_by_sender
+            .get(&sender_id)
+            .cloned()
+            .unwrap_or_default();
+        settlement_ids.insert(settlement_id.clone());
+        contract
+            .settlements_by_sender
+            .insert(sender_id, settlement_ids);
+        FtEvent::SettlementPrepared {
+            settlement_id,
+            sender_id,
+            recipient_id: *recipient_id,
+            amount: amount.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Settles the reserved amount. The coordinator names the recipient and amount it agreed to,
+    /// and the commit fails if the prepared transfer doesn't match them. The settlement is an
+    /// ordinary transfer, so the sender's withdrawal protection still applies.
+    pub fn commit_transfer(
+        sender_id: Address,
+        settlement_id: String,
+        recipient_id: Address,
+        amount: U128,
+    ) {
+        let mut contract = Self::load();
+        contract.assert_not_paused();
+        contract.assert_settlement_coordinator();
+
+        let prepared = contract.remove_prepared_transfer(&sender_id, &settlement_id);
+        assert!(
+            env::block_timestamp() < prepared.expires_at.0,
+            "The prepared transfer has timed out"
+        );
+        assert!(
+            prepared.recipient_id == recipient_id && prepared.amount == amount,
+            "The prepared transfer doesn't match the expected recipient and amount"
+        );
+        contract.transfer_or_hold(
+            &prepared.sender_id,
+            &prepared.recipient_id,
+            prepared.amount.0,
+        );
+        FtEvent::SettlementCommitted {
+            sender_id,
+            settlement_id,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Releases the reserved amount. The coordinator can abort at any time, the sender once the
+    /// transfer has timed out.
+    pub fn abort_transfer(sender_id: Address, settlement_id: String) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let prepared = contract.remove_prepared_transfer(&sender_id, &settlement_id);
+        let timed_out = env::block_timestamp() >= prepared.expires_at.0;
+        let is_coordinator = contract
+            .settlement_config
+            .as_ref()
+            .map_or(false, |config| config.coordinator_id == caller_id);
+        assert!(
+            is_coordinator || (caller_id == prepared.sender_id && timed_out),
+            "Only the coordinator, or the sender after the timeout, can abort a prepared transfer"
+        );
+        FtEvent::SettlementAborted {
+            sender_id,
+            settlement_id,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn prepared_transfer(
+        sender_id: Address,
+        settlement_id: String,
+    ) -> Option<PreparedTransfer> {
+        let contract = Self::load();
+        contract
+            .prepared_transfers
+            .get(&(sender_id, settlement_id))
+            .cloned()
+    }
+
+    pub fn prepared_balance_of(account_id: Address) -> U128 {
+        let contract = Self::load();
+        contract.prepared_amount(&account_id).into()
+    }
+
+    /// Sum of the account's prepared transfers that haven't timed out.
+    pub(crate) fn prepared_amount(&self, sender_id: &Address) -> u128 {
+        let Some(settlement_ids) = self.settlements_by_sender.get(sender_id) else {
+            return 0;
+        };
+        let now = env::block_timestamp();
+        settlement_ids
+            .iter()
+            .filter_map(|settlement_id| {
+                self.prepared_transfers
+                    .get(&(*sender_id, settlement_id.clone()))
+            })
+            .filter(|prepared| now < prepared.expires_at.0)
+            .map(|prepared| prepared.amount.0)
+            .sum()
+    }
+
+    pub(crate) fn prune_timed_out_settlements(&mut self, sender_id: &Address) -> u32 {
+        let Some(settlement_ids) = self.settlements_by_sender.get(sender_id) else {
+            return 0;
+        };
+        let now = env::block_timestamp();
+        let timed_out: Vec<String> = settlement_ids
+            .iter()
+            .filter(|settlement_id| {
+                self.prepared_transfers
+                    .get(&(*sender_id, (*settlement_id).clone()))
+                    .map_or(false, |prepared| now >= prepared.expires_at.0)
+            })
+            .cloned()
+            .collect();
+
+        for settlement_id in &timed_out {
+            self.remove_prepared_transfer(sender_id, settlement_id);
+            FtEvent::SettlementAborted {
+                sender_id: *sender_id,
+                settlement_id: settlement_id.clone(),
+            }
+            .emit();
+        }
+        timed_out.len() as u32
+    }
+
+    fn assert_settlement_coordinator(&self) {
+        let config = self
+            .settlement_config
+            .as_ref()
+            .expect("No settlement coordinator is set");
+        assert_eq!(
+            caller_address(),
+            config.coordinator_id,
+            "Only the settlement coordinator can call this function"
+        );
+    }
+
+    fn remove_prepared_transfer(
+        &mut self,
+        sender_id: &Address,
+        settlement_id: &String,
+    ) -> PreparedTransfer {
+        let prepared = self
+            .prepared_transfers
+            .remove((*sender_id, settlement_id.clone()))
+            .expect("Prepared transfer not found");
+        let mut settlement_ids = self
+            .settlements_by_sender
+            .get(sender_id)
+            .cloned()
+            .unwrap_or_default();
+        settlement_ids.remove(settlement_id);
+        if settlement_ids.is_empty() {
+            self.settlements_by_sender.remove(*sender_id);
+        } else {
+            self.settlements_by_sender
+                .insert(*sender_id, settlement_ids);
+        }
+        prepared
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{self, caller_address};
+use crate::L1xFtErc20;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct SpendingKey {
+    limit: U128,
+    spent: U128,
+    expires_at: U128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct SpendingKeys {
+    keys: BTreeMap<Address, SpendingKey>,
+}
+
+impl SpendingKeys {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        for key in self.keys.values_mut() {
+            key.limit = rescale(key.limit.0).into();
+            key.spent = rescale(key.spent.0).into();
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Lets `key_id` spend up to `limit` of the caller's tokens until `expires_at`.
+    ///
+    /// Registering an existing key again replaces it and resets what it has spent.
+    pub fn register_spending_key(key_id: AccountId, limit: U128, expires_at: U128) {
+        assert_ne!(limit.0, 0, "Limit should be greater than 0");
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+        assert_ne!(
+            owner_id, *key_id,
+            "An account can't be its own spending key"
+        );
+        assert!(
+            expires_at.0 > env::block_timestamp(),
+            "The expiry should be in the future"
+        );
+        contract.assert_no_decimals_migration();
+
+        contract.index_account(&owner_id);
+        let mut keys = contract
+            .spending_keys
+            .get(&owner_id)
+            .cloned()
+            .unwrap_or_default();
+        keys.keys.insert(
+            *key_id,
+            SpendingKey {
+                limit,
+                spent: 0.into(),
+                expires_at,
+            },
+        );
+        contract.spending_keys.insert(owner_id, keys);
+        l1x_sdk::msg(&format!(
+            "Spending key {} registered for {}",
+            *key_id, owner_id
+        ));
+
+        contract.save();
+    }
+
+    pub fn revoke_spending_key(key_id: Address) {
+        let mut contract = Self::load();
+        let owner_id = caller_address();
+
+        let mut keys = contract
+            .spending_keys
+            .get(&owner_id)
+            .cloned()
+            .unwrap_or_default();
+        assert!(
+            keys.keys.remove(&key_id).is_some(),
+            "Spending key not found"
+        );
+        contract.spending_keys.insert(owner_id, keys);
+        l1x_sdk::msg(&format!("Spending key {} revoked for {}", key_id, owner_id));
+
+        contract.save();
+    }
+
+    pub fn spending_key_of(owner_id: Address, key_id: Address) -> Option<SpendingKey> {
+        let contract = Self::load();
+        contract
+            .spending_keys
+            .get(&owner_id)
+            .and_then(|keys| keys.keys.get(&key_id).cloned())
+    }
+
+    /// Transfers from `owner_id` on their behalf. The caller must be a registered spending key.
+    pub fn ft_transfer_by_key(
+        owner_id: Address,
+        recipient_id: AccountId,
+        amount: U128,
+        idempotency_key: Option<String>,
+        reference_id: Option<String>,
+    ) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let key_id = caller_address();
+        if contract.replayed_idempotency_key(
+            &key_id,
+            idempotency_key,
+            &owner_id,
+            &recipient_id,
+            amount.0,
+        ) {
+            return;
+        }
+
+        let mut keys = contract
+            .spending_keys
+            .get(&owner_id)
+            .cloned()
+            .expect("The account has no spending keys");
+        let key = keys.keys.get_mut(&key_id).expect("Spending key not found");
+        assert!(
+            env::block_timestamp() < key.expires_at.0,
+            "The spending key has expired"
+        );
+        let spent = key
+            .spent
+            .0
+            .checked_add(amount.0)
+            .expect("amount overflowed");
+        assert!(spent <= key.limit.0, "The spending key limit is exceeded");
+        key.spent = spent.into();
+        contract.spending_keys.insert(owner_id, keys);
+
+        contract.assert_memo_not_required(&recipient_id);
+        contract.transfer_or_hold(&owner_id, &recipient_id, amount.0);
+        Self::emit_transfer_reference(&owner_id, &recipient_id, amount.0, reference_id);
+
+        contract.save();
+    }
+
+    pub(crate) fn prune_expired_spending_keys(&mut self, owner_id: &Address) -> u32 {
+        let Some(keys) = self.spending_keys.get(owner_id) else {
+            return 0;
+        };
+        let now = env::block_timestamp();
+        let mut keys = keys.clone();
+        let before = keys.keys.len();
+        keys.keys.retain(|_, key| now < key.expires_at.0);
+        let pruned = (before - keys.keys.len()) as u32;
+        if pruned == 0 {
+            return 0;
+        }
+
+        if keys.keys.is_empty() {
+            self.spending_keys.remove(*owner_id);
+        } else {
+            self.spending_keys.insert(*owner_id, keys);
+        }
+        pruned
+    }
+}
@@ -0,0 +1,595 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U256, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_instance_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::roles::Role;
+use crate::treasury::PenaltyDestination;
+use crate::L1xFtErc20;
+
+const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+const MAX_COMPOUND_INCENTIVE_BPS: u16 = 500;
+
+/// Stakers share rewards pushed in with `notify_reward`, tracked with a reward-per-share accumulator.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct StakingPool {
+    total_staked: u128,
+    reward_per_share: u128,
+    /// Shares of the auto-compound vault, which stakes under the contract's own address.
+    compound_shares: u128,
+}
+
+impl StakingPool {
+    fn accumulated(&self, amount: u128) -> u128 {
+        (U256::from(amount) * U256::from(self.reward_per_share)
+            / U256::from(REWARD_PER_SHARE_PRECISION))
+        .as_u128()
+    }
+}
+
+/// Owner-managed staking settings. Slashers are meant to be arbitration contracts.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct StakingConfig {
+    slash_destination: PenaltyDestination,
+    unbonding_period: u64,
+    compound_incentive_bps: u16,
+}
+
+/// Unstaked tokens waiting out the unbonding period. They earn nothing but can still be slashed.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct UnbondingEntry {
+    amount: U128,
+    matures_at: U128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct StakeInfo {
+    amount: u128,
+    reward_debt: u128,
+    unclaimed: u128,
+    unbonding: Vec<UnbondingEntry>,
+    /// While set, the principal is held as vault shares and `amount` stays zero.
+    auto_compound: bool,
+    compound_shares: u128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct SlashRecord {
+    slasher_id: Address,
+    amount: U128,
+    reason: String,
+    slashed_at: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StakingPoolView {
+    total_staked: U128,
+    reward_per_share: U128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StakeView {
+    amount: U128,
+    pending_rewards: U128,
+    auto_compound: bool,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn stake(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let staker_id = caller_address();
+
+        let balance = contract.balance_of(&staker_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&staker_id) >= amount.0,
+            "Not enough balance to stake"
+        );
+        contract.set_balance(&staker_id, balance - amount.0);
+
+        let mut stake = contract.settled_stake(&staker_id);
+        contract.add_active_stake(&mut stake, amount.0);
+        contract.store_stake(&staker_id, stake);
+        l1x_sdk::msg(&format!("{} staked {} tokens", staker_id, amount.0));
+
+        contract.save();
+    }
+
+    /// Starts unbonding `amount`. It can be withdrawn with `withdraw_unbonded` once mature.
+    pub fn unstake(amount: U128) -> U128 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let staker_id = caller_address();
+
+        let mut stake = contract.settled_stake(&staker_id);
+        assert!(
+            contract.active_stake(&stake) >= amount.0,
+            "Not enough stake to unstake"
+        );
+        contract.remove_active_stake(&mut stake, amount.0);
+        let matures_at = contract.start_unbonding(&mut stake, amount.0);
+        contract.store_stake(&staker_id, stake);
+        l1x_sdk::msg(&format!(
+            "{} unstaked {} tokens, withdrawable at {}",
+            staker_id, amount.0, matures_at
+        ));
+
+        contract.save();
+        matures_at.into()
+    }
+
+    /// Returns every matured unbonding entry to the caller's balance.
+    pub fn withdraw_unbonded() -> U128 {
+        let mut contract = Self::load();
+        let staker_id = caller_address();
+
+        let mut stake = contract.settled_stake(&staker_id);
+        let now = env::block_timestamp();
+        let (matured, pending): (Vec<_>, Vec<_>) = stake
+            .unbonding
+            .into_iter()
+            .partition(|entry| entry.matures_at.0 <= now);
+        let amount: u128 = matured.iter().map(|entry| entry.amount.0).sum();
+        assert_ne!(amount, 0, "Nothing to withdraw");
+        stake.unbonding = pending;
+        contract.store_stake(&staker_id, stake);
+
+        let balance = contract.balance_of(&staker_id).unwrap_or_default();
+        contract.set_balance(
+            &staker_id,
+            balance.checked_add(amount).expect("Balance overflowed"),
+        );
+
+        contract.save();
+        amount.into()
+    }
+
+    pub fn unbonding_of(staker_id: Address) -> Vec<UnbondingEntry> {
+        let contract = Self::load();
+        contract
+            .stakes
+            .get(&staker_id)
+            .map(|stake| stake.unbonding.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_unbonding_period(period: U64) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.staking_config.unbonding_period = period.0;
+
+        contract.save();
+    }
+
+    pub fn unbonding_period() -> U64 {
+        let contract = Self::load();
+        contract.staking_config.unbonding_period.into()
+    }
+
+    pub fn claim_staking_rewards() -> U128 {
+        let mut contract = Self::load();
+        let staker_id = caller_address();
+
+        let mut stake = contract.settled_stake(&staker_id);
+        let rewards = stake.unclaimed;
+        assert_ne!(rewards, 0, "No rewards to claim");
+        stake.unclaimed = 0;
+        contract.store_stake(&staker_id, stake);
+
+        let balance = contract.balance_of(&staker_id).unwrap_or_default();
+        contract.set_balance(
+            &staker_id,
+            balance.checked_add(rewards).expect("Balance overflowed"),
+        );
+
+        contract.save();
+        rewards.into()
+    }
+
+    /// Moves `amount` from the caller's balance into the pool, shared pro rata by current stakers.
+    pub fn notify_reward(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let source_id = caller_address();
+        assert_ne!(
+            contract.staking_pool.total_staked, 0,
+            "There is no stake to reward"
+        );
+
+        let balance = contract.balance_of(&source_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&source_id) >= amount.0,
+            "Not enough balance to reward"
+        );
+        contract.set_balance(&source_id, balance - amount.0);
+        contract.distribute_staking_reward(amount.0);
+        l1x_sdk::msg(&format!(
+            "{} added {} tokens of staking rewards",
+            source_id, amount.0
+        ));
+
+        contract.save();
+    }
+
+    pub fn add_slasher(slasher_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.grant_role(Role::Slasher, slasher_id),
+            "This address is already a slasher"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_slasher(slasher_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.revoke_role(Role::Slasher, &slasher_id),
+            "This address is not a slasher"
+        );
+
+        contract.save();
+    }
+
+    pub fn set_slash_destination(destination: PenaltyDestination) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.staking_config.slash_destination = destination;
+
+        contract.save();
+    }
+
+    pub fn slashers() -> Vec<Address> {
+        Self::role_members(Role::Slasher)
+    }
+
+    /// Takes `amount` out of the stake, then out of unbonding entries, and burns it or sends it to the treasury.
+    pub fn slash(staker_id: Address, amount: U128, reason: String) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let slasher_id = caller_address();
+        assert!(
+            contract.has_role(Role::Slasher, &slasher_id),
+            "Only a slasher can call this function"
+        );
+
+        let mut stake = contract.settled_stake(&staker_id);
+        let unbonding: u128 = stake.unbonding.iter().map(|entry| entry.amount.0).sum();
+        assert!(
+            contract.active_stake(&stake) + unbonding >= amount.0,
+            "Not enough stake to slash"
+        );
+        let from_stake = contract.remove_active_stake(&mut stake, amount.0);
+        let mut remaining = amount.0 - from_stake;
+        // The most recently unstaked tokens are taken first.
+        for entry in stake.unbonding.iter_mut().rev() {
+            let taken = entry.amount.0.min(remaining);
+            entry.amount.0 -= taken;
+            remaining -= taken;
+        }
+        stake.unbonding.retain(|entry| entry.amount.0 != 0);
+        contract.store_stake(&staker_id, stake);
+
+        match contract.staking_config.slash_destination {
+            PenaltyDestination::Treasury => contract.treasury_credit(amount.0),
+            PenaltyDestination::Burn => {
+                *contract.total_supply = contract
+                    .total_supply
+                    .checked_sub(amount.0)
+                    .expect("total_supply is underflowed");
+                FtEvent::Burn {
+                    account_id: staker_id,
+                    amount: amount.0,
+                }
+                .emit();
+            }
+        }
+
+        let mut history = contract
+            .slash_history
+            .get(&staker_id)
+            .cloned()
+            .unwrap_or_default();
+        history.push(SlashRecord {
+            slasher_id,
+            amount,
+            reason: reason.clone(),
+            slashed_at: env::block_timestamp().into(),
+        });
+        contract.slash_history.insert(staker_id, history);
+        FtEvent::StakeSlashed {
+            staker_id,
+            slasher_id,
+            amount: amount.0,
+            reason,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    pub fn slash_history_of(staker_id: Address) -> Vec<SlashRecord> {
+        let contract = Self::load();
+        contract
+            .slash_history
+            .get(&staker_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// While enabled, rewards are restaked instead of waiting to be claimed.
+    pub fn set_auto_compound(enabled: bool) {
+        let mut contract = Self::load();
+        let staker_id = caller_address();
+
+        let mut stake = contract.settled_stake(&staker_id);
+        assert_ne!(
+            stake.auto_compound, enabled,
+            "Auto-compound is already set to this value"
+        );
+        let principal = contract.active_stake(&stake);
+        contract.remove_active_stake(&mut stake, principal);
+        let amount = principal
+            .checked_add(std::mem::take(&mut stake.unclaimed))
+            .expect("Stake overflowed");
+        stake.auto_compound = enabled;
+        if amount > 0 {
+            contract.add_active_stake(&mut stake, amount);
+        }
+        contract.store_stake(&staker_id, stake);
+
+        contract.save();
+    }
+
+    /// Restakes the rewards of every auto-compounding staker. The caller keeps a small cut.
+    pub fn compound() -> U128 {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+
+        let incentive_bps = contract.staking_config.compound_incentive_bps;
+        let (restaked, incentive) = contract.compound_vault(incentive_bps);
+        assert_ne!(restaked + incentive, 0, "Nothing to compound");
+        if incentive > 0 {
+            let balance = contract.balance_of(&caller_id).unwrap_or_default();
+            contract.set_balance(
+                &caller_id,
+                balance.checked_add(incentive).expect("Balance overflowed"),
+            );
+        }
+
+        contract.save();
+        incentive.into()
+    }
+
+    pub fn set_compound_incentive(incentive_bps: u16) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            incentive_bps <= MAX_COMPOUND_INCENTIVE_BPS,
+            "Compound incentive can't exceed {} bps",
+            MAX_COMPOUND_INCENTIVE_BPS
+        );
+
+        contract.staking_config.compound_incentive_bps = incentive_bps;
+
+        contract.save();
+    }
+
+    pub fn compound_incentive() -> u16 {
+        let contract = Self::load();
+        contract.staking_config.compound_incentive_bps
+    }
+
+    pub fn staking_pool() -> StakingPoolView {
+        let contract = Self::load();
+        StakingPoolView {
+            total_staked: contract.staking_pool.total_staked.into(),
+            reward_per_share: contract.staking_pool.reward_per_share.into(),
+        }
+    }
+
+    pub fn stake_of(staker_id: Address) -> StakeView {
+        let contract = Self::load();
+        let stake = contract.settled_stake(&staker_id);
+        StakeView {
+            amount: contract.active_stake(&stake).into(),
+            pending_rewards: stake.unclaimed.into(),
+            auto_compound: stake.auto_compound,
+        }
+    }
+
+    /// The stake with everything accrued so far moved into `unclaimed`.
+    fn settled_stake(&self, staker_id: &Address) -> StakeInfo {
+        let mut stake = self.stakes.get(staker_id).cloned().unwrap_or_default();
+        let accrued = self.staking_pool.accumulated(stake.amount) - stake.reward_debt;
+        stake.unclaimed = stake
+            .unclaimed
+            .checked_add(accrued)
+            .expect("Rewards overflowed");
+        stake.reward_debt += accrued;
+        stake
+    }
+
+    fn store_stake(&mut self, staker_id: &Address, mut stake: StakeInfo) {
+        stake.reward_debt = self.staking_pool.accumulated(stake.amount);
+        self.stakes.insert(*staker_id, stake);
+    }
+
+    /// Queues `amount` for `withdraw_unbonded` on `staker_id`, e.g. after undelegating.
+    pub(crate) fn unbond_to(&mut self, staker_id: &Address, amount: u128) -> u128 {
+        let mut stake = self.settled_stake(staker_id);
+        let matures_at = self.start_unbonding(&mut stake, amount);
+        self.store_stake(staker_id, stake);
+        matures_at
+    }
+
+    /// Rewards earned so far by `amount` of stake, before subtracting a reward debt.
+    pub(crate) fn staking_rewards_of(&self, amount: u128) -> u128 {
+        self.staking_pool.accumulated(amount)
+    }
+
+    /// Whether `staker_id` has active or unbonding stake, unclaimed rewards or delegations.
+    pub(crate) fn has_stake(&self, staker_id: &Address) -> bool {
+        let stake = self.settled_stake(staker_id);
+        self.active_stake(&stake) != 0
+            || stake.unclaimed != 0
+            || !stake.unbonding.is_empty()
+            || self.has_delegations(staker_id)
+    }
+
+    pub(crate) fn has_stakers(&self) -> bool {
+        self.staking_pool.total_staked != 0
+    }
+
+    /// Shares `amount` pro rata between current stakers. There has to be some stake.
+    pub(crate) fn distribute_staking_reward(&mut self, amount: u128) {
+        let increment = U256::from(amount) * U256::from(REWARD_PER_SHARE_PRECISION)
+            / U256::from(self.staking_pool.total_staked);
+        self.staking_pool.reward_per_share = self
+            .staking_pool
+            .reward_per_share
+            .checked_add(increment.as_u128())
+            .expect("Reward per share overflowed");
+    }
+
+    pub(crate) fn add_pool_stake(&mut self, amount: u128) {
+        self.staking_pool.total_staked = self
+            .staking_pool
+            .total_staked
+            .checked_add(amount)
+            .expect("Total stake overflowed");
+    }
+
+    pub(crate) fn remove_pool_stake(&mut self, amount: u128) {
+        self.staking_pool.total_staked -= amount;
+    }
+
+    fn start_unbonding(&self, stake: &mut StakeInfo, amount: u128) -> u128 {
+        let matures_at = env::block_timestamp()
+            .checked_add(self.staking_config.unbonding_period.into())
+            .expect("Maturity time overflowed");
+        stake.unbonding.push(UnbondingEntry {
+            amount: amount.into(),
+            matures_at: matures_at.into(),
+        });
+        matures_at
+    }
+
+    /// Stake currently earning rewards, counting vault shares at their value.
+    fn active_stake(&self, stake: &StakeInfo) -> u128 {
+        if !stake.auto_compound {
+            return stake.amount;
+        }
+        if self.staking_pool.compound_shares == 0 {
+            return 0;
+        }
+        let vault = self.settled_stake(&contract_instance_address());
+        (U256::from(stake.compound_shares) * U256::from(vault.amount + vault.unclaimed)
+            / U256::from(self.staking_pool.compound_shares))
+        .as_u128()
+    }
+
+    fn add_active_stake(&mut self, stake: &mut StakeInfo, amount: u128) {
+        self.staking_pool.total_staked = self
+            .staking_pool
+            .total_staked
+            .checked_add(amount)
+            .expect("Total stake overflowed");
+        if !stake.auto_compound {
+            stake.amount = stake.amount.checked_add(amount).expect("Stake overflowed");
+            return;
+        }
+
+        self.compound_vault(0);
+        let vault_id = contract_instance_address();
+        let mut vault = self.settled_stake(&vault_id);
+        let shares = if self.staking_pool.compound_shares == 0 || vault.amount == 0 {
+            amount
+        } else {
+            (U256::from(amount) * U256::from(self.staking_pool.compound_shares)
+                / U256::from(vault.amount))
+            .as_u128()
+        };
+        vault.amount = vault.amount.checked_add(amount).expect("Stake overflowed");
+        self.store_stake(&vault_id, vault);
+        self.staking_pool.compound_shares += shares;
+        stake.compound_shares += shares;
+    }
+
+    /// Removes up to `amount` of active stake and returns how much was removed.
+    fn remove_active_stake(&mut self, stake: &mut StakeInfo, amount: u128) -> u128 {
+        if !stake.auto_compound {
+            let removed = stake.amount.min(amount);
+            stake.amount -= removed;
+            self.staking_pool.total_staked -= removed;
+            return removed;
+        }
+
+        self.compound_vault(0);
+        let vault_id = contract_instance_address();
+        let mut vault = self.settled_stake(&vault_id);
+        let value = self.active_stake(stake);
+        let (removed, shares) = if amount >= value {
+            (value, stake.compound_shares)
+        } else {
+            // Rounds the burned shares up so the vault never pays out more than they are worth.
+            let shares = (U256::from(amount) * U256::from(self.staking_pool.compound_shares)
+                + U256::from(vault.amount - 1))
+                / U256::from(vault.amount);
+            (amount, shares.as_u128().min(stake.compound_shares))
+        };
+        vault.amount -= removed;
+        self.store_stake(&vault_id, vault);
+        self.staking_pool.compound_shares -= shares;
+        self.staking_pool.total_staked -= removed;
+        stake.compound_shares -= shares;
+        removed
+    }
+
+    /// Restakes the vault's rewards minus `incentive_bps`. Returns the restaked amount and the cut.
+    fn compound_vault(&mut self, incentive_bps: u16) -> (u128, u128) {
+        let vault_id = contract_instance_address();
+        let mut vault = self.settled_stake(&vault_id);
+        let rewards = std::mem::take(&mut vault.unclaimed);
+        let incentive = Self::bps_of(rewards, incentive_bps);
+        let restaked = rewards - incentive;
+        vault.amount = vault
+            .amount
+            .checked_add(restaked)
+            .expect("Stake overflowed");
+        self.staking_pool.total_staked = self
+            .staking_pool
+            .total_staked
+            .checked_add(restaked)
+            .expect("Total stake overflowed");
+        self.store_stake(&vault_id, vault);
+        (restaked, incentive)
+    }
+}
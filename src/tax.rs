@@ -0,0 +1,147 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::{FtEvent, TaxKind};
+use crate::L1xFtErc20;
+
+const MAX_TAX_BPS: u16 = 2_500;
+
+/// Tax rates applied in `transfer()`. The tax is split by the fee routing table.
+///
+/// A transfer out of a registered AMM pair is a buy, into one is a sell.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct TaxConfig {
+    buy_bps: u16,
+    sell_bps: u16,
+    transfer_bps: u16,
+    amm_pairs: BTreeSet<Address>,
+    exempt: BTreeSet<Address>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_tax_rates(buy_bps: u16, sell_bps: u16, transfer_bps: u16) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            buy_bps <= MAX_TAX_BPS && sell_bps <= MAX_TAX_BPS && transfer_bps <= MAX_TAX_BPS,
+            "Tax rate can't exceed {MAX_TAX_BPS} bps"
+        );
+
+        contract.tax.buy_bps = buy_bps;
+        contract.tax.sell_bps = sell_bps;
+        contract.tax.transfer_bps = transfer_bps;
+
+        contract.save();
+    }
+
+    pub fn add_amm_pair(pair_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.tax.amm_pairs.insert(pair_id),
+            "This address is already an AMM pair"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_amm_pair(pair_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.tax.amm_pairs.remove(&pair_id),
+            "This address is not an AMM pair"
+        );
+
+        contract.save();
+    }
+
+    pub fn add_tax_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.tax.exempt.insert(account_id),
+            "This address is already exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_tax_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.tax.exempt.remove(&account_id),
+            "This address is not exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn tax_config() -> TaxConfig {
+        let contract = Self::load();
+        contract.tax.into_inner()
+    }
+
+    /// Routes the tax on `amount` to the fee destinations and returns the taxed amount.
+    ///
+    /// The caller is expected to have already debited `amount` from the sender.
+    pub(crate) fn apply_transfer_tax(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> u128 {
+        let config = &*self.tax;
+        if config.exempt.contains(sender_id) || config.exempt.contains(recipient_id) {
+            return 0;
+        }
+
+        let (kind, tax_bps) = if config.amm_pairs.contains(sender_id) {
+            (TaxKind::Buy, config.buy_bps)
+        } else if config.amm_pairs.contains(recipient_id) {
+            (TaxKind::Sell, config.sell_bps)
+        } else {
+            (TaxKind::Transfer, config.transfer_bps)
+        };
+
+        let tax = Self::bps_of(amount, tax_bps);
+        if tax > 0 {
+            self.route_fee(sender_id, tax);
+            FtEvent::Tax {
+                kind,
+                sender_id: *sender_id,
+                recipient_id: *recipient_id,
+                amount: tax,
+            }
+            .emit();
+        }
+        tax
+    }
+}
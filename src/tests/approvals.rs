@@ -0,0 +1,41 @@
+use l1x_sdk::types::{Address, U128};
+
+use super::host::{self, account, ok};
+use super::{account_id, setup};
+use crate::L1xFtErc20;
+
+fn approve(owner_id: Address, spender_id: Address, amount: u128) {
+    host::set_caller(owner_id);
+    ok(|| L1xFtErc20::ft_approve(account_id(spender_id), U128(amount)));
+}
+
+fn approvers_of(spender_id: Address) -> Vec<Address> {
+    let mut owners: Vec<Address> = (0..4)
+        .flat_map(|cursor| L1xFtErc20::ft_approvals_received(spender_id, cursor, 1))
+        .map(|approval| serde_json::to_value(approval).unwrap()["account_id"].clone())
+        .map(|owner_id| serde_json::from_value(owner_id).unwrap())
+        .collect();
+    owners.sort();
+    owners
+}
+
+#[test]
+fn received_approvals_follow_the_allowances() {
+    setup();
+    let spender_id = account(4);
+    for owner in 1..=3 {
+        approve(account(owner), spender_id, 10);
+    }
+    assert_eq!(
+        approvers_of(spender_id),
+        vec![account(1), account(2), account(3)]
+    );
+
+    approve(account(1), spender_id, 0);
+    assert_eq!(approvers_of(spender_id), vec![account(2), account(3)]);
+
+    approve(account(1), spender_id, 5);
+    approve(account(3), spender_id, 0);
+    approve(account(2), spender_id, 20);
+    assert_eq!(approvers_of(spender_id), vec![account(1), account(2)]);
+}
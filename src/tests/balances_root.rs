@@ -0,0 +1,117 @@
+use l1x_sdk::types::{Address, U128};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, holders, setup};
+use crate::L1xFtErc20;
+
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// Recomputes the root from a proof the way a third party would, and returns the proven balance.
+fn verify_proof(account_id: Address) -> u128 {
+    let proof = serde_json::to_value(L1xFtErc20::ft_balance_proof(account_id)).unwrap();
+    let balance: u128 = proof["balance"].as_str().unwrap().parse().unwrap();
+    let index = proof["index"].as_u64().unwrap();
+    let mut hash = sha256(&[&[0], account_id.as_bytes(), &balance.to_le_bytes()]);
+    let siblings = proof["siblings"].as_array().unwrap();
+    for (level, sibling) in siblings.iter().map(Value::as_str).enumerate() {
+        let sibling = hex::decode(sibling.unwrap()).unwrap();
+        hash = if (index >> level) & 1 == 0 {
+            sha256(&[&[1], &hash, &sibling])
+        } else {
+            sha256(&[&[1], &sibling, &hash])
+        };
+    }
+    assert_eq!(proof["root"], hex::encode(hash));
+    let root = serde_json::to_value(L1xFtErc20::balances_root()).unwrap()["root"].clone();
+    assert_eq!(proof["root"], root);
+    balance
+}
+
+fn commit() -> bool {
+    host::set_caller(owner());
+    ok(|| L1xFtErc20::commit_balances_root(100))
+}
+
+#[test]
+fn proofs_verify_against_the_committed_root() {
+    setup();
+    fails("No balances root", || {
+        L1xFtErc20::ft_balance_proof(account(1))
+    });
+    assert!(commit());
+    for holder in holders() {
+        assert_eq!(verify_proof(holder), balance(holder));
+    }
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(100), None, None));
+    assert_eq!(verify_proof(account(2)), balance(account(2)) - 100);
+    assert!(commit());
+    assert_eq!(verify_proof(account(2)), balance(account(2)));
+    fails("not in the committed root", || {
+        L1xFtErc20::ft_balance_proof(account(9))
+    });
+}
+
+#[test]
+fn proofs_are_refused_while_a_commit_is_in_progress() {
+    setup();
+    assert!(commit());
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(100), None, None));
+    host::set_caller(owner());
+    assert!(!ok(|| L1xFtErc20::commit_balances_root(1)));
+    fails("in progress", || L1xFtErc20::ft_balance_proof(account(1)));
+    assert!(commit());
+    assert_eq!(verify_proof(account(1)), balance(account(1)));
+}
+
+#[test]
+#[cfg(feature = "reflection")]
+fn reflection_fees_rehash_every_balance() {
+    setup();
+    ok(|| L1xFtErc20::set_reflection_fee(1_000));
+    assert!(commit());
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(1_000), None, None));
+    assert!(balance(account(3)) > 1_000);
+    host::set_caller(owner());
+    assert!(!ok(|| L1xFtErc20::commit_balances_root(2)));
+    host::set_caller(account(2));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(4)), U128(100), None, None));
+    assert!(commit());
+    for holder in holders() {
+        assert_eq!(verify_proof(holder), balance(holder));
+    }
+}
+
+#[test]
+fn new_accounts_join_the_root_after_a_resumable_commit() {
+    setup();
+    assert!(commit());
+    host::set_caller(account(1));
+    fails("Only the owner", || L1xFtErc20::commit_balances_root(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(5)), U128(10), None, None));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(6)), U128(20), None, None));
+
+    // Three leaves changed, so one leaf at a time takes three calls.
+    host::set_caller(owner());
+    fails("Max leaves should be greater than 0", || {
+        L1xFtErc20::commit_balances_root(0)
+    });
+    assert!(!ok(|| L1xFtErc20::commit_balances_root(1)));
+    assert!(!ok(|| L1xFtErc20::commit_balances_root(1)));
+    assert!(ok(|| L1xFtErc20::commit_balances_root(1)));
+    for account_id in holders().into_iter().chain([account(5), account(6)]) {
+        assert_eq!(verify_proof(account_id), balance(account_id));
+    }
+}
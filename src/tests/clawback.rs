@@ -0,0 +1,115 @@
+use l1x_sdk::types::{U128, U64};
+
+use super::host::{self, account, fails, ok, owner};
+use super::{balance, emitted, setup, INITIAL_BALANCE};
+use crate::events::{ClawbackAuthority, FtEvent};
+use crate::L1xFtErc20;
+
+const OFFICER: u8 = 20;
+
+fn propose(amount: u128) -> U64 {
+    host::set_caller(account(OFFICER));
+    let clawback_id = ok(|| {
+        L1xFtErc20::propose_clawback(account(1), account(2), U128(amount), "court order".into())
+    });
+    host::set_caller(owner());
+    clawback_id
+}
+
+fn record(clawback_id: U64) -> serde_json::Value {
+    serde_json::to_value(L1xFtErc20::clawback(clawback_id).unwrap()).unwrap()
+}
+
+#[test]
+fn clawback_needs_both_approvals_and_records_them() {
+    setup();
+    ok(|| L1xFtErc20::set_timelock_delay(U64(100)));
+    fails("Only a compliance officer", || {
+        L1xFtErc20::propose_clawback(account(1), account(2), U128(1), String::new())
+    });
+    fails("owner cannot be a compliance officer", || {
+        L1xFtErc20::add_compliance_officer(owner())
+    });
+    ok(|| L1xFtErc20::add_compliance_officer(account(OFFICER)));
+    ok(|| L1xFtErc20::freeze(account(1), U128(300)));
+
+    let clawback_id = propose(400);
+    assert_eq!(record(clawback_id)["status"], "Proposed");
+    assert_eq!(balance(account(1)), INITIAL_BALANCE);
+
+    host::set_caller(account(OFFICER));
+    fails("Only the owner", || {
+        L1xFtErc20::approve_clawback(clawback_id)
+    });
+    host::set_caller(owner());
+    let operation_id = ok(|| L1xFtErc20::approve_clawback(clawback_id));
+    fails("not awaiting approval", || {
+        L1xFtErc20::approve_clawback(clawback_id)
+    });
+    fails("not ready yet", || {
+        L1xFtErc20::timelock_execute(operation_id)
+    });
+    host::advance_time(100);
+    ok(|| L1xFtErc20::timelock_execute(operation_id));
+
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 400);
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 400);
+    assert_eq!(L1xFtErc20::frozen_balance_of(account(1)).0, 0);
+    let record = record(clawback_id);
+    assert_eq!(record["status"], "Executed");
+    assert_eq!(
+        record["compliance_approval"]["approver_id"],
+        serde_json::to_value(account(OFFICER)).unwrap()
+    );
+    assert_eq!(
+        record["governance_approval"]["approver_id"],
+        serde_json::to_value(owner()).unwrap()
+    );
+    for (authority, approver_id) in [
+        (ClawbackAuthority::Compliance, account(OFFICER)),
+        (ClawbackAuthority::Governance, owner()),
+    ] {
+        assert!(emitted(
+            "",
+            FtEvent::ClawbackApproved {
+                clawback_id: clawback_id.0,
+                authority,
+                approver_id,
+            }
+        ));
+    }
+}
+
+#[test]
+fn cancelled_or_orphaned_clawbacks_do_not_execute() {
+    setup();
+    ok(|| L1xFtErc20::add_compliance_officer(account(OFFICER)));
+
+    let clawback_id = propose(100);
+    let operation_id = ok(|| L1xFtErc20::approve_clawback(clawback_id));
+    host::set_caller(account(OFFICER));
+    ok(|| L1xFtErc20::cancel_clawback(clawback_id));
+    host::set_caller(owner());
+    fails("Timelock operation not found", || {
+        L1xFtErc20::timelock_execute(operation_id)
+    });
+    fails("no longer pending", || {
+        L1xFtErc20::cancel_clawback(clawback_id)
+    });
+
+    // The compliance approval lapses with the officer's role.
+    let clawback_id = propose(100);
+    let operation_id = ok(|| L1xFtErc20::approve_clawback(clawback_id));
+    ok(|| L1xFtErc20::remove_compliance_officer(account(OFFICER)));
+    fails("no longer a compliance officer", || {
+        L1xFtErc20::timelock_execute(operation_id)
+    });
+
+    ok(|| L1xFtErc20::add_compliance_officer(account(OFFICER)));
+    let clawback_id = propose(INITIAL_BALANCE + 1);
+    let operation_id = ok(|| L1xFtErc20::approve_clawback(clawback_id));
+    fails("Not enough balance to claw back", || {
+        L1xFtErc20::timelock_execute(operation_id)
+    });
+    assert_eq!(balance(account(1)), INITIAL_BALANCE);
+}
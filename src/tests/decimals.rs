@@ -0,0 +1,141 @@
+use l1x_sdk::types::{U128, U64};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, holders, setup, transfer_event_nonce, INITIAL_BALANCE};
+use crate::L1xFtErc20;
+
+/// Views keep their fields private, so tests read them as JSON.
+fn json(view: impl Serialize) -> Value {
+    serde_json::to_value(view).unwrap()
+}
+
+fn start_migration(new_decimals: u8) {
+    let operation_id = ok(|| L1xFtErc20::migrate_decimals(new_decimals));
+    ok(|| L1xFtErc20::timelock_execute(operation_id));
+}
+
+#[test]
+fn migration_rescales_balances_and_supply() {
+    setup();
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_approve(account_id(account(2)), U128(u128::MAX)));
+    host::set_caller(owner());
+    start_migration(16);
+
+    // One pass to check, one to rescale.
+    assert!(!ok(|| L1xFtErc20::process_decimals_migration(100)));
+    assert!(ok(|| L1xFtErc20::process_decimals_migration(100)));
+
+    assert_eq!(L1xFtErc20::ft_decimals(), 16);
+    for holder in holders() {
+        assert_eq!(balance(holder), INITIAL_BALANCE / 100);
+    }
+    assert_eq!(
+        L1xFtErc20::ft_total_supply().0,
+        holders().into_iter().map(balance).sum::<u128>()
+    );
+    assert_eq!(
+        L1xFtErc20::ft_allowance(account(1), account(2)).0,
+        u128::MAX / 100
+    );
+}
+
+#[test]
+fn migration_refuses_escrowed_tokens_and_can_be_aborted() {
+    setup();
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::create_claim_code("ab".repeat(32), U128(100), U128(1_000)));
+    host::set_caller(owner());
+    start_migration(16);
+
+    fails("settled first", || {
+        L1xFtErc20::process_decimals_migration(100)
+    });
+    ok(L1xFtErc20::abort_decimals_migration);
+    assert!(L1xFtErc20::decimals_migration().is_none());
+    assert_eq!(L1xFtErc20::ft_decimals(), 18);
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 100);
+}
+
+#[test]
+fn migration_cannot_be_aborted_while_rescaling() {
+    setup();
+    start_migration(16);
+    assert!(!ok(|| L1xFtErc20::process_decimals_migration(100)));
+    fails(
+        "already being rescaled",
+        L1xFtErc20::abort_decimals_migration,
+    );
+}
+
+#[test]
+fn migration_rescales_amount_settings() {
+    setup();
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(300), None, None));
+    let event_nonce = transfer_event_nonce(account(1), account(2), 300);
+    let protection = serde_json::from_value(json!({
+        "threshold": "500", "delay": "100", "guardian": null
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(protection)));
+    ok(|| L1xFtErc20::register_spending_key(account_id(account(3)), U128(400), U128(1_000)));
+    ok(|| L1xFtErc20::set_spending_budget(account_id(account(2)), U128(200), U64(100)));
+    host::set_caller(owner());
+    ok(|| L1xFtErc20::set_max_tx_amount(Some(U128(600))));
+    let dust = serde_json::from_value(json!({ "threshold": "700", "mode": "Reject" })).unwrap();
+    ok(|| L1xFtErc20::set_dust_policy(Some(dust)));
+    let limit = serde_json::from_value(json!({ "Limit": { "max_amount": "800" } })).unwrap();
+    ok(|| L1xFtErc20::set_pair_policy(Some(account(1)), None, limit));
+    start_migration(16);
+
+    assert!(!ok(|| L1xFtErc20::process_decimals_migration(100)));
+    assert!(ok(|| L1xFtErc20::process_decimals_migration(100)));
+
+    assert_eq!(
+        json(L1xFtErc20::withdrawal_protection_of(account(1)))["active"]["threshold"],
+        "5"
+    );
+    assert_eq!(
+        json(L1xFtErc20::spending_key_of(account(1), account(3)))["limit"],
+        "4"
+    );
+    assert_eq!(
+        json(L1xFtErc20::spending_budget(account(1), account(2)))["amount"],
+        "2"
+    );
+    assert_eq!(json(L1xFtErc20::transfer_limits())["max_tx_amount"], "6");
+    assert_eq!(json(L1xFtErc20::dust_policy())["threshold"], "7");
+    assert_eq!(
+        json(L1xFtErc20::pair_policy(Some(account(1)), None))["Limit"]["max_amount"],
+        "8"
+    );
+    assert_eq!(
+        json(L1xFtErc20::transfer_record(event_nonce))["amount"],
+        "3"
+    );
+}
+
+#[test]
+fn per_account_amounts_cannot_change_during_a_migration() {
+    setup();
+    start_migration(16);
+    assert!(!ok(|| L1xFtErc20::process_decimals_migration(100)));
+
+    host::set_caller(account(1));
+    fails("paused for a decimals migration", || {
+        L1xFtErc20::ft_approve(account_id(account(2)), U128(100))
+    });
+    fails("paused for a decimals migration", || {
+        L1xFtErc20::register_spending_key(account_id(account(3)), U128(400), U128(1_000))
+    });
+    fails("paused for a decimals migration", || {
+        L1xFtErc20::set_withdrawal_protection(None)
+    });
+    host::set_caller(owner());
+    fails("paused for a decimals migration", || {
+        L1xFtErc20::freeze(account(1), U128(100))
+    });
+}
@@ -0,0 +1,73 @@
+use l1x_sdk::types::{U128, U64};
+use serde_json::json;
+
+use super::host::{self, account, fails, ok, owner};
+use super::{
+    account_id, balance, metadata, namespaced_transfer_event_nonce, setup, INITIAL_BALANCE,
+};
+use crate::L1xFtErc20;
+
+#[test]
+fn events_carry_the_namespace_of_their_token() {
+    setup();
+    let holders = vec![(account_id(account(1)), U128(50))];
+    let token_id = ok(|| L1xFtErc20::create_token(metadata(), holders));
+    host::set_input(Some(json!({ "_token_id": token_id })));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(10), None, None));
+
+    let transfer =
+        |namespace| namespaced_transfer_event_nonce(namespace, account(1), account(2), 10);
+    assert!(transfer("#1").is_some());
+    assert!(transfer("").is_none());
+}
+
+#[test]
+fn created_tokens_keep_their_own_balances_and_route_by_id() {
+    setup();
+    let create = |amount| {
+        let holders = vec![(account_id(account(1)), U128(amount))];
+        L1xFtErc20::create_token(metadata(), holders)
+    };
+    host::set_caller(account(1));
+    fails("Only the owner", || create(1));
+    host::set_caller(owner());
+    let first = ok(|| create(50));
+    let second = ok(|| create(70));
+    assert_eq!((first, second), (U64(1), U64(2)));
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::token_ft_transfer(first, account_id(account(2)), U128(10), None, None));
+    host::set_input(Some(json!({ "_token_id": second })));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(20), None, None));
+    host::set_input(None);
+
+    assert_eq!(balance(account(2)), INITIAL_BALANCE);
+    assert_eq!(L1xFtErc20::token_ft_balance_of(first, account(2)), U128(10));
+    assert_eq!(
+        L1xFtErc20::token_ft_balance_of(second, account(2)),
+        U128(20)
+    );
+    let supplies: Vec<_> = serde_json::to_value(L1xFtErc20::list_tokens())
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|token| token["total_supply"].clone())
+        .collect();
+    assert_eq!(supplies, [json!("50"), json!("70")]);
+
+    fails("Token not found", || {
+        L1xFtErc20::token_ft_total_supply(U64(3))
+    });
+    fails("Token not found", || {
+        L1xFtErc20::token_ft_total_supply(U64(0))
+    });
+    host::set_input(Some(json!({ "_token_id": "3" })));
+    fails("Token not found", L1xFtErc20::ft_total_supply);
+    host::set_input(Some(json!({ "_token_id": first, "_namespace": "second" })));
+    fails(
+        "both a token id and a namespace",
+        L1xFtErc20::ft_total_supply,
+    );
+}
@@ -0,0 +1,18 @@
+use l1x_sdk::types::U128;
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, setup, INITIAL_BALANCE};
+use crate::L1xFtErc20;
+
+#[test]
+fn pause_blocks_hold_capture() {
+    setup();
+    host::set_caller(account(1));
+    let hold_id = ok(|| L1xFtErc20::authorize(account_id(account(2)), U128(100), U128(1_000)));
+    host::set_caller(owner());
+    ok(L1xFtErc20::pause);
+
+    host::set_caller(account(2));
+    fails("paused", || L1xFtErc20::capture(hold_id, U128(100)));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE);
+}
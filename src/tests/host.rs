@@ -0,0 +1,343 @@
+//! An in-process stand-in for the L1X host. [`MemoryEnv`] serves the contract's [`Env`] and the
+//! `l1x_sys` imports serve what the SDK does itself, both over the same state. Storage,
+//! registers, the caller and the clock live in thread locals, so every test thread runs against
+//! its own chain.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+use l1x_sdk::types::Address;
+
+use crate::env::{self, Env};
+use crate::{events, namespace};
+
+const OWNER: [u8; 20] = [0x0a; 20];
+const INSTANCE: [u8; 20] = [0xc0; 20];
+
+pub fn owner() -> Address {
+    OWNER.into()
+}
+
+/// Test accounts, distinct from the owner and from each other.
+pub fn account(n: u8) -> Address {
+    [n; 20].into()
+}
+
+#[derive(Default)]
+struct Host {
+    storage: BTreeMap<Vec<u8>, Vec<u8>>,
+    registers: HashMap<u64, Vec<u8>>,
+    caller: Option<Address>,
+    timestamp: u128,
+    input: Option<Vec<u8>>,
+    events: Vec<Vec<u8>>,
+    native_balance: u128,
+    writes: BTreeMap<Vec<u8>, u32>,
+}
+
+thread_local! {
+    static HOST: RefCell<Host> = RefCell::new(Host::default());
+}
+
+fn with_host<R>(f: impl FnOnce(&mut Host) -> R) -> R {
+    HOST.with(|host| f(&mut host.borrow_mut()))
+}
+
+/// The contract's view of the mocked host.
+struct MemoryEnv;
+
+impl Env for MemoryEnv {
+    fn caller_address(&self) -> Address {
+        with_host(|host| host.caller.unwrap_or_else(owner))
+    }
+
+    fn contract_owner_address(&self) -> Address {
+        owner()
+    }
+
+    fn contract_instance_address(&self) -> Address {
+        INSTANCE.into()
+    }
+
+    fn block_timestamp(&self) -> u128 {
+        with_host(|host| host.timestamp)
+    }
+
+    fn input(&self) -> Option<Vec<u8>> {
+        with_host(|host| host.input.clone())
+    }
+
+    fn storage_read(&self, key: &[u8]) -> Option<Vec<u8>> {
+        with_host(|host| host.storage.get(key).cloned())
+    }
+
+    fn storage_write(&self, key: &[u8], value: &[u8]) {
+        with_host(|host| {
+            host.storage.insert(key.to_vec(), value.to_vec());
+            *host.writes.entry(key.to_vec()).or_default() += 1;
+        });
+    }
+
+    fn contract_instance_balance(&self) -> u128 {
+        with_host(|host| host.native_balance)
+    }
+
+    fn transfer_to(&self, _to: &Address, amount: u128) {
+        with_host(|host| {
+            host.native_balance = host
+                .native_balance
+                .checked_sub(amount)
+                .expect("Not enough native balance")
+        });
+    }
+
+    fn transfer_from_caller(&self, amount: u128) {
+        with_host(|host| host.native_balance += amount);
+    }
+}
+
+/// Starts a fresh chain on the current thread.
+pub fn reset() {
+    with_host(|host| *host = Host::default());
+    env::set_env(Box::new(MemoryEnv));
+    events::take_pending_events();
+    namespace::unbind_namespace();
+}
+
+/// Makes `caller_id` the caller of the following calls. The contract owner calls by default.
+pub fn set_caller(caller_id: Address) {
+    with_host(|host| host.caller = Some(caller_id));
+}
+
+/// Runs `f` with `caller_id` as the caller, then gives the call back to whoever had it before.
+pub fn as_caller<R>(caller_id: Address, f: impl FnOnce() -> R) -> R {
+    let previous = with_host(|host| host.caller.replace(caller_id));
+    let result = f();
+    with_host(|host| host.caller = previous);
+    result
+}
+
+pub fn advance_time(duration: u128) {
+    with_host(|host| host.timestamp += duration);
+}
+
+/// JSON arguments of the following calls, as entrypoints that read `input()` see them.
+pub fn set_input(input: Option<serde_json::Value>) {
+    with_host(|host| host.input = input.map(|input| serde_json::to_vec(&input).unwrap()));
+}
+
+/// Native coin the contract holds, e.g. sent to its address by mistake.
+pub fn set_native_balance(amount: u128) {
+    with_host(|host| host.native_balance = amount);
+}
+
+pub fn native_balance() -> u128 {
+    with_host(|host| host.native_balance)
+}
+
+/// How many times `key` was written since the chain started.
+pub fn storage_writes(key: &[u8]) -> u32 {
+    with_host(|host| host.writes.get(key).copied().unwrap_or_default())
+}
+
+pub fn remove_storage(key: &[u8]) {
+    with_host(|host| host.storage.remove(key));
+}
+
+/// Events emitted so far, in their Borsh encoding.
+pub fn events() -> Vec<Vec<u8>> {
+    with_host(|host| host.events.clone())
+}
+
+/// Runs one call as its own transaction: a panic reverts everything the call wrote and is
+/// returned as the error.
+pub fn call<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    let (storage, emitted) = with_host(|host| (host.storage.clone(), host.events.len()));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    result.map_err(|payload| {
+        with_host(|host| {
+            host.storage = storage;
+            host.events.truncate(emitted);
+        });
+        events::take_pending_events();
+        namespace::unbind_namespace();
+        payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| {
+                payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// Like [`call`], for calls that are expected to go through.
+#[track_caller]
+pub fn ok<R>(f: impl FnOnce() -> R) -> R {
+    call(f).unwrap_or_else(|message| panic!("The call failed: {message}"))
+}
+
+/// Like [`call`], for calls that are expected to fail with a message containing `expected`.
+#[track_caller]
+pub fn fails<R>(expected: &str, f: impl FnOnce() -> R) {
+    match call(f) {
+        Ok(_) => panic!("The call should have failed with '{expected}'"),
+        Err(message) => assert!(
+            message.contains(expected),
+            "The call failed with '{message}' instead of '{expected}'"
+        ),
+    }
+}
+
+unsafe fn bytes<'a>(ptr: u64, len: u64) -> &'a [u8] {
+    slice::from_raw_parts(ptr as *const u8, len as usize)
+}
+
+fn set_register(register_id: u64, value: Vec<u8>) {
+    with_host(|host| host.registers.insert(register_id, value));
+}
+
+#[no_mangle]
+extern "C" fn read_register(register_id: u64, result_addr: u64) {
+    with_host(|host| {
+        let value = &host.registers[&register_id];
+        unsafe {
+            slice::from_raw_parts_mut(result_addr as *mut u8, value.len()).copy_from_slice(value)
+        }
+    });
+}
+
+#[no_mangle]
+extern "C" fn register_len(register_id: u64) -> u64 {
+    with_host(|host| {
+        host.registers
+            .get(&register_id)
+            .map_or(u64::MAX, |value| value.len() as u64)
+    })
+}
+
+#[no_mangle]
+extern "C" fn storage_write(
+    key_addr: u64,
+    key_len: u64,
+    value_addr: u64,
+    value_len: u64,
+    evicted_register_id: u64,
+) -> u64 {
+    let (key, value) = unsafe { (bytes(key_addr, key_len), bytes(value_addr, value_len)) };
+    let evicted = with_host(|host| host.storage.insert(key.to_vec(), value.to_vec()));
+    match evicted {
+        Some(evicted) => {
+            set_register(evicted_register_id, evicted);
+            1
+        }
+        None => 0,
+    }
+}
+
+#[no_mangle]
+extern "C" fn storage_read(key_addr: u64, key_len: u64, register_id: u64) -> u64 {
+    let key = unsafe { bytes(key_addr, key_len) };
+    match with_host(|host| host.storage.get(key).cloned()) {
+        Some(value) => {
+            set_register(register_id, value);
+            1
+        }
+        None => 0,
+    }
+}
+
+#[no_mangle]
+extern "C" fn storage_remove(key_addr: u64, key_len: u64, register_id: u64) -> u64 {
+    let key = unsafe { bytes(key_addr, key_len) };
+    match with_host(|host| host.storage.remove(key)) {
+        Some(value) => {
+            set_register(register_id, value);
+            1
+        }
+        None => 0,
+    }
+}
+
+#[no_mangle]
+extern "C" fn output(_output_addr: u64, _output_len: u64) {}
+
+/// Reached only through [`HostEnv`](crate::env::HostEnv), which the tests replace.
+fn served_by_memory_env() -> ! {
+    eprintln!("The contract called the host directly instead of going through env");
+    std::process::abort()
+}
+
+#[no_mangle]
+extern "C" fn input(_register_id: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn contract_owner_address(_register_id: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn caller_address(_register_id: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn contract_instance_address(_register_id: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn block_timestamp(_output_addr: u64, _output_len: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn address_balance(_address_addr: u64, _address_len: u64, _register_id: u64) {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn transfer_to(_to_ptr: u64, _to_len: u64, _amount_ptr: u64, _amount_len: u64) -> u64 {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn transfer_from_caller(_amount_ptr: u64, _amount_len: u64) -> u64 {
+    served_by_memory_env()
+}
+
+#[no_mangle]
+extern "C" fn msg(_addr: u64, _len: u64) {}
+
+#[no_mangle]
+extern "C" fn emit_event_experimental(data_addr: u64, len: u64) -> u64 {
+    let event = unsafe { bytes(data_addr, len) }.to_vec();
+    with_host(|host| host.events.push(event));
+    1
+}
+
+/// There is no other contract to call, so every cross-contract call fails.
+#[no_mangle]
+extern "C" fn call_contract2(_call_addr: u64, _len: u64, register_id: u64) -> u64 {
+    set_register(register_id, b"No contracts to call in tests".to_vec());
+    0
+}
+
+#[no_mangle]
+extern "C" fn panic() -> ! {
+    eprintln!("The contract aborted");
+    std::process::abort()
+}
+
+#[no_mangle]
+extern "C" fn panic_msg(msg_addr: u64, msg_len: u64) -> ! {
+    let message = unsafe { bytes(msg_addr, msg_len) };
+    eprintln!("The contract aborted: {}", String::from_utf8_lossy(message));
+    std::process::abort()
+}
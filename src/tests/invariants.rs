@@ -0,0 +1,282 @@
+//! Random operation sequences against the ledger invariants.
+
+use std::collections::BTreeMap;
+
+#[cfg(feature = "locks")]
+use l1x_sdk::types::U64;
+use l1x_sdk::types::{Address, U128};
+use proptest::prelude::*;
+
+use super::host::{self, account, call, ok, owner};
+use super::{account_id, balance, holders, setup, HOLDERS, INITIAL_BALANCE};
+#[cfg(feature = "locks")]
+use crate::treasury::PenaltyDestination;
+use crate::L1xFtErc20;
+
+#[derive(Clone, Debug)]
+enum Op {
+    Transfer {
+        from: u8,
+        to: u8,
+        amount: u128,
+    },
+    Approve {
+        owner: u8,
+        spender: u8,
+        amount: u128,
+    },
+    TransferFrom {
+        spender: u8,
+        owner: u8,
+        to: u8,
+        amount: u128,
+    },
+    Mint {
+        to: u8,
+        amount: u128,
+    },
+    Freeze {
+        account: u8,
+        amount: u128,
+    },
+    Unfreeze {
+        account: u8,
+    },
+    #[cfg_attr(not(feature = "locks"), allow(dead_code))]
+    Lock {
+        account: u8,
+        amount: u128,
+    },
+    #[cfg_attr(not(feature = "locks"), allow(dead_code))]
+    Unlock {
+        account: u8,
+        index: usize,
+    },
+    Pause,
+    Unpause,
+    AdvanceTime {
+        duration: u64,
+    },
+}
+
+fn holder() -> impl Strategy<Value = u8> {
+    1..=HOLDERS
+}
+
+fn amount() -> impl Strategy<Value = u128> {
+    1..=INITIAL_BALANCE * 3 / 2
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        4 => (holder(), holder(), amount()).prop_map(|(from, to, amount)| Op::Transfer { from, to, amount }),
+        2 => (holder(), holder(), amount()).prop_map(|(owner, spender, amount)| Op::Approve { owner, spender, amount }),
+        3 => (holder(), holder(), holder(), amount())
+            .prop_map(|(spender, owner, to, amount)| Op::TransferFrom { spender, owner, to, amount }),
+        1 => (holder(), amount()).prop_map(|(to, amount)| Op::Mint { to, amount }),
+        1 => (holder(), amount()).prop_map(|(account, amount)| Op::Freeze { account, amount }),
+        1 => holder().prop_map(|account| Op::Unfreeze { account }),
+        2 => (holder(), amount()).prop_map(|(account, amount)| Op::Lock { account, amount }),
+        2 => (holder(), 0..4usize).prop_map(|(account, index)| Op::Unlock { account, index }),
+        1 => Just(Op::Pause),
+        1 => Just(Op::Unpause),
+        1 => (1..200u64).prop_map(|duration| Op::AdvanceTime { duration }),
+    ]
+}
+
+/// What the test knows about the ledger besides the contract's own state.
+#[derive(Default)]
+struct Model {
+    paused: bool,
+    frozen: BTreeMap<Address, u128>,
+    /// Open locks by account, as (lock id, amount).
+    locks: BTreeMap<Address, Vec<(u64, u128)>>,
+}
+
+impl Model {
+    fn locked(&self) -> u128 {
+        self.locks
+            .values()
+            .flatten()
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+}
+
+/// Tokens outside the balances are either locked or in the treasury.
+fn assert_supply_is_accounted_for(model: &Model) {
+    let balances: u128 = holders().into_iter().chain([owner()]).map(balance).sum();
+    let treasury = L1xFtErc20::treasury_balance().0;
+    assert_eq!(
+        balances + model.locked() + treasury,
+        L1xFtErc20::ft_total_supply().0
+    );
+}
+
+/// A frozen amount stays put: the account never ends a call below it after moving funds out.
+fn assert_frozen_respected(model: &Model, account_id: Address) {
+    let frozen = model.frozen.get(&account_id).copied().unwrap_or_default();
+    assert!(balance(account_id) >= frozen);
+}
+
+fn run(op: &Op, model: &mut Model) {
+    match *op {
+        Op::Transfer { from, to, amount } => {
+            let (from, to) = (account(from), account(to));
+            let before = (balance(from), balance(to));
+            host::set_caller(from);
+            let result = call(|| L1xFtErc20::ft_transfer(account_id(to), U128(amount), None, None));
+            if model.paused {
+                assert!(result.is_err());
+            }
+            if result.is_ok() {
+                assert_eq!(balance(from), before.0 - amount);
+                assert_eq!(balance(to), before.1 + amount);
+                assert_frozen_respected(model, from);
+            } else {
+                assert_eq!((balance(from), balance(to)), before);
+            }
+        }
+        Op::Approve {
+            owner,
+            spender,
+            amount,
+        } => {
+            host::set_caller(account(owner));
+            let _ = call(|| L1xFtErc20::ft_approve(account_id(account(spender)), U128(amount)));
+        }
+        Op::TransferFrom {
+            spender,
+            owner,
+            to,
+            amount,
+        } => {
+            let (spender, owner, to) = (account(spender), account(owner), account(to));
+            let allowance = L1xFtErc20::ft_allowance(owner, spender).0;
+            let before = (balance(owner), balance(to));
+            host::set_caller(spender);
+            let result = call(|| {
+                L1xFtErc20::ft_transfer_from(owner, account_id(to), U128(amount), None, None)
+            });
+            if model.paused || amount > allowance {
+                assert!(result.is_err());
+            }
+            if result.is_ok() {
+                assert_eq!(
+                    L1xFtErc20::ft_allowance(owner, spender).0,
+                    allowance - amount
+                );
+                assert_eq!(balance(owner), before.0 - amount);
+                assert_eq!(balance(to), before.1 + amount);
+                assert_frozen_respected(model, owner);
+            } else {
+                assert_eq!(L1xFtErc20::ft_allowance(owner, spender).0, allowance);
+                assert_eq!((balance(owner), balance(to)), before);
+            }
+        }
+        Op::Mint { to, amount } => {
+            host::set_caller(owner());
+            let result = call(|| L1xFtErc20::ft_mint(account_id(account(to)), U128(amount)));
+            assert_eq!(result.is_err(), model.paused);
+        }
+        Op::Freeze {
+            account: id,
+            amount,
+        } => {
+            host::set_caller(owner());
+            ok(|| L1xFtErc20::freeze(account(id), U128(amount)));
+            model.frozen.insert(account(id), amount);
+        }
+        Op::Unfreeze { account: id } => {
+            host::set_caller(owner());
+            if call(|| L1xFtErc20::unfreeze(account(id))).is_ok() {
+                model.frozen.remove(&account(id));
+            }
+        }
+        #[cfg(feature = "locks")]
+        Op::Lock {
+            account: id,
+            amount,
+        } => {
+            host::set_caller(account(id));
+            let result = call(|| L1xFtErc20::lock(U128(amount), U64(100)));
+            if model.paused {
+                assert!(result.is_err());
+            }
+            if let Ok(lock_id) = result {
+                model
+                    .locks
+                    .entry(account(id))
+                    .or_default()
+                    .push((lock_id.0, amount));
+                assert_frozen_respected(model, account(id));
+            }
+        }
+        #[cfg(feature = "locks")]
+        Op::Unlock { account: id, index } => {
+            let Some(&(lock_id, amount)) = model
+                .locks
+                .get(&account(id))
+                .and_then(|locks| locks.get(index))
+            else {
+                return;
+            };
+            let before = balance(account(id));
+            host::set_caller(account(id));
+            let result = call(|| L1xFtErc20::unlock(U64(lock_id)));
+            assert_eq!(result.is_err(), model.paused);
+            if let Ok(released) = result {
+                assert!(released.0 <= amount);
+                assert_eq!(balance(account(id)), before + released.0);
+                model.locks.get_mut(&account(id)).unwrap().remove(index);
+            }
+        }
+        #[cfg(not(feature = "locks"))]
+        Op::Lock { .. } | Op::Unlock { .. } => {}
+        Op::Pause => {
+            host::set_caller(owner());
+            ok(L1xFtErc20::pause);
+            model.paused = true;
+        }
+        Op::Unpause => {
+            host::set_caller(owner());
+            ok(L1xFtErc20::unpause);
+            model.paused = false;
+        }
+        Op::AdvanceTime { duration } => host::advance_time(duration.into()),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn ledger_invariants_hold(ops in prop::collection::vec(op(), 1..40)) {
+        setup();
+        host::set_caller(owner());
+        #[cfg(feature = "locks")]
+        ok(|| L1xFtErc20::set_lock_penalty(1_000, PenaltyDestination::Burn));
+
+        let mut model = Model::default();
+        for op in &ops {
+            run(op, &mut model);
+            assert_supply_is_accounted_for(&model);
+        }
+    }
+
+    #[test]
+    fn paused_calls_change_nothing(ops in prop::collection::vec(op(), 1..20)) {
+        setup();
+        host::set_caller(owner());
+        ok(L1xFtErc20::pause);
+
+        let mut model = Model { paused: true, ..Model::default() };
+        let supply = L1xFtErc20::ft_total_supply().0;
+        for op in ops.iter().filter(|op| !matches!(op, Op::Unpause)) {
+            let balances: Vec<u128> = holders().into_iter().map(balance).collect();
+            run(op, &mut model);
+            prop_assert_eq!(holders().into_iter().map(balance).collect::<Vec<_>>(), balances);
+        }
+        prop_assert_eq!(L1xFtErc20::ft_total_supply().0, supply);
+    }
+}
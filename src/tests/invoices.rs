@@ -0,0 +1,126 @@
+use l1x_sdk::types::{U128, U64};
+use serde_json::json;
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, setup, INITIAL_BALANCE};
+use crate::L1xFtErc20;
+
+#[test]
+fn held_invoice_payment_counts_once_finalized() {
+    setup();
+    host::set_caller(account(2));
+    let invoice_id = ok(|| {
+        L1xFtErc20::create_invoice(
+            account_id(account(1)),
+            U128(100),
+            U128(1_000),
+            String::new(),
+        )
+    });
+    let status = || serde_json::to_value(L1xFtErc20::invoice(invoice_id).unwrap()).unwrap();
+
+    host::set_caller(account(1));
+    let config = serde_json::from_value(json!({
+        "threshold": "50",
+        "delay": "100",
+        "guardian": null,
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(config)));
+    ok(|| L1xFtErc20::pay_invoice(invoice_id, U128(100)));
+    assert_eq!(status()["status"], "Open");
+    assert_eq!(status()["paid"], "0");
+    fails("outstanding", || {
+        L1xFtErc20::pay_invoice(invoice_id, U128(1))
+    });
+
+    ok(|| L1xFtErc20::cancel_pending_transfer(account(1), U64(0)));
+    assert_eq!(status()["pending"], "0");
+    ok(|| L1xFtErc20::pay_invoice(invoice_id, U128(100)));
+    host::advance_time(100);
+    ok(|| L1xFtErc20::finalize_pending_transfer(account(1), U64(1)));
+    assert_eq!(status()["status"], "Paid");
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 100);
+}
+
+#[test]
+fn payer_can_decline_and_open_invoices_are_capped() {
+    setup();
+    let create = |issuer| {
+        host::set_caller(account(issuer));
+        L1xFtErc20::create_invoice(account_id(account(1)), U128(1), U128(1_000), String::new())
+    };
+    let invoice_ids: Vec<_> = (0..4).map(|_| ok(|| create(2))).collect();
+    fails("Too many open invoices to this payer", || create(2));
+    for issuer in 10..17 {
+        for _ in 0..4 {
+            ok(|| create(issuer));
+        }
+    }
+    fails("too many open invoices", || create(17));
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::cancel_invoice(invoice_ids[0]));
+    ok(|| create(2));
+}
+
+#[test]
+fn invoices_cost_a_fee_to_open_and_their_payments_pay_no_fees() {
+    setup();
+    ok(|| L1xFtErc20::set_invoice_fee(U128(5)));
+    ok(|| L1xFtErc20::set_transfer_burn(true, 1_000));
+    host::set_caller(account(10));
+    fails("invoice fee", || {
+        L1xFtErc20::create_invoice(account_id(account(1)), U128(1), U128(1_000), String::new())
+    });
+
+    host::set_caller(account(2));
+    let invoice_id = ok(|| {
+        L1xFtErc20::create_invoice(
+            account_id(account(1)),
+            U128(100),
+            U128(1_000),
+            String::new(),
+        )
+    });
+    assert_eq!(balance(account(2)), INITIAL_BALANCE - 5);
+    assert_eq!(L1xFtErc20::treasury_balance(), U128(5));
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::pay_invoice(invoice_id, U128(100)));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 100);
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 95);
+    let invoice = serde_json::to_value(L1xFtErc20::invoice(invoice_id).unwrap()).unwrap();
+    assert_eq!(invoice["status"], "Paid");
+}
+
+#[test]
+fn open_invoices_do_not_block_a_decimals_migration() {
+    setup();
+    host::set_caller(account(2));
+    let invoice_id = ok(|| {
+        L1xFtErc20::create_invoice(
+            account_id(account(1)),
+            U128(300),
+            U128(1_000),
+            String::new(),
+        )
+    });
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::pay_invoice(invoice_id, U128(100)));
+
+    host::set_caller(owner());
+    let operation_id = ok(|| L1xFtErc20::migrate_decimals(16));
+    ok(|| L1xFtErc20::timelock_execute(operation_id));
+    assert!(!ok(|| L1xFtErc20::process_decimals_migration(100)));
+    assert!(ok(|| L1xFtErc20::process_decimals_migration(100)));
+
+    assert_eq!(L1xFtErc20::outstanding_balance_of(account(1)), U128(2));
+    host::set_caller(account(1));
+    fails("exceeds the outstanding balance", || {
+        L1xFtErc20::pay_invoice(invoice_id, U128(3))
+    });
+    ok(|| L1xFtErc20::pay_invoice(invoice_id, U128(2)));
+    let invoice = serde_json::to_value(L1xFtErc20::invoice(invoice_id).unwrap()).unwrap();
+    assert_eq!(invoice["status"], "Paid");
+}
@@ -0,0 +1,49 @@
+use l1x_sdk::types::{U128, U64};
+use serde_json::json;
+
+use super::host::{self, account, fails, ok};
+use super::{account_id, balance, emitted, setup, INITIAL_BALANCE};
+use crate::events::FtEvent;
+use crate::treasury::PenaltyDestination;
+use crate::L1xFtErc20;
+
+#[test]
+fn early_unlock_burn_is_an_event() {
+    setup();
+    ok(|| L1xFtErc20::set_lock_penalty(1_000, PenaltyDestination::Burn));
+    host::set_caller(account(1));
+    let lock_id = ok(|| L1xFtErc20::lock(U128(100), U64(50)));
+
+    let released = ok(|| L1xFtErc20::unlock(lock_id));
+    assert_eq!(released.0, 90);
+    assert!(emitted(
+        "",
+        FtEvent::Burn {
+            account_id: account(1),
+            amount: 10,
+        }
+    ));
+}
+
+#[test]
+fn closing_an_account_respects_locks_and_withdrawal_protection() {
+    setup();
+    host::set_caller(account(1));
+    let lock_id = ok(|| L1xFtErc20::lock(U128(10), U64(100)));
+    fails("has locks", || {
+        L1xFtErc20::close_account(account_id(account(2)))
+    });
+    host::advance_time(100);
+    ok(|| L1xFtErc20::unlock(lock_id));
+
+    let config = serde_json::from_value(json!({
+        "threshold": "50",
+        "delay": "100",
+        "guardian": null,
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(config)));
+    ok(|| L1xFtErc20::close_account(account_id(account(2))));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE);
+    assert_eq!(L1xFtErc20::pending_transfers_of(account(1)).len(), 1);
+}
@@ -0,0 +1,110 @@
+//! Tests against the contract entrypoints, run natively over the mocked host in [`host`].
+
+mod approvals;
+mod balances_root;
+#[cfg(feature = "compliance")]
+mod clawback;
+mod decimals;
+#[cfg(feature = "factory")]
+mod factory;
+#[cfg(feature = "holds")]
+mod holds;
+mod host;
+mod invariants;
+#[cfg(feature = "invoices")]
+mod invoices;
+#[cfg(feature = "locks")]
+mod locks;
+#[cfg(feature = "names")]
+mod names;
+#[cfg(feature = "reflection")]
+mod reflection;
+mod scenarios;
+#[cfg(feature = "staking")]
+mod staking;
+
+use borsh::BorshSerialize;
+use l1x_sdk::types::{Address, U128, U64};
+
+use crate::account_id::AccountId;
+use crate::events::{FtEvent, NamespacedEvent};
+use crate::{FTMetadata, L1xFtErc20};
+
+use host::{account, ok, owner};
+
+/// Accounts holding the initial supply.
+const HOLDERS: u8 = 4;
+const INITIAL_BALANCE: u128 = 1_000;
+
+fn metadata() -> FTMetadata {
+    FTMetadata {
+        name: "Test Token".to_string(),
+        decimals: 18,
+        symbol: "TST".to_string(),
+        icon: None,
+        icon_reference: None,
+    }
+}
+
+fn account_id(address: Address) -> AccountId {
+    AccountId::try_from(address).unwrap()
+}
+
+fn holders() -> Vec<Address> {
+    (1..=HOLDERS).map(account).collect()
+}
+
+/// A fresh chain with the root token initialized and `INITIAL_BALANCE` for each holder.
+fn setup() {
+    host::reset();
+    host::set_caller(owner());
+    ok(|| {
+        L1xFtErc20::new(
+            metadata(),
+            holders().into_iter().map(account_id).collect(),
+            vec![U128(INITIAL_BALANCE); HOLDERS as usize],
+            None,
+            false,
+        )
+    });
+}
+
+fn balance(account_id: Address) -> u128 {
+    L1xFtErc20::ft_balance_of(account_id).0
+}
+
+/// Whether the token instance under `namespace` emitted `event`.
+fn emitted(namespace: &str, event: FtEvent) -> bool {
+    let event = NamespacedEvent {
+        namespace: namespace.to_string(),
+        event,
+    };
+    host::events().contains(&event.try_to_vec().unwrap())
+}
+
+/// Nonce of the `Transfer` event with these parties and amount, as clients read it to refund.
+fn transfer_event_nonce(sender_id: Address, recipient_id: Address, amount: u128) -> U64 {
+    namespaced_transfer_event_nonce("", sender_id, recipient_id, amount)
+        .expect("The transfer should have an event")
+}
+
+fn namespaced_transfer_event_nonce(
+    namespace: &str,
+    sender_id: Address,
+    recipient_id: Address,
+    amount: u128,
+) -> Option<U64> {
+    (0..host::events().len() as u64)
+        .find(|event_nonce| {
+            emitted(
+                namespace,
+                FtEvent::Transfer {
+                    event_nonce: *event_nonce,
+                    sender_id,
+                    recipient_id,
+                    amount,
+                },
+            )
+        })
+        .map(U64)
+}
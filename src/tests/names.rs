@@ -0,0 +1,91 @@
+use borsh::BorshSerialize;
+use l1x_sdk::types::U128;
+use serde_json::json;
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, metadata, setup, INITIAL_BALANCE};
+use crate::L1xFtErc20;
+
+#[test]
+fn namespaced_keys_cannot_be_written_through_root_maps() {
+    setup();
+    host::set_input(Some(json!({ "_namespace": "balances" })));
+    ok(|| {
+        L1xFtErc20::new(
+            metadata(),
+            vec![account_id(account(1))],
+            vec![U128(7)],
+            Some("balances".to_string()),
+            false,
+        )
+    });
+    let registry = serde_json::from_value(json!({ "fee": "0", "ttl": "1000" })).unwrap();
+    ok(|| L1xFtErc20::set_name_registry(Some(registry)));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::register_name("abcdefghij".to_string()));
+
+    // With a plain separator, the root balance of this address would share its storage slot
+    // with the name record above.
+    let name_key = [
+        &b":names"[..],
+        &"abcdefghij".to_string().try_to_vec().unwrap(),
+    ]
+    .concat();
+    let address: [u8; 20] = name_key.try_into().unwrap();
+    host::set_input(None);
+    ok(|| L1xFtErc20::ft_transfer(account_id(address.into()), U128(5), None, None));
+
+    host::set_input(Some(json!({ "_namespace": "balances" })));
+    assert_eq!(
+        L1xFtErc20::resolve_name("abcdefghij".to_string()),
+        Some(account(1))
+    );
+}
+
+#[test]
+fn names_cost_a_fee_resolve_until_they_expire_and_receive_transfers() {
+    setup();
+    host::set_caller(account(1));
+    fails("not enabled", || {
+        L1xFtErc20::register_name("alice".to_string())
+    });
+    host::set_caller(owner());
+    let registry = serde_json::from_value(json!({ "fee": "10", "ttl": "100" })).unwrap();
+    ok(|| L1xFtErc20::set_name_registry(Some(registry)));
+
+    host::set_caller(account(1));
+    fails("3 to 32 characters", || {
+        L1xFtErc20::register_name("al".to_string())
+    });
+    fails("lowercase letters", || {
+        L1xFtErc20::register_name("Alice".to_string())
+    });
+    ok(|| L1xFtErc20::register_name("alice".to_string()));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 10);
+    assert_eq!(L1xFtErc20::treasury_balance(), U128(10));
+
+    host::set_caller(account(2));
+    fails("already registered", || {
+        L1xFtErc20::register_name("alice".to_string())
+    });
+    fails("Only the name's owner", || {
+        L1xFtErc20::renew_name("alice".to_string())
+    });
+    ok(|| L1xFtErc20::ft_transfer_to_name("alice".to_string(), U128(25)));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE + 15);
+    fails("Name not found", || {
+        L1xFtErc20::ft_transfer_to_name("bob".to_string(), U128(1))
+    });
+
+    // Once expired the name stops resolving and anyone can take it.
+    host::advance_time(100);
+    assert_eq!(L1xFtErc20::resolve_name("alice".to_string()), None);
+    fails("The name has expired", || {
+        L1xFtErc20::ft_transfer_to_name("alice".to_string(), U128(1))
+    });
+    ok(|| L1xFtErc20::register_name("alice".to_string()));
+    assert_eq!(
+        L1xFtErc20::resolve_name("alice".to_string()),
+        Some(account(2))
+    );
+}
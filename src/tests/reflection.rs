@@ -0,0 +1,61 @@
+use l1x_sdk::types::U128;
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, holders, setup, INITIAL_BALANCE};
+use crate::L1xFtErc20;
+
+fn total_of_balances() -> u128 {
+    holders().into_iter().map(balance).sum()
+}
+
+#[test]
+fn reflection_fee_accrues_to_included_holders_only() {
+    setup();
+    ok(|| L1xFtErc20::set_reflection_fee(1_000));
+    ok(|| L1xFtErc20::exclude_from_reflection(account(4)));
+
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(500), None, None));
+    host::set_caller(owner());
+
+    assert_eq!(balance(account(4)), INITIAL_BALANCE);
+    assert!(balance(account(3)) > INITIAL_BALANCE);
+    assert!(balance(account(2)) > INITIAL_BALANCE + 450);
+    assert!(balance(account(1)) > INITIAL_BALANCE - 500);
+    // Share rounding can only lose dust, never create tokens.
+    let supply = L1xFtErc20::ft_total_supply().0;
+    assert!(total_of_balances() <= supply);
+    assert!(supply - total_of_balances() < holders().len() as u128);
+
+    // Back in, the account keeps what it had, less rounding dust, and shares the next fee.
+    ok(|| L1xFtErc20::include_in_reflection(account(4)));
+    let included_balance = balance(account(4));
+    assert!(INITIAL_BALANCE - included_balance <= 1);
+    host::set_caller(account(3));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(1)), U128(500), None, None));
+    assert!(balance(account(4)) > included_balance);
+}
+
+#[test]
+fn reflection_settings_are_owner_only_and_bounded() {
+    setup();
+    fails("can't exceed", || L1xFtErc20::set_reflection_fee(1_001));
+    ok(|| L1xFtErc20::exclude_from_reflection(account(4)));
+    fails("already excluded", || {
+        L1xFtErc20::exclude_from_reflection(account(4))
+    });
+    fails("not excluded", || {
+        L1xFtErc20::include_in_reflection(account(3))
+    });
+
+    host::set_caller(account(1));
+    fails("Only the owner", || L1xFtErc20::set_reflection_fee(100));
+    fails("Only the owner", || {
+        L1xFtErc20::exclude_from_reflection(account(1))
+    });
+
+    // Without a fee transfers are exact.
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(500), None, None));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 500);
+    assert_eq!(balance(account(3)), INITIAL_BALANCE);
+}
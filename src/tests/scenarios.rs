@@ -0,0 +1,458 @@
+use l1x_sdk::types::{Address, U128, U64};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::host::{self, account, fails, ok, owner};
+use super::{
+    account_id, balance, emitted, holders, metadata, setup, transfer_event_nonce, HOLDERS,
+    INITIAL_BALANCE,
+};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+#[test]
+fn owner_can_mint_after_initialization() {
+    setup();
+    ok(|| L1xFtErc20::ft_mint(account_id(account(1)), U128(5)));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE + 5);
+}
+
+#[test]
+fn accounts_trade_through_allowances_from_initialization() {
+    setup();
+    assert_eq!(
+        L1xFtErc20::ft_total_supply().0,
+        INITIAL_BALANCE * HOLDERS as u128
+    );
+    assert!(emitted(
+        "",
+        FtEvent::Initialized {
+            holders: HOLDERS.into(),
+            total_supply: INITIAL_BALANCE * HOLDERS as u128,
+        }
+    ));
+    for holder_id in holders() {
+        assert_eq!(balance(holder_id), INITIAL_BALANCE);
+    }
+
+    let (holder_id, spender_id, recipient_id) = (account(1), account(5), account(6));
+    host::as_caller(spender_id, || {
+        fails("Only authorized caller can mint tokens", || {
+            L1xFtErc20::ft_mint(account_id(spender_id), U128(1))
+        })
+    });
+    ok(|| L1xFtErc20::ft_mint(account_id(spender_id), U128(50)));
+    assert_eq!(balance(spender_id), 50);
+
+    host::as_caller(holder_id, || {
+        ok(|| L1xFtErc20::ft_approve(account_id(spender_id), U128(300)))
+    });
+    host::as_caller(spender_id, || {
+        ok(|| {
+            L1xFtErc20::ft_transfer_from(holder_id, account_id(recipient_id), U128(200), None, None)
+        });
+        fails("The allowance is too small", || {
+            L1xFtErc20::ft_transfer_from(holder_id, account_id(recipient_id), U128(101), None, None)
+        });
+        fails("didn't set allowance", || {
+            L1xFtErc20::ft_transfer_from(account(2), account_id(recipient_id), U128(1), None, None)
+        });
+    });
+
+    assert_eq!(L1xFtErc20::ft_allowance(holder_id, spender_id).0, 100);
+    assert_eq!(balance(holder_id), INITIAL_BALANCE - 200);
+    assert_eq!(balance(recipient_id), 200);
+    assert_eq!(
+        L1xFtErc20::ft_total_supply().0,
+        INITIAL_BALANCE * HOLDERS as u128 + 50
+    );
+}
+
+#[test]
+fn pause_stops_every_account_until_lifted() {
+    setup();
+    let (holder_id, spender_id) = (account(1), account(5));
+    host::as_caller(holder_id, || {
+        ok(|| L1xFtErc20::ft_approve(account_id(spender_id), U128(300)))
+    });
+
+    host::as_caller(holder_id, || fails("Only the owner", L1xFtErc20::pause));
+    ok(L1xFtErc20::pause);
+    for holder_id in holders() {
+        host::as_caller(holder_id, || {
+            fails("The contract is paused", || {
+                L1xFtErc20::ft_transfer(account_id(account(6)), U128(1), None, None)
+            })
+        });
+    }
+    host::as_caller(spender_id, || {
+        fails("The contract is paused", || {
+            L1xFtErc20::ft_transfer_from(holder_id, account_id(spender_id), U128(1), None, None)
+        })
+    });
+    fails("The contract is paused", || {
+        L1xFtErc20::ft_mint(account_id(spender_id), U128(1))
+    });
+
+    ok(L1xFtErc20::unpause);
+    ok(|| L1xFtErc20::pause_for(U64(100)));
+    host::advance_time(100);
+    host::as_caller(spender_id, || {
+        ok(|| {
+            L1xFtErc20::ft_transfer_from(holder_id, account_id(spender_id), U128(100), None, None)
+        })
+    });
+    assert_eq!(balance(spender_id), 100);
+    assert_eq!(L1xFtErc20::ft_allowance(holder_id, spender_id).0, 200);
+}
+
+#[test]
+fn namespaced_instance_is_separate() {
+    setup();
+    ok(|| {
+        L1xFtErc20::new(
+            metadata(),
+            vec![account_id(account(1))],
+            vec![U128(7)],
+            Some("second".to_string()),
+            false,
+        )
+    });
+    fails("already", || {
+        L1xFtErc20::new(
+            metadata(),
+            vec![account_id(account(1))],
+            vec![U128(7)],
+            Some("second".to_string()),
+            false,
+        )
+    });
+
+    assert_eq!(balance(account(1)), INITIAL_BALANCE);
+    host::set_input(Some(json!({ "_namespace": "second" })));
+    assert_eq!(balance(account(1)), 7);
+}
+
+#[test]
+fn committed_settlement_respects_withdrawal_protection() {
+    setup();
+    ok(|| L1xFtErc20::set_settlement_coordinator(owner(), U64(1_000)));
+    host::set_caller(account(1));
+    let config = serde_json::from_value(json!({
+        "threshold": "50",
+        "delay": "100",
+        "guardian": null,
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(config)));
+    ok(|| L1xFtErc20::prepare_transfer(account_id(account(2)), U128(100), "s1".to_string()));
+
+    host::set_caller(owner());
+    ok(|| L1xFtErc20::commit_transfer(account(1), "s1".to_string(), account(2), U128(100)));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE);
+    assert_eq!(L1xFtErc20::pending_transfers_of(account(1)).len(), 1);
+}
+
+#[test]
+fn settlement_ids_are_scoped_by_sender_and_commits_are_checked() {
+    setup();
+    ok(|| L1xFtErc20::set_settlement_coordinator(owner(), U64(1_000)));
+    host::set_caller(account(3));
+    ok(|| L1xFtErc20::prepare_transfer(account_id(account(4)), U128(1), "s1".to_string()));
+    fails("already in use", || {
+        L1xFtErc20::prepare_transfer(account_id(account(4)), U128(1), "s1".to_string())
+    });
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::prepare_transfer(account_id(account(2)), U128(100), "s1".to_string()));
+
+    host::set_caller(owner());
+    let commit = |recipient_id, amount| {
+        L1xFtErc20::commit_transfer(account(1), "s1".to_string(), recipient_id, U128(amount))
+    };
+    fails("doesn't match", || commit(account(4), 100));
+    fails("doesn't match", || commit(account(2), 1));
+    ok(|| commit(account(2), 100));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 100);
+    assert!(L1xFtErc20::prepared_transfer(account(3), "s1".to_string()).is_some());
+}
+
+#[test]
+fn idempotency_keys_expire_and_make_room() {
+    setup();
+    host::set_caller(account(1));
+    let transfer = |key: &str| {
+        let key = key.to_string();
+        ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(1), Some(key), None))
+    };
+    for index in 0..=256 {
+        host::advance_time(1);
+        transfer(&format!("key-{index}"));
+    }
+    assert!(L1xFtErc20::idempotency_record(account(1), "key-0".to_string()).is_none());
+    assert!(L1xFtErc20::idempotency_record(account(1), "key-1".to_string()).is_some());
+
+    host::advance_time(u128::from(crate::idempotency::DEFAULT_IDEMPOTENCY_TTL));
+    assert!(L1xFtErc20::idempotency_record(account(1), "key-256".to_string()).is_none());
+    transfer("key-256");
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 258);
+}
+
+#[test]
+fn transfers_can_only_be_refunded_within_the_window() {
+    setup();
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(10), None, None));
+    let event_nonce = transfer_event_nonce(account(1), account(2), 10);
+
+    host::set_caller(account(2));
+    ok(|| L1xFtErc20::refund(event_nonce, U128(4), String::new()));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 6);
+
+    host::advance_time(30 * 86_400);
+    assert!(L1xFtErc20::transfer_record(event_nonce).is_none());
+    fails("refund window", || {
+        L1xFtErc20::refund(event_nonce, U128(1), String::new())
+    });
+}
+
+#[test]
+fn refunds_are_exact_protected_and_not_refundable() {
+    setup();
+    ok(|| L1xFtErc20::set_transfer_burn(true, 1_000));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(100), None, None));
+    let event_nonce = transfer_event_nonce(account(1), account(2), 90);
+
+    host::set_caller(account(2));
+    let config = serde_json::from_value(json!({
+        "threshold": "50",
+        "delay": "100",
+        "guardian": null,
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(config)));
+    ok(|| L1xFtErc20::refund(event_nonce, U128(90), String::new()));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 100);
+    ok(|| L1xFtErc20::cancel_pending_transfer(account(2), U64(0)));
+    ok(|| L1xFtErc20::refund(event_nonce, U128(90), String::new()));
+
+    host::advance_time(100);
+    ok(|| L1xFtErc20::finalize_pending_transfer(account(2), U64(1)));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 10);
+    let refund_nonce = transfer_event_nonce(account(2), account(1), 90);
+
+    host::set_caller(account(1));
+    fails("Transfer not found", || {
+        L1xFtErc20::refund(refund_nonce, U128(1), String::new())
+    });
+}
+
+#[test]
+fn flagged_key_cannot_clear_its_flag_and_evacuation_takes_frozen_tokens() {
+    setup();
+    ok(|| L1xFtErc20::freeze(account(1), U128(300)));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::register_backup(account_id(account(2))));
+
+    host::set_caller(account(2));
+    ok(|| L1xFtErc20::flag_compromised(account(1)));
+    host::set_caller(account(1));
+    fails("Only the owner or the backup", || {
+        L1xFtErc20::clear_compromised_flag(account(1))
+    });
+
+    host::set_caller(account(2));
+    assert_eq!(
+        ok(|| L1xFtErc20::evacuate(account(1))),
+        U128(INITIAL_BALANCE)
+    );
+    assert_eq!(balance(account(1)), 0);
+    assert_eq!(balance(account(2)), 2 * INITIAL_BALANCE);
+    assert_eq!(L1xFtErc20::frozen_balance_of(account(1)), U128(0));
+    assert_eq!(L1xFtErc20::frozen_balance_of(account(2)), U128(300));
+}
+
+#[test]
+fn native_coin_is_rescued_after_the_timelock() {
+    setup();
+    host::set_native_balance(500);
+    assert_eq!(L1xFtErc20::rescuable_native_balance(), U128(500));
+
+    let operation_id = ok(|| L1xFtErc20::rescue_native(account_id(account(1)), U128(200)));
+    assert_eq!(host::native_balance(), 500);
+    ok(|| L1xFtErc20::timelock_execute(operation_id));
+    assert_eq!(host::native_balance(), 300);
+}
+
+#[test]
+fn native_backing_of_other_instances_is_not_rescuable() {
+    setup();
+    ok(|| {
+        L1xFtErc20::new(
+            metadata(),
+            vec![],
+            vec![],
+            Some("wrapped".to_string()),
+            false,
+        )
+    });
+    host::set_input(Some(json!({ "_namespace": "wrapped" })));
+    ok(L1xFtErc20::enable_wrapped_native);
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::deposit(U128(300)));
+    host::set_input(None);
+    host::set_native_balance(host::native_balance() + 100);
+
+    assert_eq!(L1xFtErc20::rescuable_native_balance(), U128(100));
+    host::set_caller(owner());
+    let operation_id = ok(|| L1xFtErc20::rescue_native(account_id(account(1)), U128(101)));
+    fails("Not enough rescuable native balance", || {
+        L1xFtErc20::timelock_execute(operation_id)
+    });
+}
+
+#[test]
+fn multisend_checks_the_cooldown_once_per_batch() {
+    setup();
+    ok(|| L1xFtErc20::set_transfer_cooldown(U64(100)));
+    host::set_caller(account(1));
+    let multisend = || {
+        L1xFtErc20::ft_multisend(vec![
+            (account_id(account(2)), U128(10)),
+            (account_id(account(3)), U128(20)),
+        ])
+    };
+    ok(multisend);
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 10);
+    assert_eq!(balance(account(3)), INITIAL_BALANCE + 20);
+
+    fails("cooldown", multisend);
+    host::advance_time(100);
+    ok(multisend);
+}
+
+#[test]
+fn multicall_checks_the_cooldown_once() {
+    setup();
+    ok(|| L1xFtErc20::set_transfer_cooldown(U64(100)));
+    host::set_caller(account(1));
+    let calls = serde_json::from_value(json!([
+        { "Transfer": { "recipient_id": account(2), "amount": "10", "idempotency_key": null, "reference_id": null } },
+        { "Multisend": { "transfers": [[account(3), "20"]] } },
+    ]))
+    .unwrap();
+    ok(|| L1xFtErc20::ft_multicall(calls));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 10);
+    assert_eq!(balance(account(3)), INITIAL_BALANCE + 20);
+
+    fails("cooldown", || {
+        L1xFtErc20::ft_transfer(account_id(account(2)), U128(1), None, None)
+    });
+}
+
+#[test]
+fn multicall_writes_the_state_once() {
+    setup();
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(1), Some("a".into()), None));
+    let writes = host::storage_writes(b"l1x-ft");
+    let calls = serde_json::from_value(json!([
+        { "Transfer": { "recipient_id": account(2), "amount": "1", "idempotency_key": "a", "reference_id": null } },
+        { "Transfer": { "recipient_id": account(2), "amount": "10", "idempotency_key": "b", "reference_id": null } },
+        { "Approve": { "spender_id": account(3), "amount": "5" } },
+        { "Transfer": { "recipient_id": account(2), "amount": "10", "idempotency_key": "b", "reference_id": null } },
+    ]))
+    .unwrap();
+    ok(|| L1xFtErc20::ft_multicall(calls));
+
+    assert_eq!(host::storage_writes(b"l1x-ft"), writes + 1);
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 11);
+    assert_eq!(L1xFtErc20::ft_allowance(account(1), account(3)), U128(5));
+}
+
+#[test]
+fn missing_state_sections_read_as_their_defaults() {
+    setup();
+    host::remove_storage(b"dust_policy");
+    host::remove_storage(b"limits");
+
+    assert!(L1xFtErc20::dust_policy().is_none());
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(2)), U128(10), None, None));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 10);
+}
+
+#[test]
+fn cleanup_pays_only_for_other_accounts_records_up_to_a_daily_cap() {
+    setup();
+    host::set_caller(account(4));
+    ok(|| L1xFtErc20::treasury_deposit(U128(500)));
+    host::set_caller(owner());
+    let reward =
+        serde_json::from_value(json!({ "per_record": "10", "max_per_day": "25" })).unwrap();
+    ok(|| L1xFtErc20::set_cleanup_reward(reward));
+    let zero_allowances = |owner_id| {
+        host::set_caller(owner_id);
+        for n in 1..=3 {
+            if account(n) != owner_id {
+                ok(|| L1xFtErc20::ft_approve(account_id(account(n)), U128(0)));
+            }
+        }
+    };
+
+    zero_allowances(account(1));
+    host::set_caller(account(1));
+    assert_eq!(ok(|| L1xFtErc20::cleanup(10)), 2);
+    assert_eq!(balance(account(1)), INITIAL_BALANCE);
+
+    zero_allowances(account(2));
+    zero_allowances(account(3));
+    host::set_caller(account(1));
+    assert_eq!(ok(|| L1xFtErc20::cleanup(10)), 4);
+    assert_eq!(balance(account(1)), INITIAL_BALANCE + 25);
+
+    host::advance_time(86_400);
+    zero_allowances(account(2));
+    host::set_caller(account(1));
+    assert_eq!(ok(|| L1xFtErc20::cleanup(10)), 2);
+    assert_eq!(balance(account(1)), INITIAL_BALANCE + 45);
+    assert_eq!(L1xFtErc20::treasury_balance().0, 500 - 45);
+}
+
+#[test]
+fn claim_codes_are_redeemed_through_a_commitment_past_the_creators_limits() {
+    setup();
+    ok(|| L1xFtErc20::set_transfer_cooldown(U64(100)));
+    host::set_caller(account(1));
+    let hash = hex::encode(Sha256::digest(b"secret"));
+    ok(|| L1xFtErc20::create_claim_code(hash, U128(100), U128(1_000)));
+    let protection = serde_json::from_value(json!({
+        "threshold": "10", "delay": "100", "guardian": null
+    }))
+    .unwrap();
+    ok(|| L1xFtErc20::set_withdrawal_protection(Some(protection)));
+    ok(|| L1xFtErc20::ft_transfer(account_id(account(4)), U128(1), None, None));
+
+    let commit = |recipient_id: Address| {
+        let commitment = hex::encode(Sha256::digest(
+            [b"secret".as_slice(), recipient_id.as_bytes()].concat(),
+        ));
+        ok(|| L1xFtErc20::commit_claim(commitment));
+    };
+    let redeem =
+        |recipient_id| L1xFtErc20::redeem_claim("secret".to_string(), account_id(recipient_id));
+    host::set_caller(account(2));
+    fails("Commit to the claim code", || redeem(account(2)));
+    commit(account(2));
+    fails("after the block it was committed in", || redeem(account(2)));
+
+    host::advance_time(1);
+    host::set_caller(account(3));
+    commit(account(3));
+    fails("after the block it was committed in", || redeem(account(3)));
+    host::set_caller(account(2));
+    ok(|| redeem(account(2)));
+    assert_eq!(balance(account(2)), INITIAL_BALANCE + 100);
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 101);
+    assert!(L1xFtErc20::pending_transfers_of(account(1)).is_empty());
+}
@@ -0,0 +1,174 @@
+use l1x_sdk::types::{U128, U64};
+
+use super::host::{self, account, fails, ok, owner};
+use super::{account_id, balance, setup, HOLDERS, INITIAL_BALANCE};
+use crate::treasury::PenaltyDestination;
+use crate::L1xFtErc20;
+
+/// Seven days, the notice a commission increase is given.
+const COMMISSION_NOTICE: u128 = 7 * 86_400;
+const SLASHER: u8 = 9;
+
+fn pending_rewards() -> serde_json::Value {
+    serde_json::to_value(L1xFtErc20::delegation_of(account(1), account(3))).unwrap()
+        ["pending_rewards"]
+        .clone()
+}
+
+#[test]
+fn commission_increases_are_capped_and_apply_after_a_notice() {
+    setup();
+    host::set_caller(account(3));
+    ok(|| L1xFtErc20::register_operator(1_000));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::delegate_stake(account(3), U128(100)));
+    host::set_caller(account(4));
+    ok(|| L1xFtErc20::notify_reward(U128(100)));
+
+    host::set_caller(account(3));
+    fails("Commission can't increase by more than", || {
+        L1xFtErc20::register_operator(10_000)
+    });
+    ok(|| L1xFtErc20::register_operator(2_000));
+    assert_eq!(pending_rewards(), "90");
+
+    host::set_caller(account(1));
+    assert_eq!(
+        ok(|| L1xFtErc20::claim_delegation_rewards(account(3))),
+        U128(90)
+    );
+    host::advance_time(COMMISSION_NOTICE);
+    host::set_caller(account(4));
+    ok(|| L1xFtErc20::notify_reward(U128(100)));
+    assert_eq!(pending_rewards(), "80");
+}
+
+#[test]
+fn commission_decreases_apply_right_away() {
+    setup();
+    host::set_caller(account(3));
+    ok(|| L1xFtErc20::register_operator(1_000));
+    ok(|| L1xFtErc20::register_operator(2_000));
+    ok(|| L1xFtErc20::register_operator(500));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::delegate_stake(account(3), U128(100)));
+    host::set_caller(account(4));
+    ok(|| L1xFtErc20::notify_reward(U128(100)));
+
+    host::advance_time(COMMISSION_NOTICE);
+    assert_eq!(pending_rewards(), "95");
+}
+
+#[test]
+fn accounts_with_delegations_cannot_close() {
+    setup();
+    host::set_caller(account(3));
+    ok(|| L1xFtErc20::register_operator(1_000));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::delegate_stake(account(3), U128(100)));
+
+    fails("The account has stake", || {
+        L1xFtErc20::close_account(account_id(account(2)))
+    });
+    assert_eq!(balance(account(2)), INITIAL_BALANCE);
+}
+
+#[test]
+fn slashing_takes_the_stake_then_the_latest_unbonding() {
+    setup();
+    ok(|| L1xFtErc20::set_unbonding_period(U64(100)));
+    ok(|| L1xFtErc20::add_slasher(account(SLASHER)));
+    ok(|| L1xFtErc20::set_slash_destination(PenaltyDestination::Treasury));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::stake(U128(300)));
+    ok(|| L1xFtErc20::unstake(U128(100)));
+
+    host::set_caller(account(SLASHER));
+    ok(|| L1xFtErc20::slash(account(1), U128(250), "double sign".to_string()));
+    let stake = serde_json::to_value(L1xFtErc20::stake_of(account(1))).unwrap();
+    assert_eq!(stake["amount"], "0");
+    assert_eq!(L1xFtErc20::treasury_balance(), U128(250));
+    let history = serde_json::to_value(L1xFtErc20::slash_history_of(account(1))).unwrap();
+    assert_eq!(history[0]["amount"], "250");
+    assert_eq!(history[0]["reason"], "double sign");
+
+    host::advance_time(100);
+    host::set_caller(account(1));
+    assert_eq!(ok(L1xFtErc20::withdraw_unbonded), U128(50));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE - 250);
+}
+
+#[test]
+fn only_slashers_slash_and_only_what_is_staked() {
+    setup();
+    ok(|| L1xFtErc20::add_slasher(account(SLASHER)));
+    host::set_caller(account(1));
+    ok(|| L1xFtErc20::stake(U128(100)));
+    fails("Only a slasher", || {
+        L1xFtErc20::slash(account(1), U128(1), String::new())
+    });
+
+    host::set_caller(account(SLASHER));
+    fails("Not enough stake to slash", || {
+        L1xFtErc20::slash(account(1), U128(101), String::new())
+    });
+    ok(|| L1xFtErc20::slash(account(1), U128(40), String::new()));
+    assert_eq!(
+        L1xFtErc20::ft_total_supply().0,
+        INITIAL_BALANCE * HOLDERS as u128 - 40
+    );
+
+    host::set_caller(owner());
+    ok(|| L1xFtErc20::remove_slasher(account(SLASHER)));
+    host::set_caller(account(SLASHER));
+    fails("Only a slasher", || {
+        L1xFtErc20::slash(account(1), U128(1), String::new())
+    });
+}
+
+#[test]
+fn delegations_split_rewards_with_the_operator_and_unbond_on_undelegate() {
+    setup();
+    host::set_caller(account(1));
+    fails("Operator not found", || {
+        L1xFtErc20::delegate_stake(account(3), U128(100))
+    });
+    host::set_caller(account(3));
+    fails("Commission can't exceed 100%", || {
+        L1xFtErc20::register_operator(10_001)
+    });
+    ok(|| L1xFtErc20::register_operator(1_000));
+    for delegator in [1, 2] {
+        host::set_caller(account(delegator));
+        ok(|| L1xFtErc20::delegate_stake(account(3), U128(100)));
+    }
+    let operator = || serde_json::to_value(L1xFtErc20::operator(account(3))).unwrap();
+    assert_eq!(operator()["total_delegated"], "200");
+    assert_eq!(operator()["delegators"], "2");
+
+    host::set_caller(account(4));
+    ok(|| L1xFtErc20::notify_reward(U128(200)));
+    host::set_caller(account(1));
+    assert_eq!(
+        ok(|| L1xFtErc20::claim_delegation_rewards(account(3))),
+        U128(90)
+    );
+    fails("No rewards to claim", || {
+        L1xFtErc20::claim_delegation_rewards(account(3))
+    });
+    fails("Not enough delegated stake", || {
+        L1xFtErc20::undelegate(account(3), U128(101))
+    });
+    ok(|| L1xFtErc20::undelegate(account(3), U128(100)));
+    assert_eq!(operator()["delegators"], "1");
+    assert_eq!(ok(L1xFtErc20::withdraw_unbonded), U128(100));
+    assert_eq!(balance(account(1)), INITIAL_BALANCE + 90);
+
+    // The commission settles as delegations are touched: account 2's share is still pending.
+    host::set_caller(account(3));
+    assert_eq!(ok(L1xFtErc20::claim_operator_commission), U128(10));
+    host::set_caller(account(2));
+    ok(|| L1xFtErc20::claim_delegation_rewards(account(3)));
+    host::set_caller(account(3));
+    assert_eq!(ok(L1xFtErc20::claim_operator_commission), U128(10));
+}
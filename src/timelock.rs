@@ -0,0 +1,186 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::decimals;
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// Owner actions that only take effect after the timelock delay.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub enum TimelockOperation {
+    TreasuryTransfer {
+        to: Address,
+        amount: U128,
+    },
+    SetTimelockDelay {
+        delay: U64,
+    },
+    SetMaxWallet {
+        max_balance: Option<U128>,
+    },
+    RescueNative {
+        to: Address,
+        amount: U128,
+    },
+    ResumeMinting,
+    MigrateDecimals {
+        new_decimals: u8,
+    },
+    /// The governance approval of a clawback the compliance role proposed. Executing it seizes
+    /// the tokens.
+    ApproveClawback {
+        clawback_id: U64,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TimelockEntry {
+    operation: TimelockOperation,
+    eta: U128,
+    /// The token decimals the operation's amounts are in, rescaled on execution if a decimals
+    /// migration ran in between.
+    decimals: u8,
+}
+
+impl TimelockOperation {
+    fn rescale(self, from_decimals: u8, to_decimals: u8) -> TimelockOperation {
+        let rescale =
+            |amount: U128| U128::from(decimals::rescale(from_decimals, to_decimals, amount.0));
+        match self {
+            TimelockOperation::TreasuryTransfer { to, amount } => {
+                TimelockOperation::TreasuryTransfer {
+                    to,
+                    amount: rescale(amount),
+                }
+            }
+            TimelockOperation::SetMaxWallet { max_balance } => TimelockOperation::SetMaxWallet {
+                max_balance: max_balance.map(rescale),
+            },
+            operation => operation,
+        }
+    }
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Increases the delay immediately. A shorter delay has to wait out the current one.
+    pub fn set_timelock_delay(delay: U64) -> Option<U64> {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let operation_id = if delay.0 >= contract.timelock_delay {
+            contract.timelock_delay = delay.0;
+            None
+        } else {
+            Some(contract.timelock_schedule(TimelockOperation::SetTimelockDelay { delay }))
+        };
+
+        contract.save();
+        operation_id
+    }
+
+    pub fn timelock_delay() -> U64 {
+        let contract = Self::load();
+        contract.timelock_delay.into()
+    }
+
+    pub fn timelock_operation(operation_id: U64) -> Option<TimelockEntry> {
+        let contract = Self::load();
+        contract.timelock_operations.get(&operation_id.0).cloned()
+    }
+
+    pub fn timelock_execute(operation_id: U64) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let entry = contract
+            .timelock_operations
+            .remove(operation_id.0)
+            .expect("Timelock operation not found");
+        assert!(
+            env::block_timestamp() >= entry.eta.0,
+            "Timelock operation is not ready yet"
+        );
+
+        match entry
+            .operation
+            .rescale(entry.decimals, contract.metadata.decimals)
+        {
+            TimelockOperation::TreasuryTransfer { to, amount } => {
+                contract.treasury_send(&to, amount.0)
+            }
+            TimelockOperation::SetTimelockDelay { delay } => contract.timelock_delay = delay.0,
+            TimelockOperation::SetMaxWallet { max_balance } => {
+                contract.set_max_wallet_now(max_balance)
+            }
+            TimelockOperation::RescueNative { to, amount } => {
+                contract.rescue_native_now(&to, amount.0)
+            }
+            TimelockOperation::ResumeMinting => contract.resume_minting_now(),
+            TimelockOperation::MigrateDecimals { new_decimals } => {
+                contract.start_decimals_migration(new_decimals)
+            }
+            #[cfg(feature = "compliance")]
+            TimelockOperation::ApproveClawback { clawback_id } => {
+                contract.execute_clawback(clawback_id.0, operation_id.0)
+            }
+            #[cfg(not(feature = "compliance"))]
+            TimelockOperation::ApproveClawback { .. } => panic!("Clawback not found"),
+        }
+        l1x_sdk::msg(&format!("Timelock operation {} executed", operation_id.0));
+
+        contract.save();
+    }
+
+    pub fn timelock_cancel(operation_id: U64) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract
+            .timelock_operations
+            .remove(operation_id.0)
+            .expect("Timelock operation not found");
+        l1x_sdk::msg(&format!("Timelock operation {} cancelled", operation_id.0));
+
+        contract.save();
+    }
+
+    pub(crate) fn timelock_schedule(&mut self, operation: TimelockOperation) -> U64 {
+        let operation_id = self.next_timelock_operation_id;
+        self.next_timelock_operation_id = operation_id
+            .checked_add(1)
+            .expect("Timelock operation id overflowed");
+
+        let eta = env::block_timestamp()
+            .checked_add(self.timelock_delay.into())
+            .expect("Timelock eta overflowed");
+        self.timelock_operations.insert(
+            operation_id,
+            TimelockEntry {
+                operation,
+                eta: eta.into(),
+                decimals: self.metadata.decimals,
+            },
+        );
+        l1x_sdk::msg(&format!(
+            "Timelock operation {} scheduled, eta {}",
+            operation_id, eta
+        ));
+
+        operation_id.into()
+    }
+}
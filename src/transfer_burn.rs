@@ -0,0 +1,108 @@
+use std::collections::BTreeSet;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::Address;
+use serde::{Deserialize, Serialize};
+
+use crate::env::{caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+const MAX_TRANSFER_BURN_BPS: u16 = 1_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone)]
+pub struct TransferBurnConfig {
+    enabled: bool,
+    burn_bps: u16,
+    exempt: BTreeSet<Address>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn set_transfer_burn(enabled: bool, burn_bps: u16) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            burn_bps <= MAX_TRANSFER_BURN_BPS,
+            "Burn rate can't exceed {MAX_TRANSFER_BURN_BPS} bps"
+        );
+
+        contract.transfer_burn.enabled = enabled;
+        contract.transfer_burn.burn_bps = burn_bps;
+
+        contract.save();
+    }
+
+    pub fn add_transfer_burn_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.transfer_burn.exempt.insert(account_id),
+            "This address is already exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn remove_transfer_burn_exemption(account_id: Address) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            contract.transfer_burn.exempt.remove(&account_id),
+            "This address is not exempt"
+        );
+
+        contract.save();
+    }
+
+    pub fn transfer_burn_config() -> TransferBurnConfig {
+        let contract = Self::load();
+        contract.transfer_burn.into_inner()
+    }
+
+    /// Burns the configured share of `amount` on behalf of `sender_id` and returns the burned amount.
+    ///
+    /// The caller is expected to have already debited `amount` from the sender.
+    pub(crate) fn apply_transfer_burn(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> u128 {
+        let config = &*self.transfer_burn;
+        if !config.enabled
+            || config.burn_bps == 0
+            || config.exempt.contains(sender_id)
+            || config.exempt.contains(recipient_id)
+        {
+            return 0;
+        }
+
+        let burned = Self::bps_of(amount, config.burn_bps);
+        if burned > 0 {
+            *self.total_supply = self
+                .total_supply
+                .checked_sub(burned)
+                .expect("total_supply is underflowed");
+            FtEvent::Burn {
+                account_id: *sender_id,
+                amount: burned,
+            }
+            .emit();
+        }
+        burned
+    }
+}
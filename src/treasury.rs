@@ -0,0 +1,83 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::account_id::AccountId;
+use crate::env::{caller_address, contract_owner_address};
+use crate::timelock::TimelockOperation;
+use crate::L1xFtErc20;
+
+/// Where withheld or slashed tokens go.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default, Clone, Copy)]
+pub enum PenaltyDestination {
+    #[default]
+    Burn,
+    Treasury,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    pub fn treasury_balance() -> U128 {
+        let contract = Self::load();
+        contract.treasury_balance.into()
+    }
+
+    pub fn treasury_deposit(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let sender_id = caller_address();
+
+        let sender_balance = contract.balance_of(&sender_id).unwrap_or_default();
+        assert!(
+            contract.spendable_balance(&sender_id) >= amount.0,
+            "Not enough balance to deposit"
+        );
+        contract.set_balance(&sender_id, sender_balance - amount.0);
+        contract.treasury_credit(amount.0);
+
+        contract.save();
+    }
+
+    /// Schedules a payout from the treasury. It is executed with `timelock_execute`.
+    pub fn treasury_transfer(to: AccountId, amount: U128) -> U64 {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        let operation_id =
+            contract.timelock_schedule(TimelockOperation::TreasuryTransfer { to: *to, amount });
+
+        contract.save();
+        operation_id
+    }
+
+    pub(crate) fn treasury_credit(&mut self, amount: u128) {
+        self.treasury_balance = self
+            .treasury_balance
+            .checked_add(amount)
+            .expect("Treasury balance overflowed");
+    }
+
+    pub(crate) fn treasury_send(&mut self, to: &Address, amount: u128) {
+        assert!(
+            self.treasury_balance >= amount,
+            "Not enough balance in the treasury"
+        );
+        self.treasury_balance -= amount;
+
+        let receiver_balance = self.balance_of(to).unwrap_or_default();
+        self.set_balance(
+            to,
+            receiver_balance
+                .checked_add(amount)
+                .expect("Balance overflowed"),
+        );
+
+        l1x_sdk::msg(&format!("Treasury sent {} tokens to {}", amount, to));
+    }
+}
@@ -0,0 +1,163 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::events::FtEvent;
+use crate::L1xFtErc20;
+
+/// Trips when the transfer volume over `window` exceeds `trip_multiple` times `normal_volume`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct VolumeBreakerConfig {
+    window: U64,
+    normal_volume: U128,
+    trip_multiple: u16,
+    large_transfer_threshold: U128,
+    cooldown: U64,
+}
+
+/// Sliding window counter: the previous window is weighted by how much of it still overlaps.
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct VolumeBreaker {
+    config: Option<VolumeBreakerConfig>,
+    window_start: u128,
+    current_volume: u128,
+    previous_volume: u128,
+    tripped_until: u128,
+}
+
+impl VolumeBreaker {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        if let Some(config) = &mut self.config {
+            config.normal_volume = rescale(config.normal_volume.0).into();
+            config.large_transfer_threshold = rescale(config.large_transfer_threshold.0).into();
+        }
+        self.current_volume = rescale(self.current_volume);
+        self.previous_volume = rescale(self.previous_volume);
+    }
+
+    fn roll(&mut self, window: u128, now: u128) {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed >= 2 * window {
+            self.previous_volume = 0;
+            self.current_volume = 0;
+            self.window_start = now - elapsed % window;
+        } else if elapsed >= window {
+            self.previous_volume = self.current_volume;
+            self.current_volume = 0;
+            self.window_start += window;
+        }
+    }
+
+    fn volume(&self, window: u128, now: u128) -> u128 {
+        let mut rolled = self.clone();
+        rolled.roll(window, now);
+        let remaining = window - (now - rolled.window_start);
+        rolled.previous_volume * remaining / window + rolled.current_volume
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct VolumeBreakerView {
+    config: Option<VolumeBreakerConfig>,
+    volume_in_window: U128,
+    tripped_until: Option<U128>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// `None` disables the breaker. Any configuration change starts counting from zero.
+    pub fn set_volume_breaker(config: Option<VolumeBreakerConfig>) {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        if let Some(config) = &config {
+            assert_ne!(config.window.0, 0, "Window should be greater than 0");
+            assert_ne!(
+                config.trip_multiple, 0,
+                "Trip multiple should be greater than 0"
+            );
+        }
+
+        *contract.volume_breaker = VolumeBreaker {
+            config,
+            window_start: env::block_timestamp(),
+            ..VolumeBreaker::default()
+        };
+
+        contract.save();
+    }
+
+    /// Lifts a trip early. Transfers already queued keep their delay.
+    pub fn reset_volume_breaker() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+
+        contract.volume_breaker.tripped_until = 0;
+
+        contract.save();
+    }
+
+    pub fn volume_breaker() -> VolumeBreakerView {
+        let contract = Self::load();
+        let breaker = contract.volume_breaker.into_inner();
+        let now = env::block_timestamp();
+
+        VolumeBreakerView {
+            volume_in_window: breaker
+                .config
+                .as_ref()
+                .map_or(0, |config| breaker.volume(config.window.0.into(), now))
+                .into(),
+            tripped_until: (now < breaker.tripped_until).then_some(breaker.tripped_until.into()),
+            config: breaker.config,
+        }
+    }
+
+    /// Adds a transfer to the window, tripping the breaker if the volume is abnormal.
+    pub(crate) fn record_transfer_volume(&mut self, amount: u128) {
+        let Some(config) = self.volume_breaker.config.clone() else {
+            return;
+        };
+        let window = u128::from(config.window.0);
+        let now = env::block_timestamp();
+
+        self.volume_breaker.roll(window, now);
+        self.volume_breaker.current_volume =
+            self.volume_breaker.current_volume.saturating_add(amount);
+
+        let volume = self.volume_breaker.volume(window, now);
+        let limit = config
+            .normal_volume
+            .0
+            .saturating_mul(config.trip_multiple.into());
+        if volume > limit && now >= self.volume_breaker.tripped_until {
+            let until = now.saturating_add(config.cooldown.0.into());
+            self.volume_breaker.tripped_until = until;
+            FtEvent::VolumeBreakerTripped {
+                volume,
+                limit,
+                until,
+            }
+            .emit();
+        }
+    }
+
+    /// How long a transfer of `amount` has to be queued while the breaker is tripped.
+    pub(crate) fn volume_breaker_delay(&self, amount: u128, now: u128) -> Option<u64> {
+        let config = self.volume_breaker.config.as_ref()?;
+        let tripped_until = self.volume_breaker.tripped_until;
+        if now >= tripped_until || amount <= config.large_transfer_threshold.0 {
+            return None;
+        }
+        Some(u64::try_from(tripped_until - now).unwrap_or(u64::MAX))
+    }
+}
@@ -0,0 +1,54 @@
+use l1x_sdk::contract;
+use serde_json::{json, Value};
+
+use crate::env::contract_instance_address;
+use crate::L1xFtErc20;
+
+#[contract]
+impl L1xFtErc20 {
+    /// Renders the metadata in the shape a wallet expects: `"nep141"`, `"erc20-tokenlist"` or
+    /// `"l1x-wallet"`. The icon falls back to the icon reference's URL when there is no inline icon.
+    pub fn ft_metadata_json(format: String) -> Value {
+        let contract = Self::load();
+        let total_supply = *contract.total_supply;
+        let metadata = contract.metadata.into_inner();
+        let icon_url = metadata
+            .icon_reference
+            .as_ref()
+            .map(|reference| reference.url().to_string());
+        let icon = metadata.icon.clone().or_else(|| icon_url.clone());
+
+        match format.as_str() {
+            "nep141" => json!({
+                "spec": "ft-1.0.0",
+                "name": metadata.name,
+                "symbol": metadata.symbol,
+                "icon": icon,
+                "reference": null,
+                "reference_hash": null,
+                "decimals": metadata.decimals,
+            }),
+            "erc20-tokenlist" => json!({
+                "address": contract_instance_address(),
+                "name": metadata.name,
+                "symbol": metadata.symbol,
+                "decimals": metadata.decimals,
+                "logoURI": icon,
+            }),
+            "l1x-wallet" => json!({
+                "address": contract_instance_address(),
+                "name": metadata.name,
+                "symbol": metadata.symbol,
+                "decimals": metadata.decimals,
+                "icon": metadata.icon,
+                "icon_url": icon_url,
+                "icon_sha256": metadata
+                    .icon_reference
+                    .as_ref()
+                    .map(|reference| reference.sha256()),
+                "total_supply": total_supply.to_string(),
+            }),
+            _ => panic!("Unknown metadata format '{format}'"),
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::env::{self, caller_address};
+use crate::events::FtEvent;
+use crate::{L1xFtErc20, TransferKind};
+
+/// Transfers above `threshold` wait `delay` before they can be finalized.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct ProtectionConfig {
+    threshold: U128,
+    delay: U64,
+    guardian: Option<Address>,
+}
+
+/// A change that only takes effect once the current delay has passed, so a stolen key can't
+/// switch the protection off and withdraw right away.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct ScheduledProtection {
+    config: Option<ProtectionConfig>,
+    effective_at: U128,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+pub struct WithdrawalProtection {
+    active: Option<ProtectionConfig>,
+    scheduled: Option<ScheduledProtection>,
+}
+
+impl WithdrawalProtection {
+    pub(crate) fn rescale(&mut self, rescale: impl Fn(u128) -> u128) {
+        let scheduled = self
+            .scheduled
+            .as_mut()
+            .and_then(|scheduled| scheduled.config.as_mut());
+        for config in self.active.iter_mut().chain(scheduled) {
+            config.threshold = rescale(config.threshold.0).into();
+        }
+    }
+
+    fn effective(&self, now: u128) -> Option<&ProtectionConfig> {
+        match &self.scheduled {
+            Some(scheduled) if now >= scheduled.effective_at.0 => scheduled.config.as_ref(),
+            _ => self.active.as_ref(),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct PendingTransfer {
+    recipient_id: Address,
+    amount: U128,
+    executable_at: U128,
+    kind: TransferKind,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone)]
+pub(crate) struct PendingTransfers {
+    transfers: BTreeMap<u64, PendingTransfer>,
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Enables the protection right away, or schedules a change to an existing one.
+    pub fn set_withdrawal_protection(config: Option<ProtectionConfig>) {
+        let mut contract = Self::load();
+        let account_id = caller_address();
+        let now = env::block_timestamp();
+        contract.assert_no_decimals_migration();
+
+        contract.index_account(&account_id);
+        let mut protection = contract
+            .withdrawal_protection
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default();
+        let current = protection.effective(now).cloned();
+        protection = match current {
+            None => WithdrawalProtection {
+                active: config,
+                scheduled: None,
+            },
+            Some(current) => WithdrawalProtection {
+                scheduled: Some(ScheduledProtection {
+                    config,
+                    effective_at: now
+                        .checked_add(current.delay.0.into())
+                        .expect("Timestamp overflowed")
+                        .into(),
+                }),
+                active: Some(current),
+            },
+        };
+        contract
+            .withdrawal_protection
+            .insert(account_id, protection);
+
+        contract.save();
+    }
+
+    pub fn withdrawal_protection_of(account_id: Address) -> WithdrawalProtection {
+        let contract = Self::load();
+        contract
+            .withdrawal_protection
+            .get(&account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn pending_transfers_of(sender_id: Address) -> Vec<(U64, PendingTransfer)> {
+        let contract = Self::load();
+        match contract.pending_transfers.get(&sender_id) {
+            Some(pending) => pending
+                .transfers
+                .iter()
+                .map(|(transfer_id, transfer)| ((*transfer_id).into(), transfer.clone()))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the held funds to the sender. Callable by the sender or their guardian.
+    pub fn cancel_pending_transfer(sender_id: Address, transfer_id: U64) {
+        let mut contract = Self::load();
+        let caller_id = caller_address();
+        let guardian = contract
+            .withdrawal_protection
+            .get(&sender_id)
+            .and_then(|protection| {
+                protection
+                    .effective(env::block_timestamp())
+                    .and_then(|config| config.guardian)
+            });
+        assert!(
+            caller_id == sender_id || Some(caller_id) == guardian,
+            "Only the sender or their guardian can cancel a pending transfer"
+        );
+
+        let transfer = contract.take_pending_transfer(&sender_id, transfer_id.0);
+        let sender_balance = contract.balance_of(&sender_id).unwrap_or_default();
+        contract.set_balance(
+            &sender_id,
+            sender_balance
+                .checked_add(transfer.amount.0)
+                .expect("Balance overflowed"),
+        );
+        match transfer.kind {
+            TransferKind::Refund {
+                original_event_nonce,
+            } => contract.release_refund(original_event_nonce.0, transfer.amount.0),
+            #[cfg(feature = "invoices")]
+            TransferKind::InvoicePayment { invoice_id } => {
+                contract.release_invoice_payment(invoice_id.0, transfer.amount.0)
+            }
+            _ => {}
+        }
+        FtEvent::PendingTransferCancelled {
+            sender_id,
+            transfer_id: transfer_id.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Executes a pending transfer once its delay has passed. Callable by anyone.
+    pub fn finalize_pending_transfer(sender_id: Address, transfer_id: U64) {
+        let mut contract = Self::load();
+
+        let transfer = contract.take_pending_transfer(&sender_id, transfer_id.0);
+        assert!(
+            env::block_timestamp() >= transfer.executable_at.0,
+            "The pending transfer is still delayed"
+        );
+
+        let sender_balance = contract.balance_of(&sender_id).unwrap_or_default();
+        contract.set_balance(
+            &sender_id,
+            sender_balance
+                .checked_add(transfer.amount.0)
+                .expect("Balance overflowed"),
+        );
+        contract.transfer(
+            &sender_id,
+            &transfer.recipient_id,
+            transfer.amount.0,
+            transfer.kind,
+        );
+        #[cfg(feature = "invoices")]
+        if let TransferKind::InvoicePayment { invoice_id } = transfer.kind {
+            contract.settle_invoice_payment(invoice_id.0, transfer.amount.0, true);
+        }
+        FtEvent::PendingTransferFinalized {
+            sender_id,
+            transfer_id: transfer_id.0,
+        }
+        .emit();
+
+        contract.save();
+    }
+
+    /// Transfers right away, or holds the funds if the amount is above the sender's threshold
+    /// or the volume breaker is queueing large transfers. Returns the pending transfer id if the
+    /// funds are held.
+    pub(crate) fn transfer_or_hold(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+    ) -> Option<u64> {
+        self.transfer_or_hold_as(sender_id, recipient_id, amount, TransferKind::Regular)
+    }
+
+    pub(crate) fn transfer_or_hold_as(
+        &mut self,
+        sender_id: &Address,
+        recipient_id: &Address,
+        amount: u128,
+        kind: TransferKind,
+    ) -> Option<u64> {
+        let now = env::block_timestamp();
+        let protection_delay = match self
+            .withdrawal_protection
+            .get(sender_id)
+            .and_then(|protection| protection.effective(now))
+        {
+            Some(config) if amount > config.threshold.0 => Some(config.delay.0),
+            _ => None,
+        };
+        let Some(delay) = protection_delay.max(self.volume_breaker_delay(amount, now)) else {
+            self.transfer(sender_id, recipient_id, amount, kind);
+            return None;
+        };
+
+        assert_ne!(sender_id, recipient_id, "Self transfer is not allowed");
+        self.assert_not_compromised(sender_id);
+        let sender_balance = self.balance_of(sender_id).unwrap_or_default();
+        assert!(
+            self.spendable_balance(sender_id) >= amount,
+            "Not enough balance to transfer"
+        );
+        self.set_balance(sender_id, sender_balance - amount);
+
+        let transfer_id = self.next_pending_transfer_id;
+        self.next_pending_transfer_id = transfer_id
+            .checked_add(1)
+            .expect("Pending transfer id overflowed");
+        let executable_at = now.checked_add(delay.into()).expect("Timestamp overflowed");
+
+        let mut pending = self
+            .pending_transfers
+            .get(sender_id)
+            .cloned()
+            .unwrap_or_default();
+        pending.transfers.insert(
+            transfer_id,
+            PendingTransfer {
+                recipient_id: *recipient_id,
+                amount: amount.into(),
+                executable_at: executable_at.into(),
+                kind,
+            },
+        );
+        self.pending_transfers.insert(*sender_id, pending);
+
+        FtEvent::PendingTransferCreated {
+            sender_id: *sender_id,
+            recipient_id: *recipient_id,
+            transfer_id,
+            amount,
+            executable_at,
+        }
+        .emit();
+        Some(transfer_id)
+    }
+
+    pub(crate) fn has_pending_transfers(&self, sender_id: &Address) -> bool {
+        self.pending_transfers
+            .get(sender_id)
+            .map_or(false, |pending| !pending.transfers.is_empty())
+    }
+
+    fn take_pending_transfer(&mut self, sender_id: &Address, transfer_id: u64) -> PendingTransfer {
+        let mut pending = self
+            .pending_transfers
+            .get(sender_id)
+            .cloned()
+            .expect("The account has no pending transfers");
+        let transfer = pending
+            .transfers
+            .remove(&transfer_id)
+            .expect("Pending transfer not found");
+        self.pending_transfers.insert(*sender_id, pending);
+        transfer
+    }
+}
@@ -0,0 +1,109 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use l1x_sdk::contract;
+use l1x_sdk::types::U128;
+
+use crate::env::{self, caller_address, contract_owner_address};
+use crate::L1xFtErc20;
+
+/// Native coin held against wrapped tokens by all token instances together, as they share the
+/// contract's native balance. Kept outside of any namespace.
+const STORAGE_NATIVE_RESERVE_KEY: &[u8; 14] = b"native_reserve";
+
+pub(crate) fn total_native_reserve() -> u128 {
+    env::storage_read(STORAGE_NATIVE_RESERVE_KEY)
+        .map(|bytes| u128::try_from_slice(&bytes).unwrap())
+        .unwrap_or_default()
+}
+
+fn set_total_native_reserve(reserve: u128) {
+    env::storage_write(STORAGE_NATIVE_RESERVE_KEY, &reserve.try_to_vec().unwrap());
+}
+
+#[contract]
+impl L1xFtErc20 {
+    /// Turns the contract into a wrapped native token. This can't be undone.
+    ///
+    /// Only possible before any token exists, and `ft_mint` is disabled from then on, so every
+    /// token is backed by deposited native coin.
+    pub fn enable_wrapped_native() {
+        let mut contract = Self::load();
+        assert_eq!(
+            caller_address(),
+            contract_owner_address(),
+            "Only the owner can call this function"
+        );
+        assert!(
+            !contract.wrapped_native,
+            "Wrapped native mode is already enabled"
+        );
+        assert_eq!(
+            *contract.total_supply, 0,
+            "Wrapped native mode can only be enabled before any token is minted"
+        );
+
+        contract.wrapped_native = true;
+        l1x_sdk::msg("Wrapped native mode enabled");
+
+        contract.save();
+    }
+
+    pub fn is_wrapped_native() -> bool {
+        let contract = Self::load();
+        contract.wrapped_native
+    }
+
+    /// Native coin held against this token.
+    pub fn wrapped_native_reserve() -> U128 {
+        let contract = Self::load();
+        contract.wrapped_native_reserve.into()
+    }
+
+    /// Pulls `amount` of native L1X from the caller and mints the same amount of tokens.
+    pub fn deposit(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        assert!(contract.wrapped_native, "Wrapped native mode is disabled");
+        let account_id = caller_address();
+
+        env::transfer_from_caller(amount.0);
+        contract.wrapped_native_reserve = contract
+            .wrapped_native_reserve
+            .checked_add(amount.0)
+            .expect("Wrapped native reserve overflowed");
+        set_total_native_reserve(total_native_reserve() + amount.0);
+        contract.mint(&account_id, amount.0);
+
+        contract.save();
+    }
+
+    /// Burns `amount` of the caller's tokens and sends the same amount of native L1X back.
+    pub fn withdraw(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        assert!(contract.wrapped_native, "Wrapped native mode is disabled");
+        let account_id = caller_address();
+
+        contract.burn(&account_id, amount.0);
+        contract.wrapped_native_reserve = contract
+            .wrapped_native_reserve
+            .checked_sub(amount.0)
+            .expect("Not enough native coin in the reserve");
+        set_total_native_reserve(total_native_reserve() - amount.0);
+        env::transfer_to(&account_id, amount.0);
+
+        contract.save();
+    }
+
+    /// Mints that don't bring their own backing would dilute the native reserve, or the
+    /// underlying token held by a wrapper.
+    pub(crate) fn assert_unbacked_mint_allowed(&self) {
+        assert!(
+            !self.wrapped_native,
+            "Minting is disabled in wrapped native mode"
+        );
+        assert!(
+            self.underlying.is_none(),
+            "Minting is disabled for a wrapper token"
+        );
+    }
+}
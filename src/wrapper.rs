@@ -0,0 +1,72 @@
+use l1x_sdk::contract;
+use l1x_sdk::types::{Address, U128};
+use serde_json::json;
+
+use crate::env::{caller_address, contract_instance_address, contract_owner_address};
+use crate::roles::Role;
+use crate::{cross_contract, namespace, FTMetadata, L1xFtErc20};
+
+#[contract]
+impl L1xFtErc20 {
+    /// Initializes the contract as a 1:1 wrapper over the `underlying_id` token.
+    pub fn new_wrapper(metadata: FTMetadata, underlying_id: Address) {
+        Self::assert_can_initialize(&metadata);
+
+        let mut contract = Self::initial_state(metadata, &namespace::active_namespace());
+        contract.grant_role(Role::Minter, contract_owner_address());
+        contract.underlying = Some(underlying_id);
+        contract.save();
+    }
+
+    pub fn underlying() -> Option<Address> {
+        let contract = Self::load();
+        contract.underlying
+    }
+
+    /// Pulls `amount` of the underlying token from the caller and mints the same amount.
+    ///
+    /// The caller has to approve this contract on the underlying token first.
+    pub fn wrap(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let underlying_id = contract.underlying.expect("The contract is not a wrapper");
+        let account_id = caller_address();
+
+        cross_contract::call(
+            underlying_id,
+            "ft_transfer_from",
+            &json!({
+                "sender_id": account_id,
+                "recipient_id": contract_instance_address(),
+                "amount": amount,
+            }),
+            false,
+        )
+        .unwrap_or_else(|err| panic!("Failed to pull the underlying token: {err}"));
+        contract.mint(&account_id, amount.0);
+
+        contract.save();
+    }
+
+    /// Burns `amount` of the caller's tokens and returns the same amount of the underlying token.
+    pub fn unwrap(amount: U128) {
+        assert_ne!(amount.0, 0, "Amount should be greater than 0");
+        let mut contract = Self::load();
+        let underlying_id = contract.underlying.expect("The contract is not a wrapper");
+        let account_id = caller_address();
+
+        contract.burn(&account_id, amount.0);
+        cross_contract::call(
+            underlying_id,
+            "ft_transfer",
+            &json!({
+                "recipient_id": account_id,
+                "amount": amount,
+            }),
+            false,
+        )
+        .unwrap_or_else(|err| panic!("Failed to return the underlying token: {err}"));
+
+        contract.save();
+    }
+}